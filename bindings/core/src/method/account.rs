@@ -316,6 +316,12 @@ pub enum AccountMethod {
         /// Sync options
         options: Option<SyncOptions>,
     },
+    /// Summarize the effects of a prepared transaction, to review it before signing.
+    /// Expected response: [`TransactionSummary`](crate::Response::TransactionSummary)
+    #[serde(rename_all = "camelCase")]
+    TransactionSummary {
+        prepared_transaction_data: PreparedTransactionDataDto,
+    },
     /// Returns all transaction of the account
     /// Expected response: [`Transactions`](crate::Response::Transactions)
     Transactions,
@@ -187,6 +187,9 @@ pub enum WalletMethod {
     StoreMnemonic {
         #[derivative(Debug(format_with = "OmittedDebug::omitted_fmt"))]
         mnemonic: String,
+        #[derivative(Debug(format_with = "OmittedDebug::omitted_fmt"))]
+        #[serde(default)]
+        passphrase: Option<String>,
     },
     /// Start background syncing.
     /// Expected response: [`Ok`](crate::Response::Ok)
@@ -7,6 +7,7 @@ use std::str::FromStr;
 use iota_sdk::{
     client::api::{
         PreparedTransactionData, PreparedTransactionDataDto, SignedTransactionData, SignedTransactionDataDto,
+        TransactionSummaryDto,
     },
     types::{
         block::output::{dto::OutputDto, Output},
@@ -333,6 +334,15 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             Response::SentTransaction(TransactionDto::from(&transaction))
         }
         AccountMethod::Sync { options } => Response::Balance(account.sync(options).await?),
+        AccountMethod::TransactionSummary {
+            prepared_transaction_data,
+        } => {
+            let prepared_transaction_data = PreparedTransactionData::try_from_dto_with_params(
+                prepared_transaction_data,
+                account.client().get_protocol_parameters().await?,
+            )?;
+            Response::TransactionSummary(TransactionSummaryDto::from(&prepared_transaction_data.summary()))
+        }
         AccountMethod::Transactions => {
             let transactions = account.transactions().await;
             Response::Transactions(transactions.iter().map(TransactionDto::from).collect())
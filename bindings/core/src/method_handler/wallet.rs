@@ -170,8 +170,10 @@ pub(crate) async fn call_wallet_method_internal(wallet: &Wallet, method: WalletM
             Response::Ok
         }
         #[cfg(feature = "stronghold")]
-        WalletMethod::StoreMnemonic { mnemonic } => {
-            wallet.store_mnemonic(mnemonic.into()).await?;
+        WalletMethod::StoreMnemonic { mnemonic, passphrase } => {
+            wallet
+                .store_mnemonic_with_passphrase(mnemonic.into(), passphrase.unwrap_or_default().into())
+                .await?;
             Response::Ok
         }
         WalletMethod::StartBackgroundSync {
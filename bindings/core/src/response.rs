@@ -10,7 +10,7 @@ use derivative::Derivative;
 use iota_sdk::client::secret::LedgerNanoStatus;
 use iota_sdk::{
     client::{
-        api::{PreparedTransactionDataDto, SignedTransactionDataDto},
+        api::{PreparedTransactionDataDto, SignedTransactionDataDto, TransactionSummaryDto},
         node_manager::node::Node,
         NetworkInfo, NodeInfoWrapper,
     },
@@ -356,6 +356,9 @@ pub enum Response {
     /// - [`Transactions`](crate::method::AccountMethod::Transactions),
     Transactions(Vec<TransactionDto>),
     /// Response for:
+    /// - [`TransactionSummary`](crate::method::AccountMethod::TransactionSummary)
+    TransactionSummary(TransactionSummaryDto),
+    /// Response for:
     /// - [`SignTransactionEssence`](crate::method::AccountMethod::SignTransactionEssence)
     SignedTransactionData(SignedTransactionDataDto),
     /// GenerateAddress response.
@@ -75,12 +75,14 @@ export class SecretManager {
      * Store a mnemonic in the Stronghold vault.
      *
      * @param mnemonic The mnemonic to store.
+     * @param passphrase The BIP-39 passphrase to derive the seed with, if any.
      */
-    async storeMnemonic(mnemonic: string): Promise<void> {
+    async storeMnemonic(mnemonic: string, passphrase?: string): Promise<void> {
         const response = await this.methodHandler.callMethod({
             name: 'storeMnemonic',
             data: {
                 mnemonic,
+                passphrase,
             },
         });
 
@@ -4,6 +4,7 @@
 import type { CoinType } from '../../client';
 import type { IRange } from './range';
 import type { Burn } from './burn';
+import type { SelectionStrategy } from './selection-strategy';
 import { UTXOInput } from '../block/input';
 import { Output } from '../block/output';
 
@@ -37,6 +38,8 @@ export interface IBuildBlockOptions {
     parents?: string[];
     /** Parameters for explicit burning of aliases, nfts, foundries and native tokens. */
     burn?: Burn;
+    /** The strategy used to select the inputs. */
+    selectionStrategy?: SelectionStrategy;
 }
 
 /** Address with base coin amount. */
@@ -12,3 +12,5 @@ export * from './nodeInfo';
 export * from './prepared-transaction-data';
 export * from './query-parameters';
 export * from './range';
+export * from './selection-strategy';
+export * from './selection-trace';
@@ -0,0 +1,17 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * The strategy used to select the inputs covering the amount of a transaction.
+ * - `smallestFirst`: the inputs with the lowest amounts first, consolidating dust (default).
+ * - `largestFirst`: the inputs with the highest amounts first.
+ * - `fewestInputs`: the smallest input covering the amount on its own, the highest amounts first otherwise.
+ * - `branchAndBound`: a set of inputs matching the amount exactly to avoid a remainder, `largestFirst` otherwise.
+ * - `privacy`: inputs from as few addresses as possible to avoid linking them together.
+ */
+export type SelectionStrategy =
+    | 'smallestFirst'
+    | 'largestFirst'
+    | 'fewestInputs'
+    | 'branchAndBound'
+    | 'privacy';
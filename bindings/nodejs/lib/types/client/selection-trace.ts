@@ -0,0 +1,63 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type { Address } from '../block/address';
+import type { AliasId, FoundryId, NftId } from '../block/id';
+import type { OutputId } from '../block/output';
+import type { NumericString } from '../utils';
+
+/** A requirement, imposed by outputs, that needs to be resolved by selected inputs. */
+export type Requirement =
+    | { type: 'sender'; address: Address }
+    | { type: 'issuer'; address: Address }
+    | { type: 'ed25519'; address: Address }
+    | { type: 'foundry'; foundryId: FoundryId }
+    | { type: 'alias'; aliasId: AliasId; transition: 'State' | 'Governance' }
+    | { type: 'nft'; nftId: NftId }
+    | { type: 'nativeTokens' }
+    | { type: 'amount' };
+
+/** Why an input was not available for the input selection. */
+export type RejectionReason =
+    /** The input is forbidden, e.g. because it's already used by a pending transaction. */
+    | { type: 'forbidden' }
+    /** The kind of output can't be selected automatically. */
+    | { type: 'unsupportedKind' }
+    /** The input is timelocked until the given timestamp. */
+    | { type: 'timelocked'; until: number }
+    /** The input has an expiration that has not been reached yet. */
+    | { type: 'expiring'; expiresAt: number }
+    /** The input can only be unlocked by an address that is not controlled. */
+    | { type: 'notOwned'; requiredAddress: Address }
+    /** The whole amount of the input has to be returned as storage deposit. */
+    | {
+          type: 'storageDepositReturn';
+          returnAddress: Address;
+          amount: NumericString;
+      };
+
+/** The outcome of the evaluation of a requirement. */
+export interface RequirementTrace {
+    /** The evaluated requirement. */
+    requirement: Requirement;
+    /** The inputs selected to fulfill the requirement. */
+    selectedInputs: OutputId[];
+    /** Whether the requirement could be fulfilled. */
+    fulfilled: boolean;
+}
+
+/** An input that was not available for the input selection. */
+export interface RejectedInput {
+    /** The output ID of the input. */
+    outputId: OutputId;
+    /** Why the input was not available. */
+    reason: RejectionReason;
+}
+
+/** A record of the decisions taken by the input selection, explaining why a selection failed. */
+export interface SelectionTrace {
+    /** The requirements in the order they were evaluated. */
+    requirements: RequirementTrace[];
+    /** The inputs that were not available for the selection. */
+    rejectedInputs: RejectedInput[];
+}
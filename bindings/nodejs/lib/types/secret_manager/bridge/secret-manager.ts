@@ -39,6 +39,7 @@ export interface __StoreMnemonicMethod__ {
     name: 'storeMnemonic';
     data: {
         mnemonic: string;
+        passphrase?: string;
     };
 }
 
@@ -15,6 +15,16 @@ export interface MnemonicSecretManager {
     mnemonic: string;
 }
 
+/** Secret manager that uses a mnemonic and a BIP-39 passphrase. */
+export interface MnemonicWithPassphraseSecretManager {
+    mnemonicWithPassphrase: {
+        /** The underlying mnemonic. */
+        mnemonic: string;
+        /** The BIP-39 passphrase, also known as the "25th word". */
+        passphrase: string;
+    };
+}
+
 /** Secret manager that uses a seed. */
 export interface SeedSecretManager {
     /** The underlying seed. */
@@ -38,13 +48,32 @@ export interface PrivateKeySecretManager {
     privateKey: HexEncodedString;
 }
 
+/** The public keys of the addresses of an account. */
+export interface WatchOnlyAccount {
+    /** The coin type of the account. */
+    coinType: number;
+    /** The index of the account. */
+    accountIndex: number;
+    /** The public keys of the public addresses, by address index. */
+    publicKeys: HexEncodedString[];
+    /** The public keys of the internal addresses, by address index. */
+    internalPublicKeys?: HexEncodedString[];
+}
+
+/** Secret manager that only holds public keys, it can generate addresses but can't sign. */
+export interface WatchOnlySecretManager {
+    watchOnly: WatchOnlyAccount[];
+}
+
 /** Supported secret managers */
 export type SecretManagerType =
     | LedgerNanoSecretManager
     | MnemonicSecretManager
+    | MnemonicWithPassphraseSecretManager
     | SeedSecretManager
     | StrongholdSecretManager
     | PrivateKeySecretManager
+    | WatchOnlySecretManager
     | PlaceholderSecretManager;
 
 export interface Secp256k1EcdsaSignature {
@@ -313,6 +313,13 @@ export type __SignTransactionEssenceMethod__ = {
     };
 };
 
+export type __TransactionSummaryMethod__ = {
+    name: 'transactionSummary';
+    data: {
+        preparedTransactionData: PreparedTransactionData;
+    };
+};
+
 export type __SignAndSubmitTransactionMethod__ = {
     name: 'signAndSubmitTransaction';
     data: {
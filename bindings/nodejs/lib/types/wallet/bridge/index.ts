@@ -40,6 +40,7 @@ import type {
     __SetDefaultSyncOptionsMethod__,
     __SignTransactionEssenceMethod__,
     __SignAndSubmitTransactionMethod__,
+    __TransactionSummaryMethod__,
     __SubmitAndStoreTransactionMethod__,
     __SyncAccountMethod__,
     __PrepareVoteMethod__,
@@ -126,6 +127,7 @@ export type __AccountMethod__ =
     | __SetDefaultSyncOptionsMethod__
     | __SignTransactionEssenceMethod__
     | __SignAndSubmitTransactionMethod__
+    | __TransactionSummaryMethod__
     | __SubmitAndStoreTransactionMethod__
     | __SyncAccountMethod__
     | __PrepareVoteMethod__
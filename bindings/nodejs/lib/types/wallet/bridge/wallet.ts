@@ -130,7 +130,7 @@ export type __StopBackgroundSyncMethod__ = {
 
 export type __StoreMnemonicMethod__ = {
     name: 'storeMnemonic';
-    data: { mnemonic: string };
+    data: { mnemonic: string; passphrase?: string };
 };
 
 export type __UpdateNodeAuthMethod__ = {
@@ -3,7 +3,12 @@
 
 import type { OutputData } from './output';
 import { InclusionState } from './transaction';
-import { InputSigningData, Remainder } from '../client';
+import {
+    InputSigningData,
+    Remainder,
+    RejectedInput,
+    RequirementTrace,
+} from '../client';
 import { TransactionEssence, TransactionPayload } from '../block';
 import { OutputResponse } from '../models';
 
@@ -51,6 +56,10 @@ enum WalletEventType {
     TransactionInclusion = 4,
     /** A progress update while submitting a transaction. */
     TransactionProgress = 5,
+    /** The status of a queued payment changed. */
+    PaymentStatus = 6,
+    /** An instalment of a scheduled payment was sent. */
+    ScheduledPaymentExecuted = 7,
 }
 
 /**
@@ -149,6 +158,57 @@ class TransactionInclusionWalletEvent extends WalletEvent {
     }
 }
 
+/**
+ * The status of a payment of the payment queue.
+ */
+type PaymentStatus =
+    | { type: 'queued' }
+    | { type: 'sent'; transactionId: TransactionId }
+    | { type: 'failed'; error: string };
+
+/**
+ * A 'payment status' wallet event.
+ */
+class PaymentStatusWalletEvent extends WalletEvent {
+    paymentId: number;
+    status: PaymentStatus;
+
+    /**
+     * @param paymentId The id of the queued payment.
+     * @param status The new status of the payment.
+     */
+    constructor(paymentId: number, status: PaymentStatus) {
+        super(WalletEventType.PaymentStatus);
+        this.paymentId = paymentId;
+        this.status = status;
+    }
+}
+
+/**
+ * A 'scheduled payment executed' wallet event.
+ */
+class ScheduledPaymentExecutedWalletEvent extends WalletEvent {
+    paymentId: number;
+    instalment: number;
+    transactionId: TransactionId;
+
+    /**
+     * @param paymentId The id of the scheduled payment.
+     * @param instalment The index of the instalment that was sent, starting at 0.
+     * @param transactionId The transaction that sent the instalment.
+     */
+    constructor(
+        paymentId: number,
+        instalment: number,
+        transactionId: TransactionId,
+    ) {
+        super(WalletEventType.ScheduledPaymentExecuted);
+        this.paymentId = paymentId;
+        this.instalment = instalment;
+        this.transactionId = transactionId;
+    }
+}
+
 /**
  * All of the transaction progress types.
  */
@@ -167,6 +227,8 @@ enum TransactionProgressType {
     PerformingPow = 5,
     /** Broadcasting. */
     Broadcasting = 6,
+    /** Input selection failed. */
+    SelectingInputsFailed = 7,
 }
 
 /**
@@ -289,6 +351,27 @@ class BroadcastingProgress extends TransactionProgress {
     }
 }
 
+/**
+ * A 'selecting inputs failed' progress, explaining why the input selection failed.
+ */
+class SelectingInputsFailedProgress extends TransactionProgress {
+    requirements: RequirementTrace[];
+    rejectedInputs: RejectedInput[];
+
+    /**
+     * @param requirements The requirements in the order they were evaluated.
+     * @param rejectedInputs The inputs that were not available for the selection.
+     */
+    constructor(
+        requirements: RequirementTrace[],
+        rejectedInputs: RejectedInput[],
+    ) {
+        super(TransactionProgressType.SelectingInputsFailed);
+        this.requirements = requirements;
+        this.rejectedInputs = rejectedInputs;
+    }
+}
+
 export {
     Event,
     WalletEventType,
@@ -299,6 +382,9 @@ export {
     SpentOutputWalletEvent,
     TransactionInclusionWalletEvent,
     TransactionProgressWalletEvent,
+    PaymentStatus,
+    PaymentStatusWalletEvent,
+    ScheduledPaymentExecutedWalletEvent,
     TransactionProgress,
     SelectingInputsProgress,
     GeneratingRemainderDepositAddressProgress,
@@ -307,5 +393,6 @@ export {
     SigningTransactionProgress,
     PerformingPowProgress,
     BroadcastingProgress,
+    SelectingInputsFailedProgress,
     TransactionProgressType,
 };
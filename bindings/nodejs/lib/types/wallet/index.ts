@@ -15,5 +15,6 @@ export * from './prepared-transaction';
 export * from './signed-transaction-essence';
 export * from './transaction';
 export * from './transaction-options';
+export * from './transaction-summary';
 export * from './prepared-create-token-transaction-data';
 export * from './consolidation-params';
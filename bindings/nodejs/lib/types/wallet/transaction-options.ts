@@ -3,7 +3,7 @@
 
 import { AliasId, Bech32Address } from '../block';
 import { TaggedDataPayload } from '../block/payload/tagged';
-import { Burn } from '../client';
+import { Burn, SelectionStrategy } from '../client';
 import { HexEncodedString } from '../utils';
 import { AccountAddress } from './address';
 
@@ -27,6 +27,8 @@ export interface TransactionOptions {
     note?: string;
     /** Whether to allow sending a micro amount. */
     allowMicroAmount?: boolean;
+    /** The strategy used to select the inputs, `smallestFirst` by default. */
+    selectionStrategy?: SelectionStrategy;
 }
 
 /** The possible remainder value strategies. */
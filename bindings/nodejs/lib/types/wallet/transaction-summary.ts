@@ -0,0 +1,66 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import { Type } from 'class-transformer';
+import { Address, AddressDiscriminator } from '../block/address';
+import { TaggedDataPayload } from '../block/payload/tagged';
+import { INativeToken } from '../models/native-token';
+import { HexEncodedString } from '../utils/hex-encoding';
+
+/** The effect of a transaction on the holdings of a single address. */
+export class AddressSummary {
+    /** The address. */
+    @Type(() => Address, {
+        discriminator: AddressDiscriminator,
+    })
+    address!: Address;
+    /** The base coin amount the address loses. */
+    baseCoinSent!: string;
+    /** The base coin amount the address gains. */
+    baseCoinReceived!: string;
+    /** The native tokens the address loses. */
+    nativeTokensSent!: INativeToken[];
+    /** The native tokens the address gains. */
+    nativeTokensReceived!: INativeToken[];
+    /** The NFTs the address loses. */
+    nftsSent!: HexEncodedString[];
+    /** The NFTs the address gains, minted ones have a null id. */
+    nftsReceived!: HexEncodedString[];
+    /** The aliases the address loses the governance of. */
+    aliasesSent!: HexEncodedString[];
+    /** The aliases the address gains the governance of, created ones have a null id. */
+    aliasesReceived!: HexEncodedString[];
+    /** The foundries the alias address loses. */
+    foundriesSent!: HexEncodedString[];
+    /** The foundries the alias address gains. */
+    foundriesReceived!: HexEncodedString[];
+}
+
+/** A storage deposit that has to be returned to an address. */
+export class StorageDepositSummary {
+    /** The address the storage deposit is returned to. */
+    @Type(() => Address, {
+        discriminator: AddressDiscriminator,
+    })
+    returnAddress!: Address;
+    /** The amount of the storage deposit. */
+    amount!: string;
+}
+
+/** A human-readable summary of the effects of a transaction, to review it before signing. */
+export class TransactionSummary {
+    /** The addresses whose holdings are changed by the transaction. */
+    @Type(() => AddressSummary)
+    addresses!: AddressSummary[];
+    /** The storage deposits locked in the created outputs. */
+    @Type(() => StorageDepositSummary)
+    storageDepositsLocked!: StorageDepositSummary[];
+    /** The storage deposits of the consumed outputs, returned by the transaction. */
+    @Type(() => StorageDepositSummary)
+    storageDepositsReturned!: StorageDepositSummary[];
+    /** The native tokens consumed and not created by the transaction, either burned or melted. */
+    burnedNativeTokens!: INativeToken[];
+    /** The tagged data attached to the transaction. */
+    @Type(() => TaggedDataPayload)
+    taggedData?: TaggedDataPayload;
+}
@@ -34,6 +34,7 @@ import {
     BuildFoundryOutputData,
     BuildNftOutputData,
     SignedTransactionEssence,
+    TransactionSummary,
     PreparedTransaction,
     PreparedCreateNativeTokenTransactionData,
     ConsolidationParams,
@@ -1427,6 +1428,28 @@ export class Account {
         return plainToInstance(SignedTransactionEssence, parsed.payload);
     }
 
+    /**
+     * Summarize the effects of a prepared transaction, to review it before signing.
+     *
+     * @param preparedTransactionData The prepared transaction data to summarize.
+     * @returns The transaction summary.
+     */
+    async transactionSummary(
+        preparedTransactionData: PreparedTransactionData,
+    ): Promise<TransactionSummary> {
+        const response = await this.methodHandler.callAccountMethod(
+            this.meta.index,
+            {
+                name: 'transactionSummary',
+                data: {
+                    preparedTransactionData,
+                },
+            },
+        );
+        const parsed = JSON.parse(response) as Response<TransactionSummary>;
+        return plainToInstance(TransactionSummary, parsed.payload);
+    }
+
     /**
      * Sign a prepared transaction, and send it.
      *
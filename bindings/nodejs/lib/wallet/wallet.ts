@@ -348,12 +348,12 @@ export class Wallet {
     }
 
     /**
-     * Store a mnemonic in the Stronghold snapshot.
+     * Store a mnemonic in the Stronghold snapshot, the seed being derived with the BIP-39 passphrase, if any.
      */
-    async storeMnemonic(mnemonic: string): Promise<void> {
+    async storeMnemonic(mnemonic: string, passphrase?: string): Promise<void> {
         await this.methodHandler.callMethod({
             name: 'storeMnemonic',
-            data: { mnemonic },
+            data: { mnemonic, passphrase },
         });
     }
 
@@ -36,6 +36,7 @@ from .types.token_scheme import *
 from .types.transaction import *
 from .types.transaction_data import *
 from .types.transaction_options import *
+from .types.transaction_summary import *
 from .types.unlock import *
 from .types.unlock_condition import *
 from .types.utxo_changes import *
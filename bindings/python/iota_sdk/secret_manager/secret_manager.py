@@ -31,14 +31,19 @@ class MnemonicSecretManager(dict):
     This is not recommended in production. Use LedgerNano or Stronghold instead.
     """
 
-    def __init__(self, mnemonic):
+    def __init__(self, mnemonic, passphrase=None):
         """Initialize a mnemonic secret manager.
 
         Args:
             mnemonic: The root secret of this type of secret manager.
+            passphrase: An optional BIP-39 passphrase to derive the seed with.
         """
 
-        dict.__init__(self, mnemonic=mnemonic)
+        if passphrase is None:
+            dict.__init__(self, mnemonic=mnemonic)
+        else:
+            dict.__init__(self, mnemonicWithPassphrase={
+                'mnemonic': mnemonic, 'passphrase': passphrase})
 
 
 class SeedSecretManager(dict):
@@ -75,6 +80,21 @@ class StrongholdSecretManager(dict):
             dict.__init__(self, password=password, snapshotPath=snapshot_path)
 
 
+class WatchOnlySecretManager(dict):
+    """Secret manager that only holds public keys, it can generate addresses but can't sign.
+    """
+
+    def __init__(self, accounts):
+        """Initialize a watch-only secret manager.
+
+        Args:
+            accounts: The public keys of the addresses of accounts, as dicts with `coinType`, `accountIndex`,
+                `publicKeys` and `internalPublicKeys`, the public keys being hex encoded and ordered by address index.
+        """
+
+        dict.__init__(self, watchOnly=accounts)
+
+
 class SecretManagerError(Exception):
     """Secret manager error.
     """
@@ -83,7 +103,7 @@ class SecretManagerError(Exception):
 
 class SecretManager():
     def __init__(self, secret_manager: Optional[Union[LedgerNanoSecretManager, MnemonicSecretManager,
-                 SeedSecretManager, StrongholdSecretManager]] = None, secret_manager_handle=None):
+                 SeedSecretManager, StrongholdSecretManager, WatchOnlySecretManager]] = None, secret_manager_handle=None):
         """Initialize a secret manager.
 
         Args:
@@ -223,14 +243,16 @@ class SecretManager():
         """
         return self._call_method('getLedgerNanoStatus')
 
-    def store_mnemonic(self, mnemonic: str):
+    def store_mnemonic(self, mnemonic: str, passphrase: Optional[str] = None):
         """Store a mnemonic.
 
         Args:
             mnemonic: A mnemonic to store in the secret manager.
+            passphrase: An optional BIP-39 passphrase to derive the seed with.
         """
         return self._call_method('storeMnemonic', {
-            'mnemonic': mnemonic
+            'mnemonic': mnemonic,
+            'passphrase': passphrase
         })
 
     def sign_ed25519(self, message: HexStr, chain: Bip44) -> Ed25519Signature:
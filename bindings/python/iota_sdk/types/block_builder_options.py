@@ -9,6 +9,7 @@ from iota_sdk.client._high_level_api import Range
 from iota_sdk.types.burn import Burn
 from iota_sdk.types.output import Output
 from iota_sdk.types.input import UtxoInput
+from iota_sdk.types.transaction_options import SelectionStrategy
 
 
 @dataclass
@@ -30,3 +31,4 @@ class BlockBuilderOptions:
     data: Optional[HexStr] = None
     parents: Optional[List[HexStr]] = None
     burn: Optional[Burn] = None
+    selectionStrategy: Optional[SelectionStrategy] = None
@@ -14,6 +14,8 @@ class WalletEventType(IntEnum):
         SpentOutput (3): An output was spent.
         TransactionInclusion (4): A transaction was included into the ledger.
         TransactionProgress (5): A progress update while submitting a transaction.
+        PaymentStatus (6): The status of a queued payment changed.
+        ScheduledPaymentExecuted (7): An instalment of a scheduled payment was sent.
     """
     ConsolidationRequired = 0,
     LedgerAddressGeneration = 1,
@@ -21,3 +23,5 @@ class WalletEventType(IntEnum):
     SpentOutput = 3,
     TransactionInclusion = 4,
     TransactionProgress = 5,
+    PaymentStatus = 6,
+    ScheduledPaymentExecuted = 7,
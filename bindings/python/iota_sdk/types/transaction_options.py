@@ -46,6 +46,23 @@ class RemainderValueStrategy(Enum):
         return dict({"strategy": self.name, "value": self.value[0]})
 
 
+class SelectionStrategy(str, Enum):
+    """The strategy used to select the inputs covering the amount of a transaction.
+
+    Attributes:
+        SmallestFirst: The inputs with the lowest amounts first, consolidating dust (default).
+        LargestFirst: The inputs with the highest amounts first.
+        FewestInputs: The smallest input covering the amount on its own, the highest amounts first otherwise.
+        BranchAndBound: A set of inputs matching the amount exactly to avoid a remainder, largest first otherwise.
+        Privacy: Inputs from as few addresses as possible to avoid linking them together.
+    """
+    SmallestFirst = 'smallestFirst'
+    LargestFirst = 'largestFirst'
+    FewestInputs = 'fewestInputs'
+    BranchAndBound = 'branchAndBound'
+    Privacy = 'privacy'
+
+
 class TransactionOptions():
     """Transaction options.
 
@@ -57,6 +74,7 @@ class TransactionOptions():
         burn: Specifies what needs to be burned during input selection.
         note: A string attached to the transaction.
         allow_micro_amount: Whether to allow sending a micro amount.
+        selection_strategy: The strategy used to select the inputs.
     """
 
     def __init__(self, remainder_value_strategy: Optional[Union[RemainderValueStrategy, RemainderValueStrategyCustomAddress]] = None,
@@ -65,7 +83,8 @@ class TransactionOptions():
                  mandatory_inputs: Optional[List[OutputId]] = None,
                  burn: Optional[Burn] = None,
                  note: Optional[str] = None,
-                 allow_micro_amount: Optional[bool] = None):
+                 allow_micro_amount: Optional[bool] = None,
+                 selection_strategy: Optional[SelectionStrategy] = None):
         """Initialize transaction options.
         """
         self.remainder_value_strategy = remainder_value_strategy
@@ -75,6 +94,7 @@ class TransactionOptions():
         self.burn = burn
         self.note = note
         self.allow_micro_amount = allow_micro_amount
+        self.selection_strategy = selection_strategy
 
     def as_dict(self):
         return dict(self.__dict__)
@@ -0,0 +1,70 @@
+# Copyright 2023 IOTA Stiftung
+# SPDX-License-Identifier: Apache-2.0
+
+from __future__ import annotations
+from dataclasses import dataclass, field
+from typing import List, Optional, Union
+from iota_sdk.types.address import Ed25519Address, AliasAddress, NFTAddress
+from iota_sdk.types.common import HexStr
+from iota_sdk.types.native_token import NativeToken
+from iota_sdk.types.payload import TaggedDataPayload
+
+
+@dataclass
+class AddressSummary:
+    """The effect of a transaction on the holdings of a single address.
+
+    Attributes:
+        address: The address.
+        baseCoinSent: The base coin amount the address loses.
+        baseCoinReceived: The base coin amount the address gains.
+        nativeTokensSent: The native tokens the address loses.
+        nativeTokensReceived: The native tokens the address gains.
+        nftsSent: The NFTs the address loses.
+        nftsReceived: The NFTs the address gains, minted ones have a null id.
+        aliasesSent: The aliases the address loses the governance of.
+        aliasesReceived: The aliases the address gains the governance of, created ones have a null id.
+        foundriesSent: The foundries the alias address loses.
+        foundriesReceived: The foundries the alias address gains.
+    """
+    address: Union[Ed25519Address, AliasAddress, NFTAddress]
+    baseCoinSent: str
+    baseCoinReceived: str
+    nativeTokensSent: List[NativeToken] = field(default_factory=list)
+    nativeTokensReceived: List[NativeToken] = field(default_factory=list)
+    nftsSent: List[HexStr] = field(default_factory=list)
+    nftsReceived: List[HexStr] = field(default_factory=list)
+    aliasesSent: List[HexStr] = field(default_factory=list)
+    aliasesReceived: List[HexStr] = field(default_factory=list)
+    foundriesSent: List[HexStr] = field(default_factory=list)
+    foundriesReceived: List[HexStr] = field(default_factory=list)
+
+
+@dataclass
+class StorageDepositSummary:
+    """A storage deposit that has to be returned to an address.
+
+    Attributes:
+        returnAddress: The address the storage deposit is returned to.
+        amount: The amount of the storage deposit.
+    """
+    returnAddress: Union[Ed25519Address, AliasAddress, NFTAddress]
+    amount: str
+
+
+@dataclass
+class TransactionSummary:
+    """A human-readable summary of the effects of a transaction, to review it before signing.
+
+    Attributes:
+        addresses: The addresses whose holdings are changed by the transaction.
+        storageDepositsLocked: The storage deposits locked in the created outputs.
+        storageDepositsReturned: The storage deposits of the consumed outputs, returned by the transaction.
+        burnedNativeTokens: The native tokens consumed and not created by the transaction, either burned or melted.
+        taggedData: The tagged data attached to the transaction.
+    """
+    addresses: List[AddressSummary]
+    storageDepositsLocked: List[StorageDepositSummary]
+    storageDepositsReturned: List[StorageDepositSummary]
+    burnedNativeTokens: List[NativeToken]
+    taggedData: Optional[TaggedDataPayload] = None
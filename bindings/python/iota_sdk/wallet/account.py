@@ -18,6 +18,7 @@ from iota_sdk.types.transaction_data import PreparedTransactionData, SignedTrans
 from iota_sdk.types.send_params import CreateAliasOutputParams, CreateNativeTokenParams, MintNftParams, SendNativeTokensParams, SendNftParams, SendParams
 from iota_sdk.types.transaction import Transaction
 from iota_sdk.types.transaction_options import TransactionOptions
+from iota_sdk.types.transaction_summary import TransactionSummary
 from iota_sdk.types.consolidation_params import ConsolidationParams
 from typing import List, Optional, Union
 from dacite import from_dict
@@ -539,6 +540,16 @@ class Account:
             }
         ))
 
+    def transaction_summary(
+            self, prepared_transaction_data: PreparedTransactionData) -> TransactionSummary:
+        """Summarize the effects of a prepared transaction, to review it before signing.
+        """
+        return from_dict(TransactionSummary, self._call_account_method(
+            'transactionSummary', {
+                'preparedTransactionData': prepared_transaction_data
+            }
+        ))
+
     def sign_and_submit_transaction(
             self, prepared_transaction_data: PreparedTransactionData) -> Transaction:
         """Validate the transaction, sign it, submit it to a node and store it in the account.
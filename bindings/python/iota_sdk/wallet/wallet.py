@@ -222,12 +222,13 @@ class Wallet():
             }
         )
 
-    def store_mnemonic(self, mnemonic: str):
-        """Store mnemonic.
+    def store_mnemonic(self, mnemonic: str, passphrase: Optional[str] = None):
+        """Store mnemonic, the seed being derived with the BIP-39 passphrase, if any.
         """
         return self._call_method(
             'storeMnemonic', {
-                'mnemonic': mnemonic
+                'mnemonic': mnemonic,
+                'passphrase': passphrase
             }
 
         )
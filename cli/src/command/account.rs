@@ -5,11 +5,11 @@ use std::str::FromStr;
 
 use clap::{CommandFactory, Parser, Subcommand};
 use iota_sdk::{
-    client::request_funds_from_faucet,
+    client::{api::PreparedTransactionData, request_funds_from_faucet},
     types::{
         api::plugins::participation::types::ParticipationEventId,
         block::{
-            address::Bech32Address,
+            address::{Bech32Address, ToBech32Ext},
             output::{
                 unlock_condition::AddressUnlockCondition, AliasId, BasicOutputBuilder, FoundryId, NativeToken,
                 NativeTokensBuilder, NftId, Output, OutputId, TokenId,
@@ -683,14 +683,14 @@ pub async fn send_command(
     let params = [SendParams::new(amount, address)?
         .with_return_address(return_address.map(ConvertTo::convert).transpose()?)
         .with_expiration(expiration)];
+    let options = TransactionOptions {
+        allow_micro_amount,
+        ..Default::default()
+    };
+    let prepared_transaction = account.prepare_send(params, options.clone()).await?;
+    print_transaction_summary(account, &prepared_transaction).await?;
     let transaction = account
-        .send_with_params(
-            params,
-            TransactionOptions {
-                allow_micro_amount,
-                ..Default::default()
-            },
-        )
+        .sign_and_submit_transaction(prepared_transaction, options)
         .await?;
 
     println_log_info!(
@@ -711,7 +711,7 @@ pub async fn send_native_token_command(
     gift_storage_deposit: Option<bool>,
 ) -> Result<(), Error> {
     let address = address.convert()?;
-    let transaction = if gift_storage_deposit.unwrap_or(false) {
+    let prepared_transaction = if gift_storage_deposit.unwrap_or(false) {
         // Send native tokens together with the required storage deposit
         let rent_structure = account.client().get_rent_structure().await?;
         let token_supply = account.client().get_token_supply().await?;
@@ -726,7 +726,7 @@ pub async fn send_native_token_command(
             )?])
             .finish_output(token_supply)?];
 
-        account.send_outputs(outputs, None).await?
+        account.prepare_transaction(outputs, None).await?
     } else {
         // Send native tokens with storage deposit return and expiration
         let outputs = [SendNativeTokensParams::new(
@@ -736,8 +736,10 @@ pub async fn send_native_token_command(
                 U256::from_dec_str(&amount).map_err(|e| Error::Miscellaneous(e.to_string()))?,
             )],
         )?];
-        account.send_native_tokens(outputs, None).await?
+        account.prepare_send_native_tokens(outputs, None).await?
     };
+    print_transaction_summary(account, &prepared_transaction).await?;
+    let transaction = account.sign_and_submit_transaction(prepared_transaction, None).await?;
 
     println_log_info!(
         "Native token transaction sent:\n{:?}\n{:?}",
@@ -755,7 +757,9 @@ pub async fn send_nft_command(
     nft_id: String,
 ) -> Result<(), Error> {
     let outputs = [SendNftParams::new(address.convert()?, &nft_id)?];
-    let transaction = account.send_nft(outputs, None).await?;
+    let prepared_transaction = account.prepare_send_nft(outputs, None).await?;
+    print_transaction_summary(account, &prepared_transaction).await?;
+    let transaction = account.sign_and_submit_transaction(prepared_transaction, None).await?;
 
     println_log_info!(
         "Nft transaction sent:\n{:?}\n{:?}",
@@ -992,3 +996,84 @@ async fn print_address(account: &Account, address: &AccountAddress) -> Result<()
 
     Ok(())
 }
+
+// Prints the effects of a prepared transaction on the holdings of each address before it gets signed.
+async fn print_transaction_summary(
+    account: &Account,
+    prepared_transaction: &PreparedTransactionData,
+) -> Result<(), Error> {
+    let summary = prepared_transaction.summary();
+    let hrp = account.client().get_bech32_hrp().await?;
+    let mut log = String::from("Transaction summary:");
+
+    for address in &summary.addresses {
+        log = format!("{log}\n{}", address.address.to_bech32(hrp));
+
+        if address.base_coin_sent > 0 {
+            log = format!("{log}\n  sends {} base coin", address.base_coin_sent);
+        }
+        if address.base_coin_received > 0 {
+            log = format!("{log}\n  receives {} base coin", address.base_coin_received);
+        }
+        for native_token in &address.native_tokens_sent {
+            log = format!(
+                "{log}\n  sends {} of native token {}",
+                native_token.amount(),
+                native_token.token_id()
+            );
+        }
+        for native_token in &address.native_tokens_received {
+            log = format!(
+                "{log}\n  receives {} of native token {}",
+                native_token.amount(),
+                native_token.token_id()
+            );
+        }
+        for nft_id in &address.nfts_sent {
+            log = format!("{log}\n  sends NFT {nft_id}");
+        }
+        for nft_id in &address.nfts_received {
+            log = format!("{log}\n  receives NFT {nft_id}");
+        }
+        for alias_id in &address.aliases_sent {
+            log = format!("{log}\n  sends alias {alias_id}");
+        }
+        for alias_id in &address.aliases_received {
+            log = format!("{log}\n  receives alias {alias_id}");
+        }
+        for foundry_id in &address.foundries_sent {
+            log = format!("{log}\n  sends foundry {foundry_id}");
+        }
+        for foundry_id in &address.foundries_received {
+            log = format!("{log}\n  receives foundry {foundry_id}");
+        }
+    }
+    for storage_deposit in &summary.storage_deposits_locked {
+        log = format!(
+            "{log}\nStorage deposit of {} locked, to be returned to {}",
+            storage_deposit.amount,
+            storage_deposit.return_address.to_bech32(hrp)
+        );
+    }
+    for storage_deposit in &summary.storage_deposits_returned {
+        log = format!(
+            "{log}\nStorage deposit of {} returned to {}",
+            storage_deposit.amount,
+            storage_deposit.return_address.to_bech32(hrp)
+        );
+    }
+    for native_token in &summary.burned_native_tokens {
+        log = format!(
+            "{log}\nBurns {} of native token {}",
+            native_token.amount(),
+            native_token.token_id()
+        );
+    }
+    if let Some(tagged_data) = &summary.tagged_data {
+        log = format!("{log}\nTagged data: {tagged_data:?}");
+    }
+
+    println_log_info!("{log}");
+
+    Ok(())
+}
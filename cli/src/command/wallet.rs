@@ -11,13 +11,17 @@ use iota_sdk::{
         stronghold::StrongholdAdapter,
         utils::Password,
     },
+    crypto::keys::bip39::Passphrase,
     wallet::{account::types::AccountIdentifier, ClientOptions, Wallet},
 };
 use log::LevelFilter;
 
 use crate::{
     error::Error,
-    helper::{check_file_exists, enter_or_generate_mnemonic, generate_mnemonic, get_password, import_mnemonic},
+    helper::{
+        check_file_exists, enter_or_generate_mnemonic, generate_mnemonic, get_passphrase, get_password,
+        import_mnemonic, import_mnemonic_shares,
+    },
     println_log_error, println_log_info,
 };
 
@@ -114,20 +118,29 @@ pub struct InitParameters {
     /// generated.
     #[arg(short, long, value_name = "PATH")]
     pub mnemonic_file_path: Option<String>,
+    /// Set the paths to Stronghold files of SLIP-39 mnemonic shares, to recover the mnemonic from. Enough shares to
+    /// reach the group and member thresholds are required.
+    #[arg(long, value_name = "PATH", num_args = 1.., conflicts_with = "mnemonic_file_path")]
+    pub share_file_paths: Vec<String>,
     /// Set the node to connect to with this wallet.
     #[arg(short, long, value_name = "URL", env = "NODE_URL", default_value = DEFAULT_NODE_URL)]
     pub node_url: String,
     /// Coin type, SHIMMER_COIN_TYPE (4219) if not provided.
     #[arg(short, long, default_value_t = SHIMMER_COIN_TYPE)]
     pub coin_type: u32,
+    /// Prompt for a BIP-39 passphrase, also known as the "25th word", to derive the seed with.
+    #[arg(long)]
+    pub with_passphrase: bool,
 }
 
 impl Default for InitParameters {
     fn default() -> Self {
         Self {
             mnemonic_file_path: None,
+            share_file_paths: Vec::new(),
             node_url: DEFAULT_NODE_URL.to_string(),
             coin_type: SHIMMER_COIN_TYPE,
+            with_passphrase: false,
         }
     }
 }
@@ -187,13 +200,21 @@ pub async fn init_command(
     let password = get_password("Stronghold password", true)?;
     let mnemonic = match parameters.mnemonic_file_path {
         Some(path) => import_mnemonic(&path).await?,
+        None if !parameters.share_file_paths.is_empty() => import_mnemonic_shares(&parameters.share_file_paths).await?,
         None => enter_or_generate_mnemonic().await?,
     };
+    let passphrase = if parameters.with_passphrase {
+        get_passphrase()?
+    } else {
+        Passphrase::default()
+    };
 
     let secret_manager = StrongholdSecretManager::builder()
         .password(password)
         .build(snapshot_path)?;
-    secret_manager.store_mnemonic(mnemonic).await?;
+    secret_manager
+        .store_mnemonic_with_passphrase(mnemonic, passphrase)
+        .await?;
     let secret_manager = SecretManager::Stronghold(secret_manager);
 
     Ok(Wallet::builder()
@@ -6,8 +6,8 @@ use std::path::Path;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use dialoguer::{console::Term, theme::ColorfulTheme, Input, Select};
 use iota_sdk::{
-    client::{utils::Password, verify_mnemonic},
-    crypto::keys::bip39::Mnemonic,
+    client::{combine_mnemonic_shares, utils::Password, verify_mnemonic},
+    crypto::keys::bip39::{Mnemonic, Passphrase},
     wallet::{Account, Wallet},
 };
 use tokio::{
@@ -32,6 +32,16 @@ pub fn get_password(prompt: &str, confirmation: bool) -> Result<Password, Error>
     Ok(password.interact()?.into())
 }
 
+pub fn get_passphrase() -> Result<Passphrase, Error> {
+    let passphrase = dialoguer::Password::new()
+        .with_prompt("Provide a BIP-39 passphrase")
+        .with_confirmation("Confirm passphrase", "Passphrase mismatch")
+        .allow_empty_password(true)
+        .interact()?;
+
+    Ok(passphrase.into())
+}
+
 pub fn get_decision(prompt: &str) -> Result<bool, Error> {
     loop {
         let input = Input::<String>::new()
@@ -176,6 +186,17 @@ pub fn enter_mnemonic() -> Result<Mnemonic, Error> {
     }
 }
 
+pub async fn import_mnemonic_shares(paths: &[String]) -> Result<Mnemonic, Error> {
+    let mut shares = Vec::with_capacity(paths.len());
+    for path in paths {
+        check_file_exists(path.as_ref()).await?;
+        let password = get_password(&format!("Password of the share \"{path}\""), false)?;
+        shares.push(Wallet::read_mnemonic_share(path, password).await?);
+    }
+
+    Ok(combine_mnemonic_shares(&shares)?)
+}
+
 pub async fn import_mnemonic(path: &str) -> Result<Mnemonic, Error> {
     let mut mnemonics = read_mnemonics_from_file(path).await?;
     if mnemonics.is_empty() {
@@ -115,6 +115,10 @@ impl<'a> ClientBlockBuilder<'a> {
             input_selection = input_selection.remainder_address(address);
         }
 
+        if let Some(strategy) = &self.selection_strategy {
+            input_selection = input_selection.strategy(strategy.clone());
+        }
+
         if let Ok(selected_transaction_data) = input_selection.select() {
             return Ok(selected_transaction_data);
         }
@@ -201,6 +205,10 @@ impl<'a> ClientBlockBuilder<'a> {
                         input_selection = input_selection.remainder_address(address);
                     }
 
+                    if let Some(strategy) = &self.selection_strategy {
+                        input_selection = input_selection.strategy(strategy.clone());
+                    }
+
                     let selected_transaction_data = match input_selection.select() {
                         Ok(r) => r,
                         // for these errors, just try again in the next round with more addresses which might have more
@@ -5,18 +5,29 @@ pub(crate) mod burn;
 pub(crate) mod error;
 pub(crate) mod remainder;
 pub(crate) mod requirement;
+pub(crate) mod strategy;
+pub(crate) mod trace;
 pub(crate) mod transition;
 
 use core::ops::Deref;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use packable::PackableExt;
 pub(crate) use requirement::is_alias_transition;
 
+use self::requirement::amount::sdruc_not_expired;
 pub use self::{
     burn::{Burn, BurnDto},
     error::Error,
     requirement::Requirement,
+    strategy::{BuiltinSelectionStrategy, SelectionStrategy},
+    trace::{
+        RejectedInput, RejectedInputDto, RejectionReason, RejectionReasonDto, RequirementDto, RequirementTrace,
+        RequirementTraceDto, SelectionTrace, SelectionTraceDto,
+    },
 };
 use crate::{
     client::{api::types::RemainderData, secret::types::InputSigningData},
@@ -42,10 +53,12 @@ pub struct InputSelection {
     addresses: HashSet<Address>,
     burn: Option<Burn>,
     remainder_address: Option<Address>,
+    strategy: Arc<dyn SelectionStrategy>,
     protocol_parameters: ProtocolParameters,
     timestamp: u32,
     requirements: Vec<Requirement>,
     automatically_transitioned: HashMap<ChainId, Option<AliasTransition>>,
+    trace: SelectionTrace,
 }
 
 /// Result of the input selection algorithm.
@@ -119,8 +132,16 @@ impl InputSelection {
         self.requirements.push(Requirement::NativeTokens);
 
         // Removes forbidden inputs from available inputs.
-        self.available_inputs
-            .retain(|input| !self.forbidden_inputs.contains(input.output_id()));
+        let (forbidden_inputs, trace) = (&self.forbidden_inputs, &mut self.trace);
+        self.available_inputs.retain(|input| {
+            let forbidden = forbidden_inputs.contains(input.output_id());
+
+            if forbidden {
+                trace.reject(*input.output_id(), RejectionReason::Forbidden);
+            }
+
+            !forbidden
+        });
 
         // This is to avoid a borrow of self since there is a mutable borrow in the loop already.
         let required_inputs = std::mem::take(&mut self.required_inputs);
@@ -187,10 +208,12 @@ impl InputSelection {
             addresses,
             burn: None,
             remainder_address: None,
+            strategy: Arc::new(BuiltinSelectionStrategy::default()),
             protocol_parameters,
             timestamp: unix_timestamp_now().as_secs() as u32,
             requirements: Vec::new(),
             automatically_transitioned: HashMap::new(),
+            trace: SelectionTrace::default(),
         }
     }
 
@@ -218,6 +241,12 @@ impl InputSelection {
         self
     }
 
+    /// Sets the strategy used by an [`InputSelection`] to select inputs fulfilling the amount requirement.
+    pub fn strategy(mut self, strategy: Arc<dyn SelectionStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
     /// Sets the timestamp of an [`InputSelection`].
     pub fn timestamp(mut self, timestamp: u32) -> Self {
         self.timestamp = timestamp;
@@ -225,6 +254,8 @@ impl InputSelection {
     }
 
     fn filter_inputs(&mut self) {
+        let (addresses, timestamp, trace) = (&self.addresses, self.timestamp, &mut self.trace);
+
         self.available_inputs.retain(|input| {
             // Keep alias outputs because at this point we do not know if a state or governor address will be required.
             if input.output.is_alias() {
@@ -232,25 +263,34 @@ impl InputSelection {
             }
             // Filter out non basic/foundry/nft outputs.
             else if !input.output.is_basic() && !input.output.is_foundry() && !input.output.is_nft() {
+                trace.reject(*input.output_id(), RejectionReason::UnsupportedKind);
                 return false;
             }
 
             // PANIC: safe to unwrap as non basic/alias/foundry/nft outputs are already filtered out.
             let unlock_conditions = input.output.unlock_conditions().unwrap();
 
-            if unlock_conditions.is_time_locked(self.timestamp) {
+            if unlock_conditions.is_time_locked(timestamp) {
+                // PANIC: safe to unwrap as the output is time locked.
+                let until = unlock_conditions.timelock().unwrap().timestamp();
+                trace.reject(*input.output_id(), RejectionReason::Timelocked { until });
                 return false;
             }
 
             let required_address = input
                 .output
                 // Alias transition is irrelevant here as we keep aliases anyway.
-                .required_and_unlocked_address(self.timestamp, input.output_id(), None)
+                .required_and_unlocked_address(timestamp, input.output_id(), None)
                 // PANIC: safe to unwrap as non basic/alias/foundry/nft outputs are already filtered out.
                 .unwrap()
                 .0;
 
-            self.addresses.contains(&required_address)
+            if !addresses.contains(&required_address) {
+                trace.reject(*input.output_id(), RejectionReason::NotOwned { required_address });
+                return false;
+            }
+
+            true
         })
     }
 
@@ -360,7 +400,36 @@ impl InputSelection {
 
     /// Selects inputs that meet the requirements of the outputs to satisfy the semantic validation of the overall
     /// transaction. Also creates a remainder output and chain transition outputs if required.
-    pub fn select(mut self) -> Result<Selected, Error> {
+    pub fn select(self) -> Result<Selected, Error> {
+        self.select_with_trace().0
+    }
+
+    /// Same as [`InputSelection::select`], but also returns a [`SelectionTrace`] of the evaluated requirements and the
+    /// rejected inputs, explaining a failed selection.
+    pub fn select_with_trace(mut self) -> (Result<Selected, Error>, SelectionTrace) {
+        let selected = self.select_inner();
+
+        if selected.is_err() {
+            // Inputs whose amount is entirely held by a storage deposit return can't contribute to the amount.
+            for input in &self.available_inputs {
+                if let Some(sdruc) = sdruc_not_expired(&input.output, self.timestamp) {
+                    if input.output.amount() == sdruc.amount() {
+                        self.trace.reject(
+                            *input.output_id(),
+                            RejectionReason::StorageDepositReturn {
+                                return_address: *sdruc.return_address(),
+                                amount: sdruc.amount(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        (selected, self.trace)
+    }
+
+    fn select_inner(&mut self) -> Result<Selected, Error> {
         if !OUTPUT_COUNT_RANGE.contains(&(self.outputs.len() as u16)) {
             // If burn is provided, outputs will be added later
             if !(self.outputs.is_empty() && self.burn.is_some()) {
@@ -380,10 +449,19 @@ impl InputSelection {
         // Process all the requirements until there are no more.
         while let Some(requirement) = self.requirements.pop() {
             // Fulfill the requirement.
-            let inputs = self.fulfill_requirement(requirement)?;
+            let inputs = self.fulfill_requirement(requirement);
+
+            self.trace.requirements.push(RequirementTrace {
+                requirement,
+                selected_inputs: inputs
+                    .as_ref()
+                    .map(|inputs| inputs.iter().map(|(input, _)| *input.output_id()).collect())
+                    .unwrap_or_default(),
+                fulfilled: inputs.is_ok(),
+            });
 
             // Select suggested inputs.
-            for (input, alias_transition) in inputs {
+            for (input, alias_transition) in inputs? {
                 self.select_input(input, alias_transition)?;
             }
         }
@@ -408,8 +486,12 @@ impl InputSelection {
         self.validate_transitions()?;
 
         Ok(Selected {
-            inputs: Self::sort_input_signing_data(self.selected_inputs, &self.outputs, Some(self.timestamp))?,
-            outputs: self.outputs,
+            inputs: Self::sort_input_signing_data(
+                std::mem::take(&mut self.selected_inputs),
+                &self.outputs,
+                Some(self.timestamp),
+            )?,
+            outputs: std::mem::take(&mut self.outputs),
             remainder,
         })
     }
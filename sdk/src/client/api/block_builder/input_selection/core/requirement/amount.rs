@@ -294,15 +294,14 @@ impl InputSelection {
             );
         }
 
-        // TODO if consolidate strategy: sum all the lowest amount until diff is covered.
-        // TODO this would be lowest amount of input strategy.
+        // Try to select outputs first with the ordering of the strategy, if that fails, try from high to low amount.
 
-        // Try to select outputs first with ordering from low to high amount, if that fails, try reversed.
-
-        log::debug!("Ordering inputs from low to high amount");
-        // Sort inputs per amount, low to high.
-        self.available_inputs
-            .sort_by(|left, right| left.output.amount().cmp(&right.output.amount()));
+        log::debug!("Ordering inputs with strategy {:?}", self.strategy);
+        self.strategy.order(
+            &mut self.available_inputs,
+            amount_selection.missing_amount(),
+            self.timestamp,
+        );
 
         if let Some(r) = self.fulfill_amount_requirement_inner(&mut amount_selection) {
             return Ok(r);
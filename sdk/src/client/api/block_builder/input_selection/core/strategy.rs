@@ -0,0 +1,181 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{cmp::Reverse, collections::HashMap, fmt::Debug};
+
+use serde::{Deserialize, Serialize};
+
+use super::requirement::amount::sdruc_not_expired;
+use crate::{
+    client::secret::types::InputSigningData,
+    types::block::{address::Address, input::INPUT_COUNT_MAX, output::Output},
+};
+
+/// Maximum number of branches explored by the [`BuiltinSelectionStrategy::BranchAndBound`] search.
+const BRANCH_AND_BOUND_MAX_TRIES: usize = 100_000;
+
+/// A strategy deciding in which order the available inputs are considered to fulfill the amount requirement of an
+/// [`InputSelection`](super::InputSelection).
+///
+/// Inputs are selected in the returned order until the missing amount is covered, preferring inputs without native
+/// tokens and without storage deposit return over the others. If the selection exceeds the maximum number of inputs,
+/// it is retried with the inputs ordered from high to low amount.
+pub trait SelectionStrategy: Debug + Send + Sync {
+    /// Orders the available `inputs` by preference to cover the missing `amount` at the given `timestamp`.
+    fn order(&self, inputs: &mut [InputSigningData], amount: u64, timestamp: u32);
+}
+
+/// The input selection strategies provided by the SDK.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuiltinSelectionStrategy {
+    /// Selects the inputs with the lowest amounts first, consolidating dust along the way.
+    #[default]
+    SmallestFirst,
+    /// Selects the inputs with the highest amounts first.
+    LargestFirst,
+    /// Selects the smallest input that covers the amount on its own if there is one, the highest amounts first
+    /// otherwise.
+    FewestInputs,
+    /// Searches for a set of inputs matching the amount exactly to avoid creating a remainder, falls back to
+    /// [`BuiltinSelectionStrategy::LargestFirst`].
+    BranchAndBound,
+    /// Selects inputs from as few addresses as possible to avoid linking them together on the ledger.
+    Privacy,
+}
+
+impl SelectionStrategy for BuiltinSelectionStrategy {
+    fn order(&self, inputs: &mut [InputSigningData], amount: u64, timestamp: u32) {
+        match self {
+            Self::SmallestFirst => inputs.sort_by_key(|input| input.output.amount()),
+            Self::LargestFirst => sort_largest_first(inputs),
+            Self::FewestInputs => order_fewest_inputs(inputs, amount),
+            Self::BranchAndBound => order_branch_and_bound(inputs, amount, timestamp),
+            Self::Privacy => order_privacy(inputs, amount, timestamp),
+        }
+    }
+}
+
+fn sort_largest_first(inputs: &mut [InputSigningData]) {
+    inputs.sort_by_key(|input| Reverse(input.output.amount()));
+}
+
+fn order_fewest_inputs(inputs: &mut [InputSigningData], amount: u64) {
+    sort_largest_first(inputs);
+
+    // The smallest input covering the amount on its own is the last one in descending order.
+    if let Some(index) = inputs.iter().rposition(|input| input.output.amount() >= amount) {
+        inputs[..=index].rotate_right(1);
+    }
+}
+
+/// Whether an input can be part of an exact match, as its whole amount becomes available to the transaction.
+fn is_exact_match_candidate(input: &InputSigningData, timestamp: u32) -> bool {
+    match &input.output {
+        Output::Basic(output) => {
+            output.native_tokens().is_empty()
+                && sdruc_not_expired(&input.output, timestamp).is_none()
+                && output
+                    .unlock_conditions()
+                    .locked_address(output.address(), timestamp)
+                    .is_ed25519()
+        }
+        _ => false,
+    }
+}
+
+fn order_branch_and_bound(inputs: &mut [InputSigningData], amount: u64, timestamp: u32) {
+    sort_largest_first(inputs);
+
+    if amount == 0 {
+        return;
+    }
+
+    let candidates = inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| is_exact_match_candidate(input, timestamp))
+        .map(|(index, input)| (index, input.output.amount()))
+        .collect::<Vec<_>>();
+    let amounts = candidates.iter().map(|(_, amount)| *amount).collect::<Vec<_>>();
+    let mut selection = Vec::new();
+    let mut tries = BRANCH_AND_BOUND_MAX_TRIES;
+
+    if branch_and_bound(&amounts, 0, 0, amounts.iter().sum(), amount, &mut selection, &mut tries) {
+        log::debug!("Found an exact match of {} inputs for amount {amount}", selection.len());
+
+        // Move the matching inputs to the front, keeping the remaining ones from high to low amount.
+        for (position, selected) in selection.into_iter().enumerate() {
+            inputs[position..=candidates[selected].0].rotate_right(1);
+        }
+    }
+}
+
+// Depth first search over the inputs, from high to low amount, for a subset summing up to exactly `target`.
+fn branch_and_bound(
+    amounts: &[u64],
+    index: usize,
+    sum: u64,
+    remaining: u64,
+    target: u64,
+    selection: &mut Vec<usize>,
+    tries: &mut usize,
+) -> bool {
+    if sum == target {
+        return true;
+    }
+    if index == amounts.len() || sum + remaining < target || *tries == 0 {
+        return false;
+    }
+
+    *tries -= 1;
+
+    let amount = amounts[index];
+
+    if sum + amount <= target && selection.len() < INPUT_COUNT_MAX.into() {
+        selection.push(index);
+
+        if branch_and_bound(
+            amounts,
+            index + 1,
+            sum + amount,
+            remaining - amount,
+            target,
+            selection,
+            tries,
+        ) {
+            return true;
+        }
+
+        selection.pop();
+    }
+
+    branch_and_bound(amounts, index + 1, sum, remaining - amount, target, selection, tries)
+}
+
+fn order_privacy(inputs: &mut [InputSigningData], amount: u64, timestamp: u32) {
+    let required_address = |input: &InputSigningData| -> Option<Address> {
+        input
+            .output
+            .required_and_unlocked_address(timestamp, input.output_id(), None)
+            .ok()
+            .map(|(address, _)| address)
+    };
+    let mut clusters = HashMap::<Option<Address>, u64>::new();
+
+    for input in inputs.iter() {
+        let available =
+            input.output.amount() - sdruc_not_expired(&input.output, timestamp).map_or(0, |sdruc| sdruc.amount());
+
+        *clusters.entry(required_address(input)).or_default() += available;
+    }
+
+    // Clusters covering the amount on their own come first, smallest first, then the others from high to low total.
+    inputs.sort_by_cached_key(|input| {
+        let address = required_address(input);
+        let total = clusters[&address];
+        let rank = if total >= amount { (0, total) } else { (1, u64::MAX - total) };
+
+        (rank, address, Reverse(input.output.amount()))
+    });
+}
@@ -0,0 +1,242 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::Requirement;
+use crate::types::block::{
+    address::{dto::AddressDto, Address},
+    output::{AliasId, AliasTransition, FoundryId, NftId, OutputId},
+};
+
+/// A record of the decisions taken by an [`InputSelection`](super::InputSelection), explaining why a selection failed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SelectionTrace {
+    /// The requirements in the order they were evaluated.
+    pub requirements: Vec<RequirementTrace>,
+    /// The inputs that were not available for the selection.
+    pub rejected_inputs: Vec<RejectedInput>,
+}
+
+impl SelectionTrace {
+    /// Records that an input was not available for the selection.
+    pub fn reject(&mut self, output_id: OutputId, reason: RejectionReason) {
+        log::debug!("Rejecting input {output_id}: {reason:?}");
+
+        self.rejected_inputs.push(RejectedInput { output_id, reason });
+    }
+
+    /// Returns why an input was not available for the selection, if it was rejected.
+    pub fn rejection(&self, output_id: &OutputId) -> Option<&RejectionReason> {
+        self.rejected_inputs
+            .iter()
+            .find(|rejected| &rejected.output_id == output_id)
+            .map(|rejected| &rejected.reason)
+    }
+}
+
+/// The outcome of the evaluation of a [`Requirement`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequirementTrace {
+    /// The evaluated requirement.
+    pub requirement: Requirement,
+    /// The inputs selected to fulfill the requirement.
+    pub selected_inputs: Vec<OutputId>,
+    /// Whether the requirement could be fulfilled.
+    pub fulfilled: bool,
+}
+
+/// An input that was not available for the selection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectedInput {
+    /// The output id of the input.
+    pub output_id: OutputId,
+    /// Why the input was not available.
+    pub reason: RejectionReason,
+}
+
+/// Why an input was not available for the selection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RejectionReason {
+    /// The input is forbidden, e.g. because it's already used by a pending transaction.
+    Forbidden,
+    /// The kind of output can't be selected automatically.
+    UnsupportedKind,
+    /// The input is timelocked.
+    Timelocked {
+        /// The timestamp until which the input is locked.
+        until: u32,
+    },
+    /// The input has an expiration that has not been reached yet and could be returned to its sender.
+    Expiring {
+        /// The timestamp at which the input expires.
+        expires_at: u32,
+    },
+    /// The input can only be unlocked by an address that is not controlled, e.g. the return address of an expired
+    /// output.
+    NotOwned {
+        /// The address required to unlock the input.
+        required_address: Address,
+    },
+    /// The whole amount of the input has to be returned to the storage deposit return address.
+    StorageDepositReturn {
+        /// The address the storage deposit has to be returned to.
+        return_address: Address,
+        /// The storage deposit return amount.
+        amount: u64,
+    },
+}
+
+/// Dto for [`SelectionTrace`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionTraceDto {
+    pub requirements: Vec<RequirementTraceDto>,
+    pub rejected_inputs: Vec<RejectedInputDto>,
+}
+
+impl From<&SelectionTrace> for SelectionTraceDto {
+    fn from(value: &SelectionTrace) -> Self {
+        Self {
+            requirements: value.requirements.iter().map(Into::into).collect(),
+            rejected_inputs: value.rejected_inputs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Dto for [`RequirementTrace`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementTraceDto {
+    pub requirement: RequirementDto,
+    pub selected_inputs: Vec<OutputId>,
+    pub fulfilled: bool,
+}
+
+impl From<&RequirementTrace> for RequirementTraceDto {
+    fn from(value: &RequirementTrace) -> Self {
+        Self {
+            requirement: RequirementDto::from(&value.requirement),
+            selected_inputs: value.selected_inputs.clone(),
+            fulfilled: value.fulfilled,
+        }
+    }
+}
+
+/// Dto for [`Requirement`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RequirementDto {
+    Sender {
+        address: AddressDto,
+    },
+    Issuer {
+        address: AddressDto,
+    },
+    Ed25519 {
+        address: AddressDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    Foundry {
+        foundry_id: FoundryId,
+    },
+    #[serde(rename_all = "camelCase")]
+    Alias {
+        alias_id: AliasId,
+        transition: AliasTransition,
+    },
+    #[serde(rename_all = "camelCase")]
+    Nft {
+        nft_id: NftId,
+    },
+    NativeTokens,
+    Amount,
+}
+
+impl From<&Requirement> for RequirementDto {
+    fn from(value: &Requirement) -> Self {
+        match value {
+            Requirement::Sender(address) => Self::Sender {
+                address: address.into(),
+            },
+            Requirement::Issuer(address) => Self::Issuer {
+                address: address.into(),
+            },
+            Requirement::Ed25519(address) => Self::Ed25519 {
+                address: address.into(),
+            },
+            Requirement::Foundry(foundry_id) => Self::Foundry {
+                foundry_id: *foundry_id,
+            },
+            Requirement::Alias(alias_id, transition) => Self::Alias {
+                alias_id: *alias_id,
+                transition: *transition,
+            },
+            Requirement::Nft(nft_id) => Self::Nft { nft_id: *nft_id },
+            Requirement::NativeTokens => Self::NativeTokens,
+            Requirement::Amount => Self::Amount,
+        }
+    }
+}
+
+/// Dto for [`RejectedInput`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedInputDto {
+    pub output_id: OutputId,
+    pub reason: RejectionReasonDto,
+}
+
+impl From<&RejectedInput> for RejectedInputDto {
+    fn from(value: &RejectedInput) -> Self {
+        Self {
+            output_id: value.output_id,
+            reason: RejectionReasonDto::from(&value.reason),
+        }
+    }
+}
+
+/// Dto for [`RejectionReason`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RejectionReasonDto {
+    Forbidden,
+    UnsupportedKind,
+    Timelocked {
+        until: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    Expiring {
+        expires_at: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    NotOwned {
+        required_address: AddressDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    StorageDepositReturn {
+        return_address: AddressDto,
+        #[serde(with = "crate::utils::serde::string")]
+        amount: u64,
+    },
+}
+
+impl From<&RejectionReason> for RejectionReasonDto {
+    fn from(value: &RejectionReason) -> Self {
+        match value {
+            RejectionReason::Forbidden => Self::Forbidden,
+            RejectionReason::UnsupportedKind => Self::UnsupportedKind,
+            RejectionReason::Timelocked { until } => Self::Timelocked { until: *until },
+            RejectionReason::Expiring { expires_at } => Self::Expiring {
+                expires_at: *expires_at,
+            },
+            RejectionReason::NotOwned { required_address } => Self::NotOwned {
+                required_address: required_address.into(),
+            },
+            RejectionReason::StorageDepositReturn { return_address, amount } => Self::StorageDepositReturn {
+                return_address: return_address.into(),
+                amount: *amount,
+            },
+        }
+    }
+}
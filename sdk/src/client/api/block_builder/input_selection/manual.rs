@@ -125,6 +125,10 @@ impl<'a> ClientBlockBuilder<'a> {
             input_selection = input_selection.remainder_address(address);
         }
 
+        if let Some(strategy) = &self.selection_strategy {
+            input_selection = input_selection.strategy(strategy.clone());
+        }
+
         if let Some(burn) = burn {
             input_selection = input_selection.burn(burn);
         }
@@ -10,4 +10,10 @@ mod sender_issuer;
 mod utxo_chains;
 
 pub(crate) use self::core::is_alias_transition;
-pub use self::core::{Burn, BurnDto, Error, InputSelection, Requirement, Selected};
+#[cfg(feature = "wallet")]
+pub(crate) use self::core::requirement::amount::sdruc_not_expired;
+pub use self::core::{
+    BuiltinSelectionStrategy, Burn, BurnDto, Error, InputSelection, RejectedInput, RejectedInputDto, RejectionReason,
+    RejectionReasonDto, Requirement, RequirementDto, RequirementTrace, RequirementTraceDto, Selected,
+    SelectionStrategy, SelectionTrace, SelectionTraceDto,
+};
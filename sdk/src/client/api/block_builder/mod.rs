@@ -5,12 +5,12 @@ pub mod input_selection;
 pub mod pow;
 pub mod transaction;
 
-use std::ops::Range;
+use std::{ops::Range, sync::Arc};
 
 use packable::bounded::TryIntoBoundedU16Error;
 use serde::{Deserialize, Serialize};
 
-use self::input_selection::BurnDto;
+use self::input_selection::{BuiltinSelectionStrategy, BurnDto, SelectionStrategy};
 pub use self::transaction::verify_semantic;
 use crate::{
     client::{
@@ -49,6 +49,7 @@ pub struct ClientBlockBuilder<'a> {
     data: Option<Vec<u8>>,
     parents: Option<Parents>,
     burn: Option<Burn>,
+    selection_strategy: Option<Arc<dyn SelectionStrategy>>,
 }
 
 /// Block output address
@@ -92,6 +93,8 @@ pub struct ClientBlockBuilderOptions {
     pub parents: Option<Vec<BlockId>>,
     /// Explicit burning of aliases, nfts, foundries and native tokens
     pub burn: Option<BurnDto>,
+    /// Strategy to select the inputs
+    pub selection_strategy: Option<BuiltinSelectionStrategy>,
 }
 
 impl<'a> ClientBlockBuilder<'a> {
@@ -111,6 +114,7 @@ impl<'a> ClientBlockBuilder<'a> {
             data: None,
             parents: None,
             burn: None,
+            selection_strategy: None,
         }
     }
 
@@ -120,6 +124,12 @@ impl<'a> ClientBlockBuilder<'a> {
         self
     }
 
+    /// Sets the strategy used to select the inputs covering the amount of the outputs.
+    pub fn with_selection_strategy(mut self, strategy: impl SelectionStrategy + 'static) -> Self {
+        self.selection_strategy.replace(Arc::new(strategy));
+        self
+    }
+
     /// Sets the seed.
     pub fn with_secret_manager(mut self, manager: &'a SecretManager) -> Self {
         self.secret_manager.replace(manager);
@@ -310,6 +320,10 @@ impl<'a> ClientBlockBuilder<'a> {
             self = self.with_burn(Burn::from(burn));
         }
 
+        if let Some(selection_strategy) = options.selection_strategy {
+            self = self.with_selection_strategy(selection_strategy);
+        }
+
         Ok(self)
     }
 
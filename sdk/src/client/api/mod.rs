@@ -7,8 +7,10 @@ mod address;
 mod block_builder;
 mod consolidation;
 mod high_level;
+mod partially_signed;
+pub(crate) mod summary;
 mod types;
 
-pub use self::{address::*, block_builder::*, types::*};
+pub use self::{address::*, block_builder::*, partially_signed::*, summary::*, types::*};
 
 const ADDRESS_GAP_RANGE: u32 = 20;
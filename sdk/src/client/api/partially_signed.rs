@@ -0,0 +1,260 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use crypto::keys::bip44::Bip44;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{
+        api::{
+            input_selection::{is_alias_transition, Error as InputSelectionError},
+            transaction::validate_transaction_payload_length,
+            verify_semantic, PreparedTransactionData, PreparedTransactionDataDto,
+        },
+        secret::{merge_unlocks, SecretManage},
+        Error, Result,
+    },
+    types::{
+        block::{
+            address::{Address, Ed25519Address},
+            payload::{transaction::TransactionEssence, TransactionPayload},
+            semantic::ConflictReason,
+            signature::{dto::SignatureDto, Signature},
+            unlock::{dto::SignatureUnlockDto, SignatureUnlock, Unlock, Unlocks},
+        },
+        TryFromDto, ValidationParams,
+    },
+};
+
+/// A transaction signed by some of the addresses required to unlock its inputs.
+///
+/// Used when the inputs of a transaction belong to different parties: each party adds its signatures, with
+/// [`PartiallySignedTransaction::sign()`] or [`PartiallySignedTransaction::add_signature_unlock()`], the partially
+/// signed transactions of all parties are merged and, once complete, finalized into a [`TransactionPayload`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartiallySignedTransaction {
+    prepared_transaction_data: PreparedTransactionData,
+    time: u32,
+    signature_unlocks: BTreeMap<Ed25519Address, SignatureUnlock>,
+}
+
+impl PartiallySignedTransaction {
+    /// Creates a [`PartiallySignedTransaction`] without signatures.
+    ///
+    /// `time` is the unix timestamp in seconds at which the addresses required to unlock the inputs are determined and
+    /// the transaction is validated, the current time if not provided. All parties have to use the same time.
+    pub fn new(prepared_transaction_data: PreparedTransactionData, time: impl Into<Option<u32>>) -> Self {
+        Self {
+            prepared_transaction_data,
+            time: time
+                .into()
+                .unwrap_or_else(|| crate::utils::unix_timestamp_now().as_secs() as u32),
+            signature_unlocks: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the transaction to sign.
+    pub fn prepared_transaction_data(&self) -> &PreparedTransactionData {
+        &self.prepared_transaction_data
+    }
+
+    /// Returns the unix timestamp in seconds at which the inputs are unlocked.
+    pub fn time(&self) -> u32 {
+        self.time
+    }
+
+    /// Returns the signature unlocks added so far, by the address they unlock.
+    pub fn signature_unlocks(&self) -> &BTreeMap<Ed25519Address, SignatureUnlock> {
+        &self.signature_unlocks
+    }
+
+    /// Returns the addresses that have to sign the transaction.
+    pub fn required_addresses(&self) -> Result<Vec<Ed25519Address>> {
+        Ok(self.required_signers()?.into_keys().collect())
+    }
+
+    /// Returns the addresses that have to sign the transaction but didn't yet.
+    pub fn missing_addresses(&self) -> Result<Vec<Ed25519Address>> {
+        Ok(self
+            .required_addresses()?
+            .into_iter()
+            .filter(|address| !self.signature_unlocks.contains_key(address))
+            .collect())
+    }
+
+    /// Returns whether all addresses required to unlock the inputs signed the transaction.
+    pub fn is_complete(&self) -> Result<bool> {
+        Ok(self.missing_addresses()?.is_empty())
+    }
+
+    /// Adds a signature unlock, which has to be a valid signature of the transaction essence by a required address.
+    pub fn add_signature_unlock(&mut self, signature_unlock: SignatureUnlock) -> Result<()> {
+        let Signature::Ed25519(signature) = signature_unlock.signature();
+        let hashed_essence = self.prepared_transaction_data.essence.hash();
+
+        let address = self
+            .required_addresses()?
+            .into_iter()
+            .find(|address| signature.is_valid(&hashed_essence, address).is_ok())
+            .ok_or(Error::UnexpectedSignature)?;
+
+        self.signature_unlocks.insert(address, signature_unlock);
+
+        Ok(())
+    }
+
+    /// Adds the signatures of the missing addresses that the secret manager holds the keys of.
+    ///
+    /// The secret manager signs with the BIP44 chains of the inputs, inputs without chain are skipped. Signatures that
+    /// don't match the address of the input are dropped, as the key belongs to another party. Returns the number of
+    /// added signatures.
+    pub async fn sign<M: SecretManage>(&mut self, secret_manager: &M) -> Result<usize>
+    where
+        Error: From<M::Error>,
+    {
+        let hashed_essence = self.prepared_transaction_data.essence.hash();
+        let (addresses, chains): (Vec<_>, Vec<_>) = self
+            .required_signers()?
+            .into_iter()
+            .filter(|(address, _)| !self.signature_unlocks.contains_key(address))
+            .filter_map(|(address, chain)| Some((address, chain?)))
+            .unzip();
+        let mut added = 0;
+
+        if chains.is_empty() {
+            return Ok(added);
+        }
+
+        let unlocks = secret_manager
+            .sign_transaction_essence_with_chains(&self.prepared_transaction_data, self.time, &chains)
+            .await?;
+
+        for (address, unlock) in addresses.into_iter().zip(unlocks) {
+            if let Unlock::Signature(signature_unlock) = unlock {
+                let Signature::Ed25519(signature) = signature_unlock.signature();
+
+                if signature.is_valid(&hashed_essence, &address).is_ok() {
+                    self.signature_unlocks.insert(address, signature_unlock);
+                    added += 1;
+                }
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Merges the signatures of another [`PartiallySignedTransaction`] of the same transaction.
+    pub fn merge(&mut self, other: Self) -> Result<()> {
+        if self.prepared_transaction_data.essence != other.prepared_transaction_data.essence || self.time != other.time
+        {
+            return Err(Error::PartiallySignedTransactionMismatch);
+        }
+
+        for (address, signature_unlock) in other.signature_unlocks {
+            self.signature_unlocks.entry(address).or_insert(signature_unlock);
+        }
+
+        Ok(())
+    }
+
+    /// Builds the unlocks of all inputs, merging the signature unlocks with Alias/Nft/Reference unlocks, and returns
+    /// the semantically valid [`TransactionPayload`]. Fails if a signature is missing.
+    pub fn finalize(self) -> Result<TransactionPayload> {
+        let unlocks = merge_unlocks(&self.prepared_transaction_data, self.time, |_, address| match address {
+            Address::Ed25519(address) => self
+                .signature_unlocks
+                .get(address)
+                .cloned()
+                .map(Unlock::Signature)
+                .ok_or(Error::MissingSignature(*address)),
+            _ => Err(InputSelectionError::MissingInputWithEd25519Address.into()),
+        })?;
+
+        let PreparedTransactionData {
+            essence, inputs_data, ..
+        } = self.prepared_transaction_data;
+        let tx_payload = TransactionPayload::new(essence, Unlocks::new(unlocks)?)?;
+
+        validate_transaction_payload_length(&tx_payload)?;
+
+        let conflict = verify_semantic(&inputs_data, &tx_payload, self.time)?;
+
+        if conflict != ConflictReason::None {
+            log::debug!("[finalize] conflict: {conflict:?} for {:#?}", tx_payload);
+            return Err(Error::TransactionSemantic(conflict));
+        }
+
+        Ok(tx_payload)
+    }
+
+    // Returns the Ed25519 addresses required to unlock the inputs, with the chain of the first input they unlock. An
+    // address unlocks all inputs requiring it with a single signature, other inputs reference it.
+    fn required_signers(&self) -> Result<BTreeMap<Ed25519Address, Option<Bip44>>> {
+        let TransactionEssence::Regular(regular) = &self.prepared_transaction_data.essence;
+        let mut signers = BTreeMap::new();
+
+        for input in &self.prepared_transaction_data.inputs_data {
+            let alias_transition = is_alias_transition(&input.output, *input.output_id(), regular.outputs(), None);
+            let (address, _) =
+                input
+                    .output
+                    .required_and_unlocked_address(self.time, input.output_id(), alias_transition)?;
+
+            if let Address::Ed25519(address) = address {
+                signers.entry(address).or_insert(input.chain);
+            }
+        }
+
+        Ok(signers)
+    }
+}
+
+/// Dto for a [`PartiallySignedTransaction`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartiallySignedTransactionDto {
+    /// The transaction to sign
+    pub prepared_transaction_data: PreparedTransactionDataDto,
+    /// The unix timestamp in seconds at which the inputs are unlocked
+    pub time: u32,
+    /// The signature unlocks added so far
+    pub signature_unlocks: Vec<SignatureUnlockDto>,
+}
+
+impl From<&PartiallySignedTransaction> for PartiallySignedTransactionDto {
+    fn from(value: &PartiallySignedTransaction) -> Self {
+        Self {
+            prepared_transaction_data: PreparedTransactionDataDto::from(&value.prepared_transaction_data),
+            time: value.time,
+            signature_unlocks: value
+                .signature_unlocks
+                .values()
+                .map(|signature_unlock| SignatureUnlockDto {
+                    kind: SignatureUnlock::KIND,
+                    signature: SignatureDto::from(signature_unlock.signature()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFromDto for PartiallySignedTransaction {
+    type Dto = PartiallySignedTransactionDto;
+    type Error = Error;
+
+    fn try_from_dto_with_params_inner(dto: Self::Dto, params: ValidationParams<'_>) -> Result<Self> {
+        let mut partially_signed_transaction = Self::new(
+            PreparedTransactionData::try_from_dto_with_params(dto.prepared_transaction_data, &params)?,
+            dto.time,
+        );
+
+        for signature_unlock in dto.signature_unlocks {
+            partially_signed_transaction
+                .add_signature_unlock(SignatureUnlock::new(Signature::try_from(signature_unlock.signature)?))?;
+        }
+
+        Ok(partially_signed_transaction)
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{api::PreparedTransactionData, secret::types::InputSigningData},
+    types::block::{
+        address::{dto::AddressDto, Address},
+        output::{AliasId, FoundryId, NativeToken, NftId, Output, OutputId, TokenId},
+        payload::{
+            dto::TaggedDataPayloadDto,
+            transaction::{RegularTransactionEssence, TransactionEssence},
+            Payload, TaggedDataPayload,
+        },
+    },
+};
+
+/// The effect of a transaction on the holdings of a single address.
+///
+/// Amounts are netted per address, e.g. an address sending 12 coins out of an input of 100 and receiving the
+/// remainder of 88 only sends 12. NFTs and aliases minted by the transaction have a null id, as their id derives from
+/// the id of the transaction, and are listed once each.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressSummary {
+    /// The address.
+    pub address: Address,
+    /// Base coin amount the address loses.
+    pub base_coin_sent: u64,
+    /// Base coin amount the address gains.
+    pub base_coin_received: u64,
+    /// Native tokens the address loses.
+    pub native_tokens_sent: Vec<NativeToken>,
+    /// Native tokens the address gains.
+    pub native_tokens_received: Vec<NativeToken>,
+    /// NFTs the address loses.
+    pub nfts_sent: Vec<NftId>,
+    /// NFTs the address gains.
+    pub nfts_received: Vec<NftId>,
+    /// Aliases the address loses the governance of.
+    pub aliases_sent: Vec<AliasId>,
+    /// Aliases the address gains the governance of.
+    pub aliases_received: Vec<AliasId>,
+    /// Foundries the alias address loses.
+    pub foundries_sent: Vec<FoundryId>,
+    /// Foundries the alias address gains.
+    pub foundries_received: Vec<FoundryId>,
+}
+
+impl AddressSummary {
+    fn is_empty(&self) -> bool {
+        self.base_coin_sent == 0
+            && self.base_coin_received == 0
+            && self.native_tokens_sent.is_empty()
+            && self.native_tokens_received.is_empty()
+            && self.nfts_sent.is_empty()
+            && self.nfts_received.is_empty()
+            && self.aliases_sent.is_empty()
+            && self.aliases_received.is_empty()
+            && self.foundries_sent.is_empty()
+            && self.foundries_received.is_empty()
+    }
+}
+
+/// A storage deposit that has to be returned to an address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StorageDepositSummary {
+    /// The address the storage deposit is returned to.
+    pub return_address: Address,
+    /// The amount of the storage deposit.
+    pub amount: u64,
+}
+
+/// A human-readable summary of the effects of a transaction, to review it before signing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionSummary {
+    /// The addresses whose holdings are changed by the transaction.
+    pub addresses: Vec<AddressSummary>,
+    /// Storage deposits locked in the created outputs, to be returned to their return address.
+    pub storage_deposits_locked: Vec<StorageDepositSummary>,
+    /// Storage deposits of the consumed outputs, returned by the transaction.
+    pub storage_deposits_returned: Vec<StorageDepositSummary>,
+    /// Native tokens consumed and not created by the transaction, either burned or melted.
+    pub burned_native_tokens: Vec<NativeToken>,
+    /// The tagged data attached to the transaction.
+    pub tagged_data: Option<TaggedDataPayload>,
+}
+
+// The assets held by an address in either the inputs or the outputs of a transaction. Chains minted by the transaction
+// all have the null id, so they are told apart by their output index.
+#[derive(Default)]
+struct Holdings {
+    base_coin: u64,
+    native_tokens: BTreeMap<TokenId, U256>,
+    nfts: BTreeSet<(NftId, Option<u16>)>,
+    aliases: BTreeSet<(AliasId, Option<u16>)>,
+    foundries: BTreeSet<FoundryId>,
+}
+
+impl Holdings {
+    // Adds the assets of an output, the output id is only known for inputs.
+    fn add(&mut self, output: &Output, output_id: Option<&OutputId>, output_index: u16) {
+        self.base_coin += output.amount();
+
+        if let Some(native_tokens) = output.native_tokens() {
+            add_native_tokens(&mut self.native_tokens, native_tokens.iter());
+        }
+
+        match output {
+            Output::Alias(alias) => {
+                let alias_id =
+                    output_id.map_or_else(|| *alias.alias_id(), |output_id| alias.alias_id_non_null(output_id));
+                self.aliases
+                    .insert((alias_id, alias_id.is_null().then_some(output_index)));
+            }
+            Output::Nft(nft) => {
+                let nft_id = output_id.map_or_else(|| *nft.nft_id(), |output_id| nft.nft_id_non_null(output_id));
+                self.nfts.insert((nft_id, nft_id.is_null().then_some(output_index)));
+            }
+            Output::Foundry(foundry) => {
+                self.foundries.insert(foundry.id());
+            }
+            Output::Basic(_) | Output::Treasury(_) => {}
+        }
+    }
+}
+
+fn add_native_tokens<'a>(totals: &mut BTreeMap<TokenId, U256>, native_tokens: impl Iterator<Item = &'a NativeToken>) {
+    for native_token in native_tokens {
+        let total = totals.entry(*native_token.token_id()).or_default();
+        *total = total.saturating_add(native_token.amount());
+    }
+}
+
+// Returns the native tokens of which more are held in `lhs` than in `rhs`, with the difference.
+fn native_tokens_difference(lhs: &BTreeMap<TokenId, U256>, rhs: &BTreeMap<TokenId, U256>) -> Vec<NativeToken> {
+    lhs.iter()
+        .filter_map(|(token_id, amount)| {
+            let difference = amount.saturating_sub(rhs.get(token_id).copied().unwrap_or_default());
+            // PANIC: the amount is not zero.
+            (!difference.is_zero()).then(|| NativeToken::new(*token_id, difference).unwrap())
+        })
+        .collect()
+}
+
+// Returns the ids of the chains held in `lhs` but not in `rhs`.
+fn chain_ids_difference<T: Copy + Ord>(lhs: &BTreeSet<(T, Option<u16>)>, rhs: &BTreeSet<(T, Option<u16>)>) -> Vec<T> {
+    lhs.difference(rhs).map(|(id, _)| *id).collect()
+}
+
+// Returns the address to which the assets of an output are attributed.
+pub(crate) fn owner(output: &Output) -> Option<Address> {
+    match output {
+        Output::Basic(basic) => Some(*basic.address()),
+        // Aliases are owned by their governor, the state controller only operates them.
+        Output::Alias(alias) => Some(*alias.governor_address()),
+        Output::Foundry(foundry) => Some(Address::Alias(*foundry.alias_address())),
+        Output::Nft(nft) => Some(*nft.address()),
+        Output::Treasury(_) => None,
+    }
+}
+
+fn storage_deposit(output: &Output) -> Option<StorageDepositSummary> {
+    output
+        .unlock_conditions()
+        .and_then(|unlock_conditions| unlock_conditions.storage_deposit_return())
+        .map(|storage_deposit_return| StorageDepositSummary {
+            return_address: *storage_deposit_return.return_address(),
+            amount: storage_deposit_return.amount(),
+        })
+}
+
+impl TransactionSummary {
+    /// Summarizes the effects of a transaction essence consuming the given inputs.
+    pub fn new(essence: &TransactionEssence, inputs_data: &[InputSigningData]) -> Self {
+        let TransactionEssence::Regular(essence) = essence;
+        let mut consumed = BTreeMap::<Address, Holdings>::new();
+        let mut created = BTreeMap::<Address, Holdings>::new();
+
+        for input in inputs_data {
+            if let Some(owner) = owner(&input.output) {
+                consumed.entry(owner).or_default().add(
+                    &input.output,
+                    Some(input.output_id()),
+                    input.output_id().index(),
+                );
+            }
+        }
+        for (output_index, output) in essence.outputs().iter().enumerate() {
+            if let Some(owner) = owner(output) {
+                created.entry(owner).or_default().add(output, None, output_index as u16);
+            }
+        }
+
+        let empty = Holdings::default();
+        let addresses = consumed
+            .keys()
+            .chain(created.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|address| {
+                let consumed = consumed.get(address).unwrap_or(&empty);
+                let created = created.get(address).unwrap_or(&empty);
+
+                AddressSummary {
+                    address: *address,
+                    base_coin_sent: consumed.base_coin.saturating_sub(created.base_coin),
+                    base_coin_received: created.base_coin.saturating_sub(consumed.base_coin),
+                    native_tokens_sent: native_tokens_difference(&consumed.native_tokens, &created.native_tokens),
+                    native_tokens_received: native_tokens_difference(&created.native_tokens, &consumed.native_tokens),
+                    nfts_sent: chain_ids_difference(&consumed.nfts, &created.nfts),
+                    nfts_received: chain_ids_difference(&created.nfts, &consumed.nfts),
+                    aliases_sent: chain_ids_difference(&consumed.aliases, &created.aliases),
+                    aliases_received: chain_ids_difference(&created.aliases, &consumed.aliases),
+                    foundries_sent: consumed.foundries.difference(&created.foundries).copied().collect(),
+                    foundries_received: created.foundries.difference(&consumed.foundries).copied().collect(),
+                }
+            })
+            .filter(|summary| !summary.is_empty())
+            .collect();
+
+        Self {
+            addresses,
+            storage_deposits_locked: essence.outputs().iter().filter_map(storage_deposit).collect(),
+            storage_deposits_returned: inputs_data
+                .iter()
+                .filter_map(|input| storage_deposit(&input.output))
+                .collect(),
+            burned_native_tokens: burned_native_tokens(essence, inputs_data),
+            tagged_data: match essence.payload() {
+                Some(Payload::TaggedData(tagged_data)) => Some(tagged_data.as_ref().clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the summary of the given address, if its holdings are changed by the transaction.
+    pub fn address(&self, address: &Address) -> Option<&AddressSummary> {
+        self.addresses.iter().find(|summary| &summary.address == address)
+    }
+}
+
+fn burned_native_tokens(essence: &RegularTransactionEssence, inputs_data: &[InputSigningData]) -> Vec<NativeToken> {
+    let mut consumed = BTreeMap::new();
+    let mut created = BTreeMap::new();
+
+    for input in inputs_data {
+        if let Some(native_tokens) = input.output.native_tokens() {
+            add_native_tokens(&mut consumed, native_tokens.iter());
+        }
+    }
+    for output in essence.outputs() {
+        if let Some(native_tokens) = output.native_tokens() {
+            add_native_tokens(&mut created, native_tokens.iter());
+        }
+    }
+
+    native_tokens_difference(&consumed, &created)
+}
+
+impl PreparedTransactionData {
+    /// Summarizes the effects of the prepared transaction, to review it before signing.
+    pub fn summary(&self) -> TransactionSummary {
+        TransactionSummary::new(&self.essence, &self.inputs_data)
+    }
+}
+
+/// Dto for [`AddressSummary`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSummaryDto {
+    pub address: AddressDto,
+    #[serde(with = "crate::utils::serde::string")]
+    pub base_coin_sent: u64,
+    #[serde(with = "crate::utils::serde::string")]
+    pub base_coin_received: u64,
+    pub native_tokens_sent: Vec<NativeToken>,
+    pub native_tokens_received: Vec<NativeToken>,
+    pub nfts_sent: Vec<NftId>,
+    pub nfts_received: Vec<NftId>,
+    pub aliases_sent: Vec<AliasId>,
+    pub aliases_received: Vec<AliasId>,
+    pub foundries_sent: Vec<FoundryId>,
+    pub foundries_received: Vec<FoundryId>,
+}
+
+impl From<&AddressSummary> for AddressSummaryDto {
+    fn from(value: &AddressSummary) -> Self {
+        Self {
+            address: AddressDto::from(&value.address),
+            base_coin_sent: value.base_coin_sent,
+            base_coin_received: value.base_coin_received,
+            native_tokens_sent: value.native_tokens_sent.clone(),
+            native_tokens_received: value.native_tokens_received.clone(),
+            nfts_sent: value.nfts_sent.clone(),
+            nfts_received: value.nfts_received.clone(),
+            aliases_sent: value.aliases_sent.clone(),
+            aliases_received: value.aliases_received.clone(),
+            foundries_sent: value.foundries_sent.clone(),
+            foundries_received: value.foundries_received.clone(),
+        }
+    }
+}
+
+/// Dto for [`StorageDepositSummary`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDepositSummaryDto {
+    pub return_address: AddressDto,
+    #[serde(with = "crate::utils::serde::string")]
+    pub amount: u64,
+}
+
+impl From<&StorageDepositSummary> for StorageDepositSummaryDto {
+    fn from(value: &StorageDepositSummary) -> Self {
+        Self {
+            return_address: AddressDto::from(&value.return_address),
+            amount: value.amount,
+        }
+    }
+}
+
+/// Dto for [`TransactionSummary`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSummaryDto {
+    pub addresses: Vec<AddressSummaryDto>,
+    pub storage_deposits_locked: Vec<StorageDepositSummaryDto>,
+    pub storage_deposits_returned: Vec<StorageDepositSummaryDto>,
+    pub burned_native_tokens: Vec<NativeToken>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tagged_data: Option<TaggedDataPayloadDto>,
+}
+
+impl From<&TransactionSummary> for TransactionSummaryDto {
+    fn from(value: &TransactionSummary) -> Self {
+        Self {
+            addresses: value.addresses.iter().map(Into::into).collect(),
+            storage_deposits_locked: value.storage_deposits_locked.iter().map(Into::into).collect(),
+            storage_deposits_returned: value.storage_deposits_returned.iter().map(Into::into).collect(),
+            burned_native_tokens: value.burned_native_tokens.clone(),
+            tagged_data: value.tagged_data.as_ref().map(Into::into),
+        }
+    }
+}
@@ -11,12 +11,18 @@ use super::{node_manager::builder::NodeManagerBuilder, ClientInner};
 use crate::client::node_api::mqtt::{BrokerOptions, MqttEvent};
 use crate::{
     client::{
+        cache::{ResponseCache, SharedCacheStorage},
         constants::{DEFAULT_API_TIMEOUT, DEFAULT_REMOTE_POW_API_TIMEOUT, DEFAULT_TIPS_INTERVAL},
         error::Result,
+        light_client::{CoordinatorConfig, MilestoneVerifier},
         node_manager::{
             builder::validate_url,
             node::{Node, NodeAuth},
+            retry::RetryPolicy,
+            statistics::CircuitBreakerOptions,
+            transport::{SharedTransport, Transport},
         },
+        storage::StorageAdapter,
         Client,
     },
     types::block::protocol::ProtocolParameters,
@@ -52,6 +58,15 @@ pub struct ClientBuilder {
     #[cfg(not(target_family = "wasm"))]
     #[serde(default = "default_max_parallel_api_requests")]
     pub max_parallel_api_requests: usize,
+    /// The maximum amount of immutable node responses kept in the response cache, disabled if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache_capacity: Option<usize>,
+    /// The storage the response cache is persisted in
+    #[serde(skip)]
+    pub(crate) response_cache_storage: Option<SharedCacheStorage>,
+    /// The coordinator configuration milestones are verified against, disabled if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone_verification: Option<CoordinatorConfig>,
 }
 
 fn default_api_timeout() -> Duration {
@@ -93,6 +108,9 @@ impl Default for ClientBuilder {
             pow_worker_count: None,
             #[cfg(not(target_family = "wasm"))]
             max_parallel_api_requests: super::constants::MAX_PARALLEL_API_REQUESTS,
+            response_cache_capacity: None,
+            response_cache_storage: None,
+            milestone_verification: None,
         }
     }
 }
@@ -196,6 +214,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets after how many consecutive failures a node is left out, and for how long.
+    pub fn with_circuit_breaker(mut self, options: CircuitBreakerOptions) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_circuit_breaker(options);
+        self
+    }
+
+    /// Sets how requests are retried when all nodes failed with a transient error.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_retry_policy(retry_policy);
+        self
+    }
+
     /// Sets the MQTT broker options.
     #[cfg(feature = "mqtt")]
     #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
@@ -248,6 +278,39 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the [`Transport`] requests to nodes are sent with.
+    /// Default is [`ReqwestTransport`](crate::client::node_manager::transport::ReqwestTransport)
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_transport(SharedTransport(Arc::new(transport)));
+        self
+    }
+
+    /// Caches up to `capacity` responses that can't change anymore, like blocks, milestones and spent outputs, so
+    /// that they are only requested once.
+    pub fn with_response_cache(mut self, capacity: usize) -> Self {
+        self.response_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Persists the response cache in the given storage, so that cached responses outlive the client.
+    /// Only has an effect if the response cache is enabled with [`ClientBuilder::with_response_cache()`].
+    pub fn with_response_cache_storage<S: StorageAdapter + 'static>(mut self, storage: S) -> Self
+    where
+        S::Error: std::fmt::Display,
+    {
+        self.response_cache_storage = Some(SharedCacheStorage(Arc::new(storage)));
+        self
+    }
+
+    /// Verifies the signatures and the linkage of milestones against the given coordinator configuration, and refuses
+    /// confirmation data of blocks and outputs that isn't backed by a verified milestone.
+    pub fn with_milestone_verification(mut self, config: CoordinatorConfig) -> Self {
+        self.milestone_verification = Some(config);
+        self
+    }
+
     /// Set maximum parallel API requests.
     #[cfg(not(target_family = "wasm"))]
     pub fn with_max_parallel_api_requests(mut self, max_parallel_api_requests: usize) -> Self {
@@ -283,11 +346,21 @@ impl ClientBuilder {
             mqtt: super::MqttInner {
                 client: Default::default(),
                 topic_handlers: Default::default(),
+                last_milestone_index: Default::default(),
+                resynchronization: Default::default(),
                 broker_options: RwLock::new(self.broker_options),
                 sender: RwLock::new(mqtt_event_tx),
                 receiver: RwLock::new(mqtt_event_rx),
             },
             request_pool: crate::client::request_pool::RequestPool::new(self.max_parallel_api_requests),
+            response_cache: RwLock::new(
+                self.response_cache_capacity
+                    .map(|capacity| Arc::new(ResponseCache::new(capacity, self.response_cache_storage))),
+            ),
+            milestone_verifier: RwLock::new(
+                self.milestone_verification
+                    .map(|config| Arc::new(MilestoneVerifier::new(config))),
+            ),
         });
 
         client_inner.sync_nodes(&nodes, ignore_node_health).await?;
@@ -325,11 +398,21 @@ impl ClientBuilder {
                 mqtt: super::MqttInner {
                     client: Default::default(),
                     topic_handlers: Default::default(),
+                    last_milestone_index: Default::default(),
+                    resynchronization: Default::default(),
                     broker_options: RwLock::new(self.broker_options),
                     sender: RwLock::new(mqtt_event_tx),
                     receiver: RwLock::new(mqtt_event_rx),
                 },
                 last_sync: tokio::sync::Mutex::new(None),
+                response_cache: RwLock::new(
+                    self.response_cache_capacity
+                        .map(|capacity| Arc::new(ResponseCache::new(capacity, self.response_cache_storage))),
+                ),
+                milestone_verifier: RwLock::new(
+                    self.milestone_verification
+                        .map(|config| Arc::new(MilestoneVerifier::new(config))),
+                ),
             }),
         };
 
@@ -337,6 +420,8 @@ impl ClientBuilder {
     }
 
     pub async fn from_client(client: &Client) -> Self {
+        let response_cache = client.response_cache.read().await.clone();
+
         Self {
             node_manager_builder: NodeManagerBuilder::from(&*client.node_manager.read().await),
             #[cfg(feature = "mqtt")]
@@ -348,6 +433,14 @@ impl ClientBuilder {
             pow_worker_count: *client.pow_worker_count.read().await,
             #[cfg(not(target_family = "wasm"))]
             max_parallel_api_requests: client.request_pool.size().await,
+            response_cache_capacity: response_cache.as_ref().map(|cache| cache.capacity()),
+            response_cache_storage: response_cache.as_ref().and_then(|cache| cache.storage()),
+            milestone_verification: client
+                .milestone_verifier
+                .read()
+                .await
+                .as_ref()
+                .map(|verifier| verifier.config().clone()),
         }
     }
 }
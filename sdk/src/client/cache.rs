@@ -0,0 +1,255 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A cache of node responses that never change, like blocks, milestones and spent outputs.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Display},
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    client::{storage::StorageAdapter, ClientInner, Result},
+    types::block::output::OutputMetadata,
+};
+
+/// Prefix of the keys of cached responses in the storage.
+const STORAGE_KEY_PREFIX: &str = "response-cache-";
+
+/// Hit and miss counters of the response cache.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseCacheStatistics {
+    /// Amount of requests answered from the cache.
+    pub hits: u64,
+    /// Amount of requests that had to be sent to a node.
+    pub misses: u64,
+    /// Amount of responses held in memory.
+    pub entries: usize,
+}
+
+/// A storage the response cache is persisted in, so that it outlives the client.
+#[async_trait]
+pub(crate) trait CacheStorage: Debug + Send + Sync {
+    async fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+    async fn set(&self, key: &str, value: &[u8]);
+}
+
+// Failing to read from or write to the storage only costs a request, so errors are logged and otherwise ignored.
+#[async_trait]
+impl<S: StorageAdapter> CacheStorage for S
+where
+    S::Error: Display,
+{
+    async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        self.get_bytes(key)
+            .await
+            .map_err(|e| log::warn!("[response cache] couldn't read {key}: {e}"))
+            .ok()
+            .flatten()
+    }
+
+    async fn set(&self, key: &str, value: &[u8]) {
+        if let Err(e) = self.set_bytes(key, value).await {
+            log::warn!("[response cache] couldn't write {key}: {e}");
+        }
+    }
+}
+
+/// A [`CacheStorage`] shared between clients, compared by identity.
+#[derive(Clone)]
+pub(crate) struct SharedCacheStorage(pub(crate) Arc<dyn CacheStorage>);
+
+impl Debug for SharedCacheStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for SharedCacheStorage {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::as_ptr(&self.0).cast::<()>() == Arc::as_ptr(&other.0).cast::<()>()
+    }
+}
+
+impl Eq for SharedCacheStorage {}
+
+/// The least recently used responses, by path.
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<String, (Vec<u8>, u64)>,
+    // The paths by the tick they were last used at, to find the least recently used one.
+    usage: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl Lru {
+    fn get(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.tick += 1;
+        let (value, last_used) = self.entries.get_mut(path)?;
+        self.usage.remove(last_used);
+        self.usage.insert(self.tick, path.to_string());
+        *last_used = self.tick;
+
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, path: &str, value: Vec<u8>, capacity: usize) {
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.insert(path.to_string(), (value, self.tick)) {
+            self.usage.remove(&last_used);
+        }
+        self.usage.insert(self.tick, path.to_string());
+
+        while self.entries.len() > capacity {
+            let Some((_, path)) = self.usage.pop_first() else {
+                break;
+            };
+            self.entries.remove(&path);
+        }
+    }
+}
+
+/// A bounded LRU cache of immutable node responses, optionally backed by a storage.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    capacity: usize,
+    storage: Option<SharedCacheStorage>,
+    lru: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    // The latest ledger index of the node responses, as the one of cached outputs is outdated.
+    ledger_index: AtomicU32,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(capacity: usize, storage: Option<SharedCacheStorage>) -> Self {
+        Self {
+            capacity,
+            storage,
+            lru: Default::default(),
+            hits: Default::default(),
+            misses: Default::default(),
+            ledger_index: Default::default(),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn storage(&self) -> Option<SharedCacheStorage> {
+        self.storage.clone()
+    }
+
+    async fn get(&self, path: &str) -> Option<Vec<u8>> {
+        let mut value = self.lru.lock().expect("response cache lock poisoned").get(path);
+
+        if value.is_none() {
+            if let Some(storage) = &self.storage {
+                value = storage.0.get(&format!("{STORAGE_KEY_PREFIX}{path}")).await;
+                if let Some(value) = &value {
+                    self.lru
+                        .lock()
+                        .expect("response cache lock poisoned")
+                        .insert(path, value.clone(), self.capacity);
+                }
+            }
+        }
+
+        match value {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+
+        value
+    }
+
+    async fn insert(&self, path: &str, value: Vec<u8>) {
+        if let Some(storage) = &self.storage {
+            storage.0.set(&format!("{STORAGE_KEY_PREFIX}{path}"), &value).await;
+        }
+        self.lru
+            .lock()
+            .expect("response cache lock poisoned")
+            .insert(path, value, self.capacity);
+    }
+
+    // Records the ledger index of a node response and returns the latest one.
+    fn update_ledger_index(&self, ledger_index: u32) -> u32 {
+        self.ledger_index
+            .fetch_max(ledger_index, Ordering::Relaxed)
+            .max(ledger_index)
+    }
+
+    fn statistics(&self) -> ResponseCacheStatistics {
+        ResponseCacheStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lru.lock().expect("response cache lock poisoned").entries.len(),
+        }
+    }
+}
+
+impl ClientInner {
+    /// Returns the hit and miss counters of the response cache, if it is enabled.
+    pub async fn response_cache_statistics(&self) -> Option<ResponseCacheStatistics> {
+        self.response_cache
+            .read()
+            .await
+            .as_deref()
+            .map(ResponseCache::statistics)
+    }
+
+    /// Records the ledger index of a node response, which replaces the outdated one of outputs answered from the
+    /// response cache.
+    pub(crate) async fn update_ledger_index(&self, ledger_index: u32) {
+        if let Some(cache) = self.response_cache.read().await.as_deref() {
+            cache.update_ledger_index(ledger_index);
+        }
+    }
+
+    /// Replaces the ledger index of output metadata, which is outdated if it was answered from the response cache, with
+    /// the latest one.
+    pub(crate) async fn refresh_ledger_index(&self, metadata: &mut OutputMetadata) {
+        if let Some(cache) = self.response_cache.read().await.as_deref() {
+            metadata.set_ledger_index(cache.update_ledger_index(metadata.ledger_index()));
+        }
+    }
+
+    /// Sends a GET request like [`ClientInner::get_request()`], answering it from the response cache if possible.
+    /// Responses are only cached if `is_immutable` holds for them.
+    pub(crate) async fn get_request_cached<T: DeserializeOwned + Debug + Serialize>(
+        &self,
+        path: &str,
+        need_quorum: bool,
+        prefer_permanode: bool,
+        is_immutable: impl Fn(&T) -> bool + Send,
+    ) -> Result<T> {
+        let Some(cache) = self.response_cache.read().await.clone() else {
+            return self.get_request(path, None, need_quorum, prefer_permanode).await;
+        };
+
+        if let Some(value) = cache.get(path).await {
+            match serde_json::from_slice(&value) {
+                Ok(response) => return Ok(response),
+                Err(e) => log::warn!("[response cache] invalid response for {path}: {e}"),
+            }
+        }
+
+        let response = self.get_request(path, None, need_quorum, prefer_permanode).await?;
+        if is_immutable(&response) {
+            cache.insert(path, serde_json::to_vec(&response)?).await;
+        }
+
+        Ok(response)
+    }
+}
@@ -19,6 +19,14 @@ pub(crate) const DEFAULT_TIPS_INTERVAL: u64 = 5;
 pub(crate) const NODE_SYNC_INTERVAL: Duration = Duration::from_secs(60);
 pub(crate) const DEFAULT_MIN_QUORUM_SIZE: usize = 3;
 pub(crate) const DEFAULT_QUORUM_THRESHOLD: usize = 66;
+/// Amount of consecutive failed requests after which a node is left out for the cool-down
+pub(crate) const DEFAULT_NODE_FAILURE_THRESHOLD: u32 = 3;
+pub(crate) const DEFAULT_NODE_COOL_DOWN: Duration = Duration::from_secs(30);
+/// Amount of attempts of a request that failed with a transient error, including the first one, so requests are only
+/// retried if enabled
+pub(crate) const DEFAULT_RETRY_MAX_ATTEMPTS: u32 = 1;
+pub(crate) const DEFAULT_RETRY_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+pub(crate) const DEFAULT_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(10);
 pub(crate) const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 #[cfg(not(target_family = "wasm"))]
 pub(crate) const MAX_PARALLEL_API_REQUESTS: usize = 100;
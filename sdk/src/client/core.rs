@@ -8,7 +8,7 @@ use std::{sync::Arc, time::Duration};
 use tokio::sync::RwLock;
 #[cfg(feature = "mqtt")]
 use {
-    crate::client::node_api::mqtt::{BrokerOptions, MqttEvent, TopicHandlerMap},
+    crate::client::node_api::mqtt::{BrokerOptions, MqttEvent, Resynchronization, TopicHandlerMap},
     rumqttc::AsyncClient as MqttClient,
     tokio::sync::watch::{Receiver as WatchReceiver, Sender as WatchSender},
 };
@@ -20,7 +20,9 @@ use crate::client::constants::CACHE_NETWORK_INFO_TIMEOUT_IN_SECONDS;
 use crate::{
     client::{
         builder::{ClientBuilder, NetworkInfo},
+        cache::ResponseCache,
         error::Result,
+        light_client::MilestoneVerifier,
         node_manager::NodeManager,
         Error,
     },
@@ -60,6 +62,9 @@ pub struct ClientInner {
     pub(crate) last_sync: tokio::sync::Mutex<Option<u32>>,
     #[cfg(not(target_family = "wasm"))]
     pub(crate) request_pool: RequestPool,
+    pub(crate) response_cache: RwLock<Option<Arc<ResponseCache>>>,
+    /// Verifies milestones before confirmation data referencing them is accepted.
+    pub(crate) milestone_verifier: RwLock<Option<Arc<MilestoneVerifier>>>,
 }
 
 #[derive(Default)]
@@ -79,6 +84,10 @@ pub(crate) struct MqttInner {
     /// A MQTT client to subscribe/unsubscribe to topics.
     pub(crate) client: RwLock<Option<MqttClient>>,
     pub(crate) topic_handlers: RwLock<TopicHandlerMap>,
+    /// The index of the latest confirmed milestone seen, from which missed events are backfilled after a reconnection.
+    pub(crate) last_milestone_index: RwLock<Option<u32>>,
+    /// The backfill of missed events after a reconnection.
+    pub(crate) resynchronization: tokio::sync::Mutex<Resynchronization>,
     pub(crate) broker_options: RwLock<BrokerOptions>,
     pub(crate) sender: RwLock<WatchSender<MqttEvent>>,
     pub(crate) receiver: RwLock<WatchReceiver<MqttEvent>>,
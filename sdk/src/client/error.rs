@@ -56,6 +56,9 @@ pub enum Error {
     /// Invalid amount in API response
     #[error("invalid amount in API response: {0}")]
     InvalidAmount(String),
+    /// The inclusion proof of a block doesn't lead to the inclusion Merkle root of its milestone
+    #[error("invalid inclusion proof of block {0}")]
+    InvalidInclusionProof(crate::types::block::BlockId),
     /// Invalid mnemonic error
     #[error("invalid mnemonic {0}")]
     InvalidMnemonic(String),
@@ -75,12 +78,35 @@ pub enum Error {
         /// The max length.
         max_length: usize,
     },
+    /// IO error
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
     /// JSON error
     #[error("{0}")]
     Json(#[from] serde_json::Error),
+    /// Milestone verification error
+    #[error("{0}")]
+    MilestoneVerification(#[from] crate::client::light_client::MilestoneVerificationError),
     /// Missing required parameters
     #[error("must provide required parameter: {0}")]
     MissingParameter(&'static str),
+    /// Missing public key of an address of a watch-only secret manager
+    #[error(
+        "missing public key of address {address_index} (internal: {internal}) of account {account_index} with coin type {coin_type}"
+    )]
+    MissingPublicKey {
+        /// The coin type of the account.
+        coin_type: u32,
+        /// The index of the account.
+        account_index: u32,
+        /// Whether the address is internal.
+        internal: bool,
+        /// The index of the address.
+        address_index: u32,
+    },
+    /// Missing signature of an address required to unlock inputs of a transaction
+    #[error("missing signature of address {0}")]
+    MissingSignature(crate::types::block::address::Ed25519Address),
     /// Error on API request
     #[error("node error: {0}")]
     Node(#[from] crate::client::node_api::error::Error),
@@ -90,12 +116,18 @@ pub enum Error {
     /// Requested output id not found for this type
     #[error("No output found for {0}")]
     NoOutput(String),
+    /// Partially signed transactions of different transactions can't be merged
+    #[error("can't merge partially signed transactions of different transactions")]
+    PartiallySignedTransactionMismatch,
     /// PlaceholderSecretManager can't be used for address generation or signing
     #[error("placeholderSecretManager can't be used for address generation or signing")]
     PlaceholderSecretManager,
     /// Rw lock failed.
     #[error("rw lock failed")]
     PoisonError,
+    /// A signing request breaks a rule of the signing policy
+    #[error("signing policy violation: {0}")]
+    PolicyViolation(crate::client::secret::policy::PolicyViolation),
     /// Prefix hex string convert error
     #[error("{0}")]
     PrefixHex(#[from] prefix_hex::Error),
@@ -121,6 +153,9 @@ pub enum Error {
     /// No node available in the healthy node pool
     #[error("no healthy node available")]
     HealthyNodePoolEmpty,
+    /// SLIP-39 error
+    #[error("{0}")]
+    Slip39(#[from] crate::client::slip39::Error),
     /// Error when building tagged_data blocks
     #[error("error when building tagged_data block: {0}")]
     TaggedData(String),
@@ -144,6 +179,9 @@ pub enum Error {
     /// The semantic validation of a transaction failed.
     #[error("the semantic validation of a transaction failed with conflict reason: {} - {0:?}", *.0 as u8)]
     TransactionSemantic(ConflictReason),
+    /// A signature isn't a valid signature of the transaction by an address required to unlock its inputs
+    #[error("the signature isn't a valid signature of the transaction by a required address")]
+    UnexpectedSignature,
     /// An indexer API request contains a query parameter not supported by the endpoint.
     #[error("an indexer API request contains a query parameter not supported by the endpoint: {0}.")]
     UnsupportedQueryParameter(QueryParameter),
@@ -159,6 +197,9 @@ pub enum Error {
     /// URL validation error
     #[error("{0}")]
     UrlValidation(String),
+    /// WatchOnlySecretManager can't be used for signing
+    #[error("watch-only secret manager can't be used for signing")]
+    WatchOnlySecretManager,
     /// Input selection error.
     #[error("{0}")]
     InputSelection(#[from] InputSelectionError),
@@ -178,6 +219,12 @@ pub enum Error {
     #[error("{0}")]
     Ledger(#[from] crate::client::secret::ledger_nano::Error),
 
+    /// Mock node error
+    #[cfg(feature = "mock_node")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mock_node")))]
+    #[error("{0}")]
+    MockNode(#[from] crate::client::mock_node::Error),
+
     /// MQTT error
     #[cfg(feature = "mqtt")]
     #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
@@ -0,0 +1,319 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of milestones against the coordinator public keys, so that confirmation data given by a node doesn't
+//! have to be trusted.
+
+use std::{collections::BTreeMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{ClientInner, Error, Result},
+    types::{
+        api::core::response::BlockMetadataResponse,
+        block::{
+            output::{OutputId, OutputMetadata},
+            payload::{
+                milestone::{MilestoneId, MilestonePayload, MilestoneValidationError},
+                Payload,
+            },
+            BlockId,
+        },
+    },
+};
+
+/// Maximum amount of verified milestones remembered to check the linkage of their neighbours against.
+const MAX_VERIFIED_MILESTONES: usize = 10_000;
+
+/// A coordinator public key and the range of milestone indexes it can sign.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestonePublicKeyRange {
+    /// The Ed25519 public key, hex encoded without prefix.
+    pub key: String,
+    /// The first milestone index the key is applicable to.
+    pub start: u32,
+    /// The last milestone index the key is applicable to, 0 if it doesn't expire.
+    pub end: u32,
+}
+
+impl MilestonePublicKeyRange {
+    /// Whether the key is applicable to the milestone with the given index.
+    pub fn contains(&self, index: u32) -> bool {
+        self.start <= index && (self.end == 0 || index <= self.end)
+    }
+}
+
+/// The coordinator configuration milestones are verified against, as found in the protocol configuration of a node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoordinatorConfig {
+    /// The amount of valid signatures of applicable keys a milestone needs.
+    pub milestone_public_key_count: usize,
+    /// The coordinator public keys and the milestone indexes they are applicable to.
+    pub public_key_ranges: Vec<MilestonePublicKeyRange>,
+}
+
+impl CoordinatorConfig {
+    /// Returns the public keys applicable to the milestone with the given index.
+    pub fn applicable_public_keys(&self, index: u32) -> Vec<String> {
+        self.public_key_ranges
+            .iter()
+            .filter(|range| range.contains(index))
+            .map(|range| range.key.trim_start_matches("0x").to_ascii_lowercase())
+            .collect()
+    }
+}
+
+/// Errors of the milestone verification.
+#[derive(Debug, thiserror::Error)]
+pub enum MilestoneVerificationError {
+    /// The milestone isn't signed by enough applicable coordinator keys.
+    #[error("invalid signatures of milestone {index}: {error:?}")]
+    InvalidSignatures {
+        /// The index of the milestone.
+        index: u32,
+        /// The reason the signatures are invalid.
+        error: MilestoneValidationError,
+    },
+    /// The milestone doesn't reference the previous verified milestone.
+    #[error("milestone {index} references previous milestone {found}, expected {expected}")]
+    BrokenChain {
+        /// The index of the milestone.
+        index: u32,
+        /// The id of the verified milestone preceding it.
+        expected: MilestoneId,
+        /// The previous milestone id it references.
+        found: MilestoneId,
+    },
+    /// Another milestone was already verified for the same index.
+    #[error("milestone {found} conflicts with verified milestone {expected} at index {index}")]
+    Conflicting {
+        /// The index of the milestones.
+        index: u32,
+        /// The id of the verified milestone.
+        expected: MilestoneId,
+        /// The id of the conflicting milestone.
+        found: MilestoneId,
+    },
+    /// The node answered with another milestone than the one requested.
+    #[error("requested milestone {requested}, got {found}")]
+    UnexpectedMilestone {
+        /// The index or id of the requested milestone.
+        requested: String,
+        /// The index or id of the received milestone.
+        found: String,
+    },
+    /// The block isn't included in the milestone its metadata claims references it.
+    #[error("block {block_id} isn't included in milestone {index}")]
+    UnconfirmedBlock {
+        /// The index of the milestone.
+        index: u32,
+        /// The id of the block.
+        block_id: BlockId,
+    },
+    /// The output isn't part of the UTXO changes of the milestone its metadata claims booked or spent it.
+    #[error("output {output_id} isn't changed by milestone {index}")]
+    UnconfirmedOutput {
+        /// The index of the milestone.
+        index: u32,
+        /// The id of the output.
+        output_id: OutputId,
+    },
+}
+
+/// A milestone verified by the [`MilestoneVerifier`].
+#[derive(Copy, Clone, Debug)]
+struct VerifiedMilestone {
+    id: MilestoneId,
+    previous_milestone_id: MilestoneId,
+}
+
+/// Verifies the signatures of milestones and that verified milestones form a chain through their
+/// `previous_milestone_id`.
+#[derive(Debug)]
+pub struct MilestoneVerifier {
+    config: CoordinatorConfig,
+    verified: Mutex<BTreeMap<u32, VerifiedMilestone>>,
+}
+
+impl MilestoneVerifier {
+    /// Creates a new [`MilestoneVerifier`] with the given coordinator configuration.
+    pub fn new(config: CoordinatorConfig) -> Self {
+        Self {
+            config,
+            verified: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the coordinator configuration milestones are verified against.
+    pub fn config(&self) -> &CoordinatorConfig {
+        &self.config
+    }
+
+    /// Returns the id of the verified milestone with the given index, if any.
+    pub fn verified_milestone_id(&self, index: u32) -> Option<MilestoneId> {
+        self.verified.lock().unwrap().get(&index).map(|milestone| milestone.id)
+    }
+
+    /// Verifies that a milestone is signed by enough applicable coordinator keys and that it links to the verified
+    /// milestones preceding and following it.
+    pub fn verify(&self, milestone: &MilestonePayload) -> std::result::Result<(), MilestoneVerificationError> {
+        let index = *milestone.essence().index();
+        let id = milestone.id();
+        let previous_milestone_id = *milestone.essence().previous_milestone_id();
+
+        if let Some(expected) = self.verified_milestone_id(index) {
+            return if expected == id {
+                Ok(())
+            } else {
+                Err(MilestoneVerificationError::Conflicting {
+                    index,
+                    expected,
+                    found: id,
+                })
+            };
+        }
+
+        milestone
+            .validate(
+                &self.config.applicable_public_keys(index),
+                self.config.milestone_public_key_count,
+            )
+            .map_err(|error| MilestoneVerificationError::InvalidSignatures { index, error })?;
+
+        let mut verified = self.verified.lock().unwrap();
+
+        if let Some(previous) = index.checked_sub(1).and_then(|i| verified.get(&i)) {
+            if previous.id != previous_milestone_id {
+                return Err(MilestoneVerificationError::BrokenChain {
+                    index,
+                    expected: previous.id,
+                    found: previous_milestone_id,
+                });
+            }
+        }
+        if let Some(next) = verified.get(&(index + 1)) {
+            if next.previous_milestone_id != id {
+                return Err(MilestoneVerificationError::BrokenChain {
+                    index: index + 1,
+                    expected: id,
+                    found: next.previous_milestone_id,
+                });
+            }
+        }
+
+        verified.insert(
+            index,
+            VerifiedMilestone {
+                id,
+                previous_milestone_id,
+            },
+        );
+        if verified.len() > MAX_VERIFIED_MILESTONES {
+            verified.pop_first();
+        }
+
+        Ok(())
+    }
+}
+
+impl ClientInner {
+    /// Verifies a milestone received from a node, if milestone verification is enabled.
+    pub(crate) async fn verify_milestone(&self, milestone: &MilestonePayload) -> Result<()> {
+        if let Some(verifier) = self.milestone_verifier.read().await.as_ref() {
+            verifier.verify(milestone)?;
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure the milestone with the given index is verified before accepting confirmation data referencing it,
+    /// if milestone verification is enabled.
+    pub(crate) async fn verify_confirmation(&self, milestone_index: u32) -> Result<()> {
+        // Index 0 is the snapshot the ledger starts from, there is no milestone for it.
+        if milestone_index == 0 {
+            return Ok(());
+        }
+        let verifier = self.milestone_verifier.read().await.clone();
+
+        if let Some(verifier) = verifier {
+            if verifier.verified_milestone_id(milestone_index).is_none() {
+                // The milestone is verified when it's received.
+                self.get_milestone_by_index(milestone_index).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure a block is included in the milestone its metadata claims references it, with an inclusion proof
+    /// against the inclusion Merkle root of the verified milestone, if milestone verification is enabled.
+    pub(crate) async fn verify_block_confirmation(&self, metadata: &BlockMetadataResponse) -> Result<()> {
+        let Some(milestone_index) = metadata.referenced_by_milestone_index.filter(|index| *index != 0) else {
+            return Ok(());
+        };
+        if self.milestone_verifier.read().await.is_none() {
+            return Ok(());
+        }
+
+        // A milestone block isn't part of the inclusion Merkle tree, it's confirmed by the signatures of its milestone.
+        let block = self.get_block(&metadata.block_id).await?;
+        if block.id() != metadata.block_id {
+            return Err(Error::InvalidInclusionProof(metadata.block_id));
+        }
+        if let Some(Payload::Milestone(milestone)) = block.payload() {
+            if *milestone.essence().index() == milestone_index {
+                return self.verify_milestone(milestone).await;
+            }
+        }
+
+        // The proof and its milestone are verified when it's received.
+        let proof = self.get_inclusion_proof(&metadata.block_id).await?;
+        if *proof.milestone().essence().index() != milestone_index {
+            return Err(MilestoneVerificationError::UnconfirmedBlock {
+                index: milestone_index,
+                block_id: metadata.block_id,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure an output is part of the UTXO changes of the verified milestones that booked and spent it, if
+    /// milestone verification is enabled.
+    pub(crate) async fn verify_output_confirmation(&self, metadata: &OutputMetadata) -> Result<()> {
+        if self.milestone_verifier.read().await.is_none() {
+            return Ok(());
+        }
+
+        let output_id = metadata.output_id();
+        // Index 0 is the snapshot the ledger starts from, there are no UTXO changes for it.
+        if metadata.milestone_index_booked() != 0 {
+            let utxo_changes = self
+                .get_utxo_changes_by_index(metadata.milestone_index_booked())
+                .await?;
+            if !utxo_changes.created_outputs.contains(output_id) {
+                return Err(MilestoneVerificationError::UnconfirmedOutput {
+                    index: metadata.milestone_index_booked(),
+                    output_id: *output_id,
+                }
+                .into());
+            }
+        }
+        if let Some(milestone_index_spent) = metadata.milestone_index_spent() {
+            let utxo_changes = self.get_utxo_changes_by_index(milestone_index_spent).await?;
+            if !utxo_changes.consumed_outputs.contains(output_id) {
+                return Err(MilestoneVerificationError::UnconfirmedOutput {
+                    index: milestone_index_spent,
+                    output_id: *output_id,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
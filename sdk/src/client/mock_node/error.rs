@@ -0,0 +1,22 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// Mock node related errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Block error.
+    #[error("block error {0}")]
+    Block(#[from] crate::types::block::Error),
+    /// Server error.
+    #[error("server error {0}")]
+    Hyper(#[from] hyper::Error),
+    /// Invalid request.
+    #[error("invalid request {0}")]
+    InvalidRequest(String),
+    /// Resource not found.
+    #[error("{0} not found")]
+    NotFound(String),
+    /// IO error.
+    #[error("io error {0}")]
+    Io(#[from] std::io::Error),
+}
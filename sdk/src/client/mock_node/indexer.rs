@@ -0,0 +1,269 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The indexer plugin of the mock node.
+
+use serde_json::Value;
+
+use super::{ledger::Ledger, Error};
+use crate::{
+    client::node_api::indexer::query_parameters::{
+        verify_query_parameters_alias_outputs, verify_query_parameters_basic_outputs,
+        verify_query_parameters_foundry_outputs, verify_query_parameters_nft_outputs, verify_query_parameters_outputs,
+        QueryParameter, QueryParameters,
+    },
+    types::{
+        api::plugins::indexer::OutputIdsResponse,
+        block::{
+            address::{Address, Bech32Address},
+            output::{AliasId, FoundryId, NftId, Output, OutputId},
+        },
+    },
+};
+
+/// The default amount of items returned in one page.
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// Handles `GET api/indexer/v1/outputs/*`, `route` being the path after `outputs`.
+pub(crate) fn output_ids(ledger: &Ledger, route: &[&str], query: Option<&str>) -> Result<OutputIdsResponse, Error> {
+    let query_parameters = parse_query(query)?;
+
+    match route {
+        [] => list(ledger, query_parameters, verify_query_parameters_outputs, |_| true),
+        ["basic"] => list(
+            ledger,
+            query_parameters,
+            verify_query_parameters_basic_outputs,
+            Output::is_basic,
+        ),
+        ["alias"] => list(
+            ledger,
+            query_parameters,
+            verify_query_parameters_alias_outputs,
+            Output::is_alias,
+        ),
+        ["foundry"] => list(
+            ledger,
+            query_parameters,
+            verify_query_parameters_foundry_outputs,
+            Output::is_foundry,
+        ),
+        ["nft"] => list(
+            ledger,
+            query_parameters,
+            verify_query_parameters_nft_outputs,
+            Output::is_nft,
+        ),
+        ["alias", id] => {
+            let alias_id = id.parse::<AliasId>().map_err(Error::Block)?;
+            single(ledger, |output_id, output| match output {
+                Output::Alias(alias) => alias.alias_id_non_null(output_id) == alias_id,
+                _ => false,
+            })
+        }
+        ["foundry", id] => {
+            let foundry_id = id.parse::<FoundryId>().map_err(Error::Block)?;
+            single(ledger, |_, output| match output {
+                Output::Foundry(foundry) => foundry.id() == foundry_id,
+                _ => false,
+            })
+        }
+        ["nft", id] => {
+            let nft_id = id.parse::<NftId>().map_err(Error::Block)?;
+            single(ledger, |output_id, output| match output {
+                Output::Nft(nft) => nft.nft_id_non_null(output_id) == nft_id,
+                _ => false,
+            })
+        }
+        _ => Err(Error::InvalidRequest(format!(
+            "unknown route outputs/{}",
+            route.join("/")
+        ))),
+    }
+}
+
+/// Parses a query string into [`QueryParameter`]s, relying on their serde representation.
+fn parse_query(query: Option<&str>) -> Result<Vec<QueryParameter>, Error> {
+    let Some(query) = query else {
+        return Ok(Vec::new());
+    };
+
+    url::form_urlencoded::parse(query.as_bytes())
+        .map(|(key, value)| {
+            let as_string = serde_json::json!({ key.as_ref(): value.as_ref() });
+            serde_json::from_value::<QueryParameter>(as_string).or_else(|_| {
+                let value = serde_json::from_str::<Value>(&value)
+                    .map_err(|_| Error::InvalidRequest(format!("invalid query parameter {key}={value}")))?;
+                serde_json::from_value::<QueryParameter>(serde_json::json!({ key.as_ref(): value }))
+                    .map_err(|_| Error::InvalidRequest(format!("invalid query parameter {key}")))
+            })
+        })
+        .collect()
+}
+
+fn list(
+    ledger: &Ledger,
+    query_parameters: Vec<QueryParameter>,
+    verify: fn(Vec<QueryParameter>) -> crate::client::Result<QueryParameters>,
+    kind_filter: impl Fn(&Output) -> bool,
+) -> Result<OutputIdsResponse, Error> {
+    verify(query_parameters.clone()).map_err(|e| Error::InvalidRequest(e.to_string()))?;
+    let timestamp = ledger
+        .latest_milestone()
+        .map_or(0, |ms| ms.payload.essence().timestamp());
+
+    let cursor = query_parameters.iter().find_map(|qp| match qp {
+        QueryParameter::Cursor(cursor) => Some(cursor),
+        _ => None,
+    });
+    let page_size = query_parameters.iter().find_map(|qp| match qp {
+        QueryParameter::PageSize(page_size) => Some(*page_size),
+        _ => None,
+    });
+    // The page size is ignored when it is defined via the cursor.
+    let (offset, page_size) = match cursor {
+        Some(cursor) => parse_cursor(cursor)?,
+        None => (0, page_size.unwrap_or(DEFAULT_PAGE_SIZE)),
+    };
+
+    let matching = ledger
+        .unspent_outputs()
+        .filter(|(_, output)| kind_filter(&output.output))
+        .filter(|(_, output)| {
+            query_parameters
+                .iter()
+                .all(|qp| matches_query_parameter(qp, &output.output, output.milestone_timestamp_booked, timestamp))
+        })
+        .map(|(output_id, _)| *output_id)
+        .collect::<Vec<_>>();
+
+    let items = matching.iter().skip(offset).take(page_size).copied().collect();
+    let cursor = (offset + page_size < matching.len()).then(|| format!("{}.{page_size}", offset + page_size));
+
+    Ok(OutputIdsResponse {
+        ledger_index: ledger.ledger_index(),
+        cursor,
+        items,
+    })
+}
+
+fn single(ledger: &Ledger, predicate: impl Fn(&OutputId, &Output) -> bool) -> Result<OutputIdsResponse, Error> {
+    let output_id = ledger
+        .unspent_outputs()
+        .find(|(output_id, output)| predicate(output_id, &output.output))
+        .map(|(output_id, _)| *output_id)
+        .ok_or_else(|| Error::NotFound("output".to_string()))?;
+
+    Ok(OutputIdsResponse {
+        ledger_index: ledger.ledger_index(),
+        cursor: None,
+        items: vec![output_id],
+    })
+}
+
+/// Cursors of the mock node have the form `offset.pageSize`.
+fn parse_cursor(cursor: &str) -> Result<(usize, usize), Error> {
+    cursor
+        .split_once('.')
+        .and_then(|(offset, page_size)| Some((offset.parse().ok()?, page_size.parse().ok()?)))
+        .ok_or_else(|| Error::InvalidRequest(format!("invalid cursor {cursor}")))
+}
+
+fn matches_query_parameter(query_parameter: &QueryParameter, output: &Output, created: u32, timestamp: u32) -> bool {
+    let unlock_conditions = output.unlock_conditions();
+    let is = |address: Option<&Address>, expected: &Bech32Address| address == Some(expected.inner());
+    let native_token_count = output.native_tokens().map_or(0, |native_tokens| native_tokens.len());
+
+    match query_parameter {
+        QueryParameter::Address(address) => is(
+            unlock_conditions.and_then(|uc| uc.address()).map(|uc| uc.address()),
+            address,
+        ),
+        QueryParameter::AliasAddress(address) => match output {
+            Output::Foundry(foundry) => &Address::from(*foundry.alias_address()) == address.inner(),
+            _ => false,
+        },
+        QueryParameter::CreatedAfter(after) => created > *after,
+        QueryParameter::CreatedBefore(before) => created < *before,
+        QueryParameter::Cursor(_) | QueryParameter::PageSize(_) => true,
+        QueryParameter::ExpirationReturnAddress(address) => is(
+            unlock_conditions
+                .and_then(|uc| uc.expiration())
+                .map(|uc| uc.return_address()),
+            address,
+        ),
+        QueryParameter::ExpiresAfter(after) => unlock_conditions
+            .and_then(|uc| uc.expiration())
+            .is_some_and(|uc| uc.timestamp() > *after),
+        QueryParameter::ExpiresBefore(before) => unlock_conditions
+            .and_then(|uc| uc.expiration())
+            .is_some_and(|uc| uc.timestamp() < *before),
+        QueryParameter::Governor(address) => is(
+            unlock_conditions
+                .and_then(|uc| uc.governor_address())
+                .map(|uc| uc.address()),
+            address,
+        ),
+        QueryParameter::HasExpiration(has) => unlock_conditions.is_some_and(|uc| uc.expiration().is_some()) == *has,
+        QueryParameter::HasNativeTokens(has) => (native_token_count > 0) == *has,
+        QueryParameter::HasStorageDepositReturn(has) => {
+            unlock_conditions.is_some_and(|uc| uc.storage_deposit_return().is_some()) == *has
+        }
+        QueryParameter::HasTimelock(has) => unlock_conditions.is_some_and(|uc| uc.timelock().is_some()) == *has,
+        QueryParameter::Issuer(address) => is(
+            output
+                .immutable_features()
+                .and_then(|features| features.issuer())
+                .map(|issuer| issuer.address()),
+            address,
+        ),
+        QueryParameter::MaxNativeTokenCount(max) => native_token_count <= *max as usize,
+        QueryParameter::MinNativeTokenCount(min) => native_token_count >= *min as usize,
+        QueryParameter::Sender(address) => is(
+            output
+                .features()
+                .and_then(|features| features.sender())
+                .map(|sender| sender.address()),
+            address,
+        ),
+        QueryParameter::StateController(address) => is(
+            unlock_conditions
+                .and_then(|uc| uc.state_controller_address())
+                .map(|uc| uc.address()),
+            address,
+        ),
+        QueryParameter::StorageDepositReturnAddress(address) => is(
+            unlock_conditions
+                .and_then(|uc| uc.storage_deposit_return())
+                .map(|uc| uc.return_address()),
+            address,
+        ),
+        QueryParameter::Tag(tag) => output
+            .features()
+            .and_then(|features| features.tag())
+            .is_some_and(|feature| prefix_hex::encode(feature.tag()).eq_ignore_ascii_case(tag)),
+        QueryParameter::TimelockedAfter(after) => unlock_conditions
+            .and_then(|uc| uc.timelock())
+            .is_some_and(|uc| uc.timestamp() > *after),
+        QueryParameter::TimelockedBefore(before) => unlock_conditions
+            .and_then(|uc| uc.timelock())
+            .is_some_and(|uc| uc.timestamp() < *before),
+        QueryParameter::UnlockableByAddress(address) => {
+            let Some(unlock_conditions) = unlock_conditions else {
+                return false;
+            };
+            if unlock_conditions.is_time_locked(timestamp) {
+                return false;
+            }
+            match output {
+                Output::Alias(alias) => {
+                    alias.state_controller_address() == address.inner() || alias.governor_address() == address.inner()
+                }
+                Output::Foundry(foundry) => &Address::from(*foundry.alias_address()) == address.inner(),
+                _ => unlock_conditions
+                    .address()
+                    .is_some_and(|uc| unlock_conditions.locked_address(uc.address(), timestamp) == address.inner()),
+            }
+        }
+    }
+}
@@ -0,0 +1,453 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The in-memory ledger of the mock node.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use crypto::{
+    hashes::{blake2b::Blake2b256, Digest},
+    signatures::ed25519,
+};
+
+use super::Error;
+use crate::{
+    types::{
+        api::core::response::{BlockMetadataResponse, LedgerInclusionState, UtxoChangesResponse},
+        block::{
+            address::Address,
+            input::Input,
+            output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, Output, OutputId, OutputMetadata},
+            parent::Parents,
+            payload::{
+                milestone::{
+                    InclusionProof, MerkleHasher, MerkleRoot, MilestoneEssence, MilestoneId, MilestoneIndex,
+                    MilestoneOptions,
+                },
+                transaction::{TransactionEssence, TransactionId},
+                MilestonePayload, Payload, TransactionPayload,
+            },
+            protocol::ProtocolParameters,
+            semantic::{validate_ledger_transaction, ConflictReason},
+            signature::{Ed25519Signature, Signature},
+            Block, BlockBuilder, BlockId,
+        },
+    },
+    utils::unix_timestamp_now,
+};
+
+/// An output stored in the ledger, together with the information needed to build its metadata.
+#[derive(Clone, Debug)]
+pub(crate) struct LedgerOutput {
+    pub(crate) output: Output,
+    pub(crate) block_id: BlockId,
+    pub(crate) milestone_index_booked: u32,
+    pub(crate) milestone_timestamp_booked: u32,
+    pub(crate) spent: Option<(u32, u32, TransactionId)>,
+}
+
+/// A milestone issued by the mock node.
+#[derive(Clone, Debug)]
+pub(crate) struct LedgerMilestone {
+    pub(crate) payload: MilestonePayload,
+    pub(crate) block_id: BlockId,
+    /// The blocks referenced by the milestone, in white flag order.
+    pub(crate) included_blocks: Vec<BlockId>,
+    pub(crate) created_outputs: Vec<OutputId>,
+    pub(crate) consumed_outputs: Vec<OutputId>,
+}
+
+/// In-memory UTXO ledger that applies blocks with white-flag semantics whenever a milestone is issued.
+pub(crate) struct Ledger {
+    pub(crate) protocol_parameters: ProtocolParameters,
+    pub(crate) faucet_amount: u64,
+    pub(crate) auto_confirm: bool,
+    milestone_key: ed25519::SecretKey,
+    outputs: HashMap<OutputId, LedgerOutput>,
+    output_order: Vec<OutputId>,
+    blocks: HashMap<BlockId, (Block, BlockMetadataResponse)>,
+    included_transactions: HashMap<TransactionId, BlockId>,
+    milestones: Vec<LedgerMilestone>,
+    pending_blocks: Vec<BlockId>,
+    pending_outputs: Vec<(OutputId, Output)>,
+    genesis_transactions: u32,
+}
+
+impl Ledger {
+    pub(crate) fn new(protocol_parameters: ProtocolParameters, faucet_amount: u64, auto_confirm: bool) -> Self {
+        Self {
+            protocol_parameters,
+            faucet_amount,
+            auto_confirm,
+            milestone_key: ed25519::SecretKey::generate().expect("failed to generate the milestone key"),
+            outputs: HashMap::new(),
+            output_order: Vec::new(),
+            blocks: HashMap::new(),
+            included_transactions: HashMap::new(),
+            milestones: Vec::new(),
+            pending_blocks: Vec::new(),
+            pending_outputs: Vec::new(),
+            genesis_transactions: 0,
+        }
+    }
+
+    /// Returns the public key used to sign milestones, hex encoded without prefix.
+    pub(crate) fn milestone_public_key(&self) -> String {
+        hex::encode(self.milestone_key.public_key().to_bytes())
+    }
+
+    /// Returns the index of the latest milestone, which is also the ledger index.
+    pub(crate) fn ledger_index(&self) -> u32 {
+        self.milestones.len() as u32
+    }
+
+    pub(crate) fn latest_milestone(&self) -> Option<&LedgerMilestone> {
+        self.milestones.last()
+    }
+
+    pub(crate) fn milestone_by_index(&self, index: u32) -> Option<&LedgerMilestone> {
+        index
+            .checked_sub(1)
+            .and_then(|position| self.milestones.get(position as usize))
+    }
+
+    pub(crate) fn milestone_by_id(&self, milestone_id: &MilestoneId) -> Option<&LedgerMilestone> {
+        self.milestones.iter().find(|ms| &ms.payload.id() == milestone_id)
+    }
+
+    pub(crate) fn utxo_changes(milestone: &LedgerMilestone) -> UtxoChangesResponse {
+        UtxoChangesResponse {
+            index: *milestone.payload.essence().index(),
+            created_outputs: milestone.created_outputs.clone(),
+            consumed_outputs: milestone.consumed_outputs.clone(),
+        }
+    }
+
+    /// Returns the current tips: the pending blocks, or the latest milestone block if there are none.
+    pub(crate) fn tips(&self) -> Vec<BlockId> {
+        let mut tips = self
+            .pending_blocks
+            .iter()
+            .rev()
+            .take(*Parents::COUNT_RANGE.end() as usize)
+            .copied()
+            .collect::<Vec<_>>();
+
+        if tips.is_empty() {
+            tips.push(self.latest_milestone().map_or_else(BlockId::null, |ms| ms.block_id));
+        }
+
+        tips
+    }
+
+    pub(crate) fn block(&self, block_id: &BlockId) -> Option<&(Block, BlockMetadataResponse)> {
+        self.blocks.get(block_id)
+    }
+
+    /// Creates the proof that a block was included in the milestone referencing it.
+    pub(crate) fn inclusion_proof(&self, block_id: &BlockId) -> Result<InclusionProof, Error> {
+        let (block, metadata) = self
+            .block(block_id)
+            .ok_or_else(|| Error::NotFound("block".to_string()))?;
+        let milestone = metadata
+            .referenced_by_milestone_index
+            .and_then(|index| self.milestone_by_index(index))
+            .ok_or_else(|| Error::InvalidRequest(format!("block {block_id} isn't referenced by a milestone")))?;
+
+        Ok(InclusionProof::new(
+            milestone.payload.clone(),
+            block.clone(),
+            &milestone.included_blocks,
+        )?)
+    }
+
+    pub(crate) fn included_block(&self, transaction_id: &TransactionId) -> Option<&(Block, BlockMetadataResponse)> {
+        self.included_transactions
+            .get(transaction_id)
+            .and_then(|block_id| self.blocks.get(block_id))
+    }
+
+    pub(crate) fn output(&self, output_id: &OutputId) -> Option<&LedgerOutput> {
+        self.outputs.get(output_id)
+    }
+
+    pub(crate) fn output_metadata(&self, output_id: &OutputId, output: &LedgerOutput) -> OutputMetadata {
+        OutputMetadata::new(
+            output.block_id,
+            *output_id,
+            output.spent.is_some(),
+            output.spent.map(|(index, _, _)| index),
+            output.spent.map(|(_, timestamp, _)| timestamp),
+            output.spent.map(|(_, _, transaction_id)| transaction_id),
+            output.milestone_index_booked,
+            output.milestone_timestamp_booked,
+            self.ledger_index(),
+        )
+    }
+
+    /// Returns all unspent outputs, in the order they were booked.
+    pub(crate) fn unspent_outputs(&self) -> impl Iterator<Item = (&OutputId, &LedgerOutput)> + '_ {
+        self.output_order.iter().filter_map(|output_id| {
+            self.outputs
+                .get(output_id)
+                .filter(|output| output.spent.is_none())
+                .map(|output| (output_id, output))
+        })
+    }
+
+    /// Queues an output that is created out of thin air; it gets booked with the next milestone.
+    pub(crate) fn add_output(&mut self, output: Output) -> OutputId {
+        self.genesis_transactions += 1;
+        let transaction_id = TransactionId::new(
+            Blake2b256::digest([b"mock_node".as_slice(), &self.genesis_transactions.to_le_bytes()].concat()).into(),
+        );
+        // PANIC: 0 is a valid output index.
+        let output_id = OutputId::new(transaction_id, 0).unwrap();
+
+        self.pending_outputs.push((output_id, output));
+
+        if self.auto_confirm {
+            self.issue_milestone();
+        }
+
+        output_id
+    }
+
+    /// Queues a basic output holding `amount` base tokens for `address`.
+    pub(crate) fn fund(&mut self, address: Address, amount: u64) -> Result<OutputId, Error> {
+        let output = BasicOutputBuilder::new_with_amount(amount)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_output(self.protocol_parameters.token_supply())?;
+
+        Ok(self.add_output(output))
+    }
+
+    /// Stores a submitted block; it gets referenced by the next milestone.
+    pub(crate) fn submit_block(&mut self, block: Block) -> Result<BlockId, Error> {
+        if let Some(Payload::Transaction(transaction)) = block.payload() {
+            let TransactionEssence::Regular(essence) = transaction.essence();
+            if essence.network_id() != self.protocol_parameters.network_id() {
+                return Err(Error::InvalidRequest(format!(
+                    "invalid network id {}, expected {}",
+                    essence.network_id(),
+                    self.protocol_parameters.network_id()
+                )));
+            }
+        }
+
+        let block_id = block.id();
+
+        if let Entry::Vacant(entry) = self.blocks.entry(block_id) {
+            let metadata = BlockMetadataResponse {
+                block_id,
+                parents: block.parents().to_vec(),
+                is_solid: true,
+                referenced_by_milestone_index: None,
+                milestone_index: None,
+                ledger_inclusion_state: None,
+                conflict_reason: None,
+                white_flag_index: None,
+                should_promote: Some(false),
+                should_reattach: Some(false),
+            };
+            entry.insert((block, metadata));
+            self.pending_blocks.push(block_id);
+
+            if self.auto_confirm {
+                self.issue_milestone();
+            }
+        }
+
+        Ok(block_id)
+    }
+
+    /// Drops a block that isn't referenced by a milestone yet. Returns false if there is no such block.
+    pub(crate) fn orphan_block(&mut self, block_id: &BlockId) -> bool {
+        let Some(position) = self.pending_blocks.iter().position(|pending| pending == block_id) else {
+            return false;
+        };
+
+        self.pending_blocks.remove(position);
+        self.blocks.remove(block_id);
+        true
+    }
+
+    /// Issues a milestone referencing all pending blocks and outputs and applies them to the ledger.
+    pub(crate) fn issue_milestone(&mut self) -> u32 {
+        let index = self.ledger_index() + 1;
+        let timestamp = (unix_timestamp_now().as_secs() as u32)
+            .max(self.latest_milestone().map_or(0, |ms| ms.payload.essence().timestamp()));
+        let parents = self.tips();
+        let mut created_outputs = Vec::new();
+        let mut consumed_outputs = Vec::new();
+        let mut included_blocks = Vec::new();
+        let mut applied_blocks = Vec::new();
+
+        for (output_id, output) in core::mem::take(&mut self.pending_outputs) {
+            self.book_output(output_id, output, BlockId::null(), index, timestamp);
+            created_outputs.push(output_id);
+        }
+
+        for (white_flag_index, block_id) in core::mem::take(&mut self.pending_blocks).into_iter().enumerate() {
+            let block = self.blocks[&block_id].0.clone();
+            let ledger_inclusion_state = match block.payload() {
+                Some(Payload::Transaction(transaction)) => {
+                    let conflict_reason = self.apply_transaction(
+                        block_id,
+                        transaction,
+                        index,
+                        timestamp,
+                        &mut created_outputs,
+                        &mut consumed_outputs,
+                    );
+                    let metadata = &mut self.blocks.get_mut(&block_id).expect("block exists").1;
+                    if conflict_reason == ConflictReason::None {
+                        applied_blocks.push(block_id);
+                        LedgerInclusionState::Included
+                    } else {
+                        metadata.conflict_reason = Some(conflict_reason as u8);
+                        LedgerInclusionState::Conflicting
+                    }
+                }
+                _ => LedgerInclusionState::NoTransaction,
+            };
+            let metadata = &mut self.blocks.get_mut(&block_id).expect("block exists").1;
+            metadata.referenced_by_milestone_index = Some(index);
+            metadata.ledger_inclusion_state = Some(ledger_inclusion_state);
+            metadata.white_flag_index = Some(white_flag_index as u32);
+            included_blocks.push(block_id);
+        }
+
+        let previous_milestone_id = self
+            .latest_milestone()
+            .map_or_else(MilestoneId::null, |ms| ms.payload.id());
+        let (payload, block) = self.sign_milestone(
+            index,
+            timestamp,
+            previous_milestone_id,
+            parents,
+            MerkleHasher::digest(&included_blocks),
+            MerkleHasher::digest(&applied_blocks),
+        );
+        let block_id = block.id();
+        let metadata = BlockMetadataResponse {
+            block_id,
+            parents: block.parents().to_vec(),
+            is_solid: true,
+            referenced_by_milestone_index: Some(index),
+            milestone_index: Some(index),
+            ledger_inclusion_state: Some(LedgerInclusionState::NoTransaction),
+            conflict_reason: None,
+            white_flag_index: None,
+            should_promote: None,
+            should_reattach: None,
+        };
+        self.blocks.insert(block_id, (block, metadata));
+        self.milestones.push(LedgerMilestone {
+            payload,
+            block_id,
+            included_blocks,
+            created_outputs,
+            consumed_outputs,
+        });
+
+        index
+    }
+
+    fn sign_milestone(
+        &self,
+        index: u32,
+        timestamp: u32,
+        previous_milestone_id: MilestoneId,
+        parents: Vec<BlockId>,
+        inclusion_merkle_root: MerkleRoot,
+        applied_merkle_root: MerkleRoot,
+    ) -> (MilestonePayload, Block) {
+        // PANIC: the tips are never empty and at most `Parents::COUNT_RANGE.end()` long.
+        let parents = Parents::from_vec(parents).unwrap();
+        // PANIC: all the values are known to be valid.
+        let essence = MilestoneEssence::new(
+            MilestoneIndex::new(index),
+            timestamp,
+            self.protocol_parameters.protocol_version(),
+            previous_milestone_id,
+            parents.clone(),
+            inclusion_merkle_root,
+            applied_merkle_root,
+            Vec::new(),
+            MilestoneOptions::from_vec(Vec::new()).unwrap(),
+        )
+        .unwrap();
+        let signature = Ed25519Signature::new(
+            self.milestone_key.public_key(),
+            self.milestone_key.sign(&essence.hash()),
+        );
+        let payload = MilestonePayload::new(essence, [Signature::from(signature)]).unwrap();
+        let block = BlockBuilder::new(parents)
+            .with_protocol_version(self.protocol_parameters.protocol_version())
+            .with_payload(payload.clone())
+            .finish()
+            .unwrap();
+
+        (payload, block)
+    }
+
+    fn book_output(&mut self, output_id: OutputId, output: Output, block_id: BlockId, index: u32, timestamp: u32) {
+        self.outputs.insert(
+            output_id,
+            LedgerOutput {
+                output,
+                block_id,
+                milestone_index_booked: index,
+                milestone_timestamp_booked: timestamp,
+                spent: None,
+            },
+        );
+        self.output_order.push(output_id);
+    }
+
+    fn apply_transaction(
+        &mut self,
+        block_id: BlockId,
+        transaction: &TransactionPayload,
+        index: u32,
+        timestamp: u32,
+        created_outputs: &mut Vec<OutputId>,
+        consumed_outputs: &mut Vec<OutputId>,
+    ) -> ConflictReason {
+        let transaction_id = transaction.id();
+        let TransactionEssence::Regular(essence) = transaction.essence();
+
+        let conflict_reason = match validate_ledger_transaction(transaction, index, timestamp, |output_id| {
+            self.outputs
+                .get(output_id)
+                .map(|output| (&output.output, output.spent.map(|(spent_index, _, _)| spent_index)))
+        }) {
+            Ok(conflict_reason) => conflict_reason,
+            Err(err) => {
+                log::debug!("[MOCK NODE] semantic validation of {transaction_id} failed: {err}");
+                ConflictReason::SemanticValidationFailed
+            }
+        };
+
+        if conflict_reason != ConflictReason::None {
+            return conflict_reason;
+        }
+
+        for input in essence.inputs() {
+            let Input::Utxo(input) = input else { continue };
+            // PANIC: the input was found above.
+            self.outputs.get_mut(input.output_id()).unwrap().spent = Some((index, timestamp, transaction_id));
+            consumed_outputs.push(*input.output_id());
+        }
+        for (output_index, output) in essence.outputs().iter().enumerate() {
+            // PANIC: the number of outputs is bounded by the transaction essence.
+            let output_id = OutputId::new(transaction_id, output_index as u16).unwrap();
+            self.book_output(output_id, output.clone(), block_id, index, timestamp);
+            created_outputs.push(output_id);
+        }
+        self.included_transactions.insert(transaction_id, block_id);
+
+        ConflictReason::None
+    }
+}
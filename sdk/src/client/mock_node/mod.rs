@@ -0,0 +1,184 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process node serving the core and indexer REST APIs from an in-memory ledger.
+//!
+//! Submitted blocks are validated semantically and applied to the ledger when a milestone is issued, which by default
+//! happens right away. This allows [`Client`](crate::client::Client) and the wallet to be exercised end-to-end without
+//! network access.
+//!
+//! ```no_run
+//! # use iota_sdk::client::{mock_node::MockNode, Client, Result};
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let node = MockNode::builder().finish().await?;
+//! let client = Client::builder().with_node(node.url())?.finish().await?;
+//!
+//! println!("{}", client.get_info().await?.node_info);
+//! # Ok(())
+//! # }
+//! ```
+
+mod error;
+mod indexer;
+mod ledger;
+mod routes;
+
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Server,
+};
+use tokio::sync::{oneshot, Mutex};
+
+pub use self::error::Error;
+use self::ledger::Ledger;
+use crate::types::block::{
+    address::Address,
+    output::{Output, OutputId},
+    protocol::ProtocolParameters,
+    BlockId,
+};
+
+/// The amount of base tokens sent by the faucet of a mock node by default.
+pub const DEFAULT_FAUCET_AMOUNT: u64 = 1_000_000_000;
+
+/// Builder of a [`MockNode`].
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct MockNodeBuilder {
+    protocol_parameters: ProtocolParameters,
+    faucet_amount: u64,
+    auto_confirm: bool,
+}
+
+impl Default for MockNodeBuilder {
+    fn default() -> Self {
+        Self {
+            protocol_parameters: ProtocolParameters::default(),
+            faucet_amount: DEFAULT_FAUCET_AMOUNT,
+            auto_confirm: true,
+        }
+    }
+}
+
+impl MockNodeBuilder {
+    /// Sets the protocol parameters of the node.
+    pub fn with_protocol_parameters(mut self, protocol_parameters: ProtocolParameters) -> Self {
+        self.protocol_parameters = protocol_parameters;
+        self
+    }
+
+    /// Sets the amount of base tokens the faucet sends per request.
+    pub fn with_faucet_amount(mut self, faucet_amount: u64) -> Self {
+        self.faucet_amount = faucet_amount;
+        self
+    }
+
+    /// Sets whether a milestone is issued as soon as a block is submitted or an output is added. If disabled,
+    /// milestones have to be issued with [`MockNode::issue_milestone()`].
+    pub fn with_auto_confirm(mut self, auto_confirm: bool) -> Self {
+        self.auto_confirm = auto_confirm;
+        self
+    }
+
+    /// Binds the node to a free local port and starts serving requests.
+    pub async fn finish(self) -> Result<MockNode, Error> {
+        let mut ledger = Ledger::new(self.protocol_parameters, self.faucet_amount, self.auto_confirm);
+        // Genesis milestone, so that the node reports a recent milestone timestamp.
+        ledger.issue_milestone();
+        let ledger = Arc::new(Mutex::new(ledger));
+
+        let listener = std::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+
+        let ledger_ = ledger.clone();
+        let server = Server::from_tcp(listener)?.serve(make_service_fn(move |_| {
+            let ledger = ledger_.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| routes::handle(ledger.clone(), request))) }
+        }));
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            shutdown_receiver.await.ok();
+        }));
+
+        log::debug!("[MOCK NODE] listening on {address}");
+
+        Ok(MockNode {
+            url: format!("http://{address}"),
+            ledger,
+            shutdown: Some(shutdown_sender),
+        })
+    }
+}
+
+/// A node running in the current process, stopped when dropped.
+pub struct MockNode {
+    url: String,
+    ledger: Arc<Mutex<Ledger>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockNode {
+    /// Creates a new [`MockNodeBuilder`].
+    pub fn builder() -> MockNodeBuilder {
+        MockNodeBuilder::default()
+    }
+
+    /// Returns the url of the node.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the url of the faucet of the node.
+    pub fn faucet_url(&self) -> String {
+        format!("{}/api/enqueue", self.url)
+    }
+
+    /// Returns the protocol parameters of the node.
+    pub async fn protocol_parameters(&self) -> ProtocolParameters {
+        self.ledger.lock().await.protocol_parameters.clone()
+    }
+
+    /// Returns the public key the milestones are signed with, hex encoded without prefix.
+    pub async fn milestone_public_key(&self) -> String {
+        self.ledger.lock().await.milestone_public_key()
+    }
+
+    /// Adds a basic output holding `amount` base tokens for `address` to the ledger.
+    pub async fn fund(&self, address: impl Into<Address> + Send, amount: u64) -> Result<OutputId, Error> {
+        self.ledger.lock().await.fund(address.into(), amount)
+    }
+
+    /// Adds an arbitrary output to the ledger.
+    pub async fn add_output(&self, output: Output) -> OutputId {
+        self.ledger.lock().await.add_output(output)
+    }
+
+    /// Drops a block that isn't referenced by a milestone yet, as if it got orphaned, so its transaction doesn't get
+    /// confirmed. Returns false if there is no such block.
+    pub async fn orphan_block(&self, block_id: &BlockId) -> bool {
+        self.ledger.lock().await.orphan_block(block_id)
+    }
+
+    /// Issues a milestone that applies all pending blocks and outputs to the ledger and returns its index.
+    pub async fn issue_milestone(&self) -> u32 {
+        self.ledger.lock().await.issue_milestone()
+    }
+}
+
+impl core::fmt::Debug for MockNode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MockNode").field("url", &self.url).finish()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The REST API of the mock node.
+
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+use hyper::{body::Bytes, header, Body, Method, Request, Response, StatusCode};
+use packable::PackableExt;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use super::{indexer, ledger::Ledger, Error};
+use crate::types::{
+    api::core::response::{
+        BaseTokenResponse, ConfirmedMilestoneResponse, InfoResponse, LatestMilestoneResponse, MetricsResponse,
+        OutputWithMetadataResponse, ReceiptsResponse, RoutesResponse, StatusResponse, SubmitBlockResponse,
+        TipsResponse,
+    },
+    block::{
+        address::Bech32Address,
+        output::{dto::OutputDto, OutputId},
+        payload::{
+            milestone::{
+                dto::{InclusionProofDto, MilestonePayloadDto},
+                MilestoneId,
+            },
+            transaction::TransactionId,
+        },
+        Block, BlockDto, BlockId,
+    },
+    TryFromDto,
+};
+
+/// Content type of packed objects.
+const SERIALIZER_V1: &str = "application/vnd.iota.serializer-v1";
+
+/// The body of a successful request, either JSON or packed bytes.
+enum Reply {
+    Json(StatusCode, serde_json::Value),
+    Bytes(Vec<u8>),
+    Empty,
+}
+
+impl Reply {
+    fn json(value: impl Serialize) -> Self {
+        Self::Json(
+            StatusCode::OK,
+            serde_json::to_value(value).expect("responses are serializable"),
+        )
+    }
+}
+
+pub(crate) async fn handle(ledger: Arc<Mutex<Ledger>>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let method = request.method().clone();
+    let path = request.uri().path().trim_matches('/').to_string();
+    let query = request.uri().query().map(ToOwned::to_owned);
+    let wants_bytes = header_contains(&request, header::ACCEPT, SERIALIZER_V1);
+    let sends_bytes = header_contains(&request, header::CONTENT_TYPE, SERIALIZER_V1);
+    let body = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => body,
+        Err(e) => return Ok(error_response(StatusCode::BAD_REQUEST, &e.to_string())),
+    };
+
+    let segments = path.split('/').collect::<Vec<_>>();
+    let mut ledger = ledger.lock().await;
+    let reply = route(
+        &mut ledger,
+        &method,
+        &segments,
+        query.as_deref(),
+        wants_bytes,
+        sends_bytes,
+        body,
+    );
+    log::debug!("[MOCK NODE] {method} /{path}");
+
+    Ok(match reply {
+        Ok(Reply::Json(status, value)) => Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(value.to_string()))
+            .expect("valid response"),
+        Ok(Reply::Bytes(bytes)) => Response::builder()
+            .header(header::CONTENT_TYPE, SERIALIZER_V1)
+            .body(Body::from(bytes))
+            .expect("valid response"),
+        Ok(Reply::Empty) => Response::new(Body::empty()),
+        Err(Error::NotFound(message)) => error_response(StatusCode::NOT_FOUND, &format!("{message} not found")),
+        Err(e @ (Error::InvalidRequest(_) | Error::Block(_))) => {
+            error_response(StatusCode::BAD_REQUEST, &e.to_string())
+        }
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    })
+}
+
+fn header_contains(request: &Request<Body>, name: header::HeaderName, value: &str) -> bool {
+    request
+        .headers()
+        .get(name)
+        .and_then(|header| header.to_str().ok())
+        .is_some_and(|header| header.contains(value))
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    let body = serde_json::json!({
+        "error": {
+            "code": status.as_u16().to_string(),
+            "message": message,
+        }
+    });
+
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("valid response")
+}
+
+fn parse<T: core::str::FromStr>(value: &str) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidRequest(format!("invalid path parameter {value}")))
+}
+
+fn route(
+    ledger: &mut Ledger,
+    method: &Method,
+    segments: &[&str],
+    query: Option<&str>,
+    wants_bytes: bool,
+    sends_bytes: bool,
+    body: Bytes,
+) -> Result<Reply, Error> {
+    match (method, segments) {
+        (&Method::GET, ["health"]) => Ok(Reply::Empty),
+        (&Method::GET, ["api", "routes"]) => Ok(Reply::json(RoutesResponse {
+            routes: vec!["core/v2".to_string(), "indexer/v1".to_string(), "poi/v1".to_string()],
+        })),
+        (&Method::GET, ["api", "core", "v2", rest @ ..]) => core_get(ledger, rest, wants_bytes),
+        (&Method::POST, ["api", "core", "v2", "blocks"]) => {
+            let block = if sends_bytes {
+                Block::unpack_verified(body.as_ref(), &ledger.protocol_parameters)
+                    .map_err(|e| Error::InvalidRequest(e.to_string()))?
+            } else {
+                let dto =
+                    serde_json::from_slice::<BlockDto>(&body).map_err(|e| Error::InvalidRequest(e.to_string()))?;
+                Block::try_from_dto_with_params(dto, &ledger.protocol_parameters)?
+            };
+            let block_id = ledger.submit_block(block)?;
+
+            Ok(Reply::Json(
+                StatusCode::CREATED,
+                serde_json::to_value(SubmitBlockResponse { block_id }).expect("responses are serializable"),
+            ))
+        }
+        (&Method::GET, ["api", "indexer", "v1", "outputs", rest @ ..]) => {
+            Ok(Reply::json(indexer::output_ids(ledger, rest, query)?))
+        }
+        (&Method::GET, ["api", "poi", "v1", "create", block_id]) => {
+            let proof = ledger.inclusion_proof(&parse::<BlockId>(block_id)?)?;
+            Ok(Reply::json(InclusionProofDto::from(&proof)))
+        }
+        (&Method::POST, ["api", "enqueue"]) => {
+            let request = serde_json::from_slice::<HashMap<String, String>>(&body)
+                .map_err(|e| Error::InvalidRequest(e.to_string()))?;
+            let bech32_address = request
+                .get("address")
+                .ok_or_else(|| Error::InvalidRequest("missing address".to_string()))?;
+            let address = Bech32Address::try_from_str(bech32_address)?;
+            let amount = ledger.faucet_amount;
+            ledger.fund(address.into_inner(), amount)?;
+
+            Ok(Reply::Json(
+                StatusCode::ACCEPTED,
+                serde_json::json!({ "address": bech32_address, "waitingRequests": 0 }),
+            ))
+        }
+        _ => Err(Error::NotFound(format!("route {method} /{}", segments.join("/")))),
+    }
+}
+
+fn core_get(ledger: &Ledger, segments: &[&str], wants_bytes: bool) -> Result<Reply, Error> {
+    let block_reply = |block: &Block| {
+        if wants_bytes {
+            Reply::Bytes(block.pack_to_vec())
+        } else {
+            Reply::json(BlockDto::from(block))
+        }
+    };
+
+    match segments {
+        ["info"] => Ok(Reply::json(info(ledger))),
+        ["tips"] => Ok(Reply::json(TipsResponse { tips: ledger.tips() })),
+        ["blocks", block_id] => {
+            let (block, _) = ledger
+                .block(&parse::<BlockId>(block_id)?)
+                .ok_or_else(|| Error::NotFound("block".to_string()))?;
+            Ok(block_reply(block))
+        }
+        ["blocks", block_id, "metadata"] => {
+            let (_, metadata) = ledger
+                .block(&parse::<BlockId>(block_id)?)
+                .ok_or_else(|| Error::NotFound("block".to_string()))?;
+            Ok(Reply::json(metadata))
+        }
+        ["outputs", output_id, rest @ ..] => {
+            let output_id = parse::<OutputId>(output_id)?;
+            let output = ledger
+                .output(&output_id)
+                .ok_or_else(|| Error::NotFound("output".to_string()))?;
+            let metadata = ledger.output_metadata(&output_id, output);
+            match rest {
+                [] if wants_bytes => Ok(Reply::Bytes(output.output.pack_to_vec())),
+                [] => Ok(Reply::json(OutputWithMetadataResponse {
+                    metadata,
+                    output: OutputDto::from(&output.output),
+                })),
+                ["metadata"] => Ok(Reply::json(metadata)),
+                _ => Err(Error::NotFound("route".to_string())),
+            }
+        }
+        ["transactions", transaction_id, "included-block", rest @ ..] => {
+            let (block, metadata) = ledger
+                .included_block(&parse::<TransactionId>(transaction_id)?)
+                .ok_or_else(|| Error::NotFound("transaction".to_string()))?;
+            match rest {
+                [] => Ok(block_reply(block)),
+                ["metadata"] => Ok(Reply::json(metadata)),
+                _ => Err(Error::NotFound("route".to_string())),
+            }
+        }
+        ["milestones", "by-index", index, rest @ ..] => {
+            let milestone = ledger
+                .milestone_by_index(parse(index)?)
+                .ok_or_else(|| Error::NotFound("milestone".to_string()))?;
+            milestone_reply(milestone, rest, wants_bytes)
+        }
+        ["milestones", milestone_id, rest @ ..] => {
+            let milestone = ledger
+                .milestone_by_id(&parse::<MilestoneId>(milestone_id)?)
+                .ok_or_else(|| Error::NotFound("milestone".to_string()))?;
+            milestone_reply(milestone, rest, wants_bytes)
+        }
+        ["receipts", ..] => Ok(Reply::json(ReceiptsResponse { receipts: Vec::new() })),
+        ["peers"] => Ok(Reply::json(Vec::<()>::new())),
+        _ => Err(Error::NotFound(format!("route /api/core/v2/{}", segments.join("/")))),
+    }
+}
+
+fn milestone_reply(
+    milestone: &super::ledger::LedgerMilestone,
+    segments: &[&str],
+    wants_bytes: bool,
+) -> Result<Reply, Error> {
+    match segments {
+        [] if wants_bytes => Ok(Reply::Bytes(milestone.payload.pack_to_vec())),
+        [] => Ok(Reply::json(MilestonePayloadDto::from(&milestone.payload))),
+        ["utxo-changes"] => Ok(Reply::json(Ledger::utxo_changes(milestone))),
+        _ => Err(Error::NotFound("route".to_string())),
+    }
+}
+
+fn info(ledger: &Ledger) -> InfoResponse {
+    let (index, timestamp, milestone_id) = ledger.latest_milestone().map_or((0, None, None), |ms| {
+        (
+            *ms.payload.essence().index(),
+            Some(ms.payload.essence().timestamp()),
+            Some(ms.payload.id()),
+        )
+    });
+
+    InfoResponse {
+        name: "MockNode".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        status: StatusResponse {
+            is_healthy: true,
+            latest_milestone: LatestMilestoneResponse {
+                index,
+                timestamp,
+                milestone_id,
+            },
+            confirmed_milestone: ConfirmedMilestoneResponse {
+                index,
+                timestamp,
+                milestone_id,
+            },
+            pruning_index: 0,
+        },
+        supported_protocol_versions: vec![ledger.protocol_parameters.protocol_version()],
+        protocol: ledger.protocol_parameters.clone(),
+        pending_protocol_parameters: Vec::new(),
+        base_token: BaseTokenResponse {
+            name: "Mock".to_string(),
+            ticker_symbol: "MOCK".to_string(),
+            unit: "MOCK".to_string(),
+            subunit: Some("glow".to_string()),
+            decimals: 6,
+            use_metric_prefix: false,
+        },
+        metrics: MetricsResponse {
+            blocks_per_second: 0.0,
+            referenced_blocks_per_second: 0.0,
+            referenced_rate: 0.0,
+        },
+        // Blocks are accepted regardless of their nonce, which to clients looks like remote PoW.
+        features: vec!["pow".to_string()],
+    }
+}
@@ -37,14 +37,20 @@ macro_rules! lazy_static {
 
 pub mod api;
 pub mod builder;
+pub mod cache;
 pub mod constants;
 pub mod core;
 pub mod error;
+pub mod light_client;
+#[cfg(feature = "mock_node")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock_node")))]
+pub mod mock_node;
 pub mod node_api;
 pub mod node_manager;
 #[cfg(not(target_family = "wasm"))]
 pub(crate) mod request_pool;
 pub mod secret;
+pub mod slip39;
 pub mod storage;
 #[cfg(feature = "stronghold")]
 #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
@@ -10,7 +10,11 @@ use url::Url;
 use crate::{
     client::{
         constants::{DEFAULT_API_TIMEOUT, DEFAULT_USER_AGENT},
-        node_manager::node::{Node, NodeAuth},
+        light_client::MilestoneVerificationError,
+        node_manager::{
+            http_client::HttpClient,
+            node::{Node, NodeAuth},
+        },
         Client, ClientInner, Error, Result,
     },
     types::{
@@ -51,17 +55,19 @@ impl ClientInner {
     pub async fn get_health(&self, url: &str) -> Result<bool> {
         let path = "health";
 
-        let mut url = Url::parse(url)?;
+        let node = Node {
+            url: Url::parse(url)?,
+            auth: None,
+            disabled: false,
+        };
+        let mut url = node.url.clone();
         url.set_path(path);
-        let status = crate::client::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
-            .get(
-                Node {
-                    url,
-                    auth: None,
-                    disabled: false,
-                },
-                DEFAULT_API_TIMEOUT,
-            )
+        let status = self
+            .node_manager
+            .read()
+            .await
+            .http_client
+            .get(&node, url, DEFAULT_API_TIMEOUT)
             .await?
             .status();
 
@@ -82,7 +88,11 @@ impl ClientInner {
     /// Returns general information about the node.
     /// GET /api/core/v2/info
     pub async fn get_info(&self) -> Result<NodeInfoWrapper> {
-        self.get_request(INFO_PATH, None, false, false).await
+        let info: NodeInfoWrapper = self.get_request(INFO_PATH, None, false, false).await?;
+        self.update_ledger_index(info.node_info.status.confirmed_milestone.index)
+            .await;
+
+        Ok(info)
     }
 
     // Tangle routes.
@@ -116,7 +126,7 @@ impl ClientInner {
             .node_manager
             .read()
             .await
-            .post_request_json::<SubmitBlockResponse>(path, timeout, serde_json::to_value(block_dto)?, local_pow)
+            .post_request_json::<SubmitBlockResponse>(path, timeout, serde_json::to_value(block_dto)?, local_pow, true)
             .await
         {
             Ok(res) => res,
@@ -146,7 +156,7 @@ impl ClientInner {
                 self.node_manager
                     .read()
                     .await
-                    .post_request_json(path, timeout, serde_json::to_value(block_dto)?, true)
+                    .post_request_json(path, timeout, serde_json::to_value(block_dto)?, true, true)
                     .await?
             }
             Err(e) => return Err(e),
@@ -212,7 +222,7 @@ impl ClientInner {
     pub async fn get_block(&self, block_id: &BlockId) -> Result<Block> {
         let path = &format!("api/core/v2/blocks/{block_id}");
 
-        let dto = self.get_request::<BlockDto>(path, None, false, true).await?;
+        let dto = self.get_request_cached::<BlockDto>(path, false, true, |_| true).await?;
 
         Ok(Block::try_from_dto_with_params(
             dto,
@@ -233,7 +243,10 @@ impl ClientInner {
     pub async fn get_block_metadata(&self, block_id: &BlockId) -> Result<BlockMetadataResponse> {
         let path = &format!("api/core/v2/blocks/{block_id}/metadata");
 
-        self.get_request(path, None, true, true).await
+        let metadata: BlockMetadataResponse = self.get_request(path, None, true, true).await?;
+        self.verify_block_confirmation(&metadata).await?;
+
+        Ok(metadata)
     }
 
     // UTXO routes.
@@ -243,7 +256,15 @@ impl ClientInner {
     pub async fn get_output(&self, output_id: &OutputId) -> Result<OutputWithMetadata> {
         let path = &format!("api/core/v2/outputs/{output_id}");
 
-        let response: OutputWithMetadataResponse = self.get_request(path, None, false, true).await?;
+        // Only spent outputs can't change anymore.
+        let mut response: OutputWithMetadataResponse = self
+            .get_request_cached(path, false, true, |response: &OutputWithMetadataResponse| {
+                response.metadata.is_spent()
+            })
+            .await?;
+        self.refresh_ledger_index(&mut response.metadata).await;
+
+        self.verify_output_confirmation(&response.metadata).await?;
 
         let token_supply = self.get_token_supply().await?;
         let output = Output::try_from_dto_with_params(response.output, token_supply)?;
@@ -264,7 +285,11 @@ impl ClientInner {
     pub async fn get_output_metadata(&self, output_id: &OutputId) -> Result<OutputMetadata> {
         let path = &format!("api/core/v2/outputs/{output_id}/metadata");
 
-        self.get_request::<OutputMetadata>(path, None, false, true).await
+        let metadata = self.get_request::<OutputMetadata>(path, None, false, true).await?;
+        self.verify_output_confirmation(&metadata).await?;
+        self.update_ledger_index(metadata.ledger_index()).await;
+
+        Ok(metadata)
     }
 
     /// Gets all stored receipts.
@@ -301,7 +326,7 @@ impl ClientInner {
     pub async fn get_included_block(&self, transaction_id: &TransactionId) -> Result<Block> {
         let path = &format!("api/core/v2/transactions/{transaction_id}/included-block");
 
-        let dto = self.get_request::<BlockDto>(path, None, true, true).await?;
+        let dto = self.get_request_cached::<BlockDto>(path, true, true, |_| true).await?;
 
         Ok(Block::try_from_dto_with_params(
             dto,
@@ -322,7 +347,10 @@ impl ClientInner {
     pub async fn get_included_block_metadata(&self, transaction_id: &TransactionId) -> Result<BlockMetadataResponse> {
         let path = &format!("api/core/v2/transactions/{transaction_id}/included-block/metadata");
 
-        self.get_request(path, None, true, true).await
+        let metadata: BlockMetadataResponse = self.get_request(path, None, true, true).await?;
+        self.verify_block_confirmation(&metadata).await?;
+
+        Ok(metadata)
     }
 
     // Milestones routes.
@@ -332,12 +360,21 @@ impl ClientInner {
     pub async fn get_milestone_by_id(&self, milestone_id: &MilestoneId) -> Result<MilestonePayload> {
         let path = &format!("api/core/v2/milestones/{milestone_id}");
 
-        let dto = self.get_request::<MilestonePayloadDto>(path, None, false, true).await?;
+        let dto = self
+            .get_request_cached::<MilestonePayloadDto>(path, false, true, |_| true)
+            .await?;
+        let milestone = MilestonePayload::try_from_dto_with_params(dto, self.get_protocol_parameters().await?)?;
 
-        Ok(MilestonePayload::try_from_dto_with_params(
-            dto,
-            self.get_protocol_parameters().await?,
-        )?)
+        if milestone.id() != *milestone_id {
+            return Err(MilestoneVerificationError::UnexpectedMilestone {
+                requested: milestone_id.to_string(),
+                found: milestone.id().to_string(),
+            }
+            .into());
+        }
+        self.verify_milestone(&milestone).await?;
+
+        Ok(milestone)
     }
 
     /// Gets the milestone by the given milestone id.
@@ -353,7 +390,19 @@ impl ClientInner {
     pub async fn get_utxo_changes_by_id(&self, milestone_id: &MilestoneId) -> Result<UtxoChangesResponse> {
         let path = &format!("api/core/v2/milestones/{milestone_id}/utxo-changes");
 
-        self.get_request(path, None, false, false).await
+        let utxo_changes: UtxoChangesResponse = self.get_request(path, None, false, false).await?;
+        if self.milestone_verifier.read().await.is_some() {
+            let milestone_index = *self.get_milestone_by_id(milestone_id).await?.essence().index();
+            if milestone_index != utxo_changes.index {
+                return Err(MilestoneVerificationError::UnexpectedMilestone {
+                    requested: milestone_index.to_string(),
+                    found: utxo_changes.index.to_string(),
+                }
+                .into());
+            }
+        }
+
+        Ok(utxo_changes)
     }
 
     /// Gets the milestone by the given milestone index.
@@ -361,12 +410,21 @@ impl ClientInner {
     pub async fn get_milestone_by_index(&self, index: u32) -> Result<MilestonePayload> {
         let path = &format!("api/core/v2/milestones/by-index/{index}");
 
-        let dto = self.get_request::<MilestonePayloadDto>(path, None, false, true).await?;
+        let dto = self
+            .get_request_cached::<MilestonePayloadDto>(path, false, true, |_| true)
+            .await?;
+        let milestone = MilestonePayload::try_from_dto_with_params(dto, self.get_protocol_parameters().await?)?;
 
-        Ok(MilestonePayload::try_from_dto_with_params(
-            dto,
-            self.get_protocol_parameters().await?,
-        )?)
+        if *milestone.essence().index() != index {
+            return Err(MilestoneVerificationError::UnexpectedMilestone {
+                requested: index.to_string(),
+                found: milestone.essence().index().to_string(),
+            }
+            .into());
+        }
+        self.verify_milestone(&milestone).await?;
+
+        Ok(milestone)
     }
 
     /// Gets the milestone by the given milestone index.
@@ -382,7 +440,18 @@ impl ClientInner {
     pub async fn get_utxo_changes_by_index(&self, index: u32) -> Result<UtxoChangesResponse> {
         let path = &format!("api/core/v2/milestones/by-index/{index}/utxo-changes");
 
-        self.get_request(path, None, false, false).await
+        self.verify_confirmation(index).await?;
+
+        let utxo_changes: UtxoChangesResponse = self.get_request(path, None, false, false).await?;
+        if self.milestone_verifier.read().await.is_some() && utxo_changes.index != index {
+            return Err(MilestoneVerificationError::UnexpectedMilestone {
+                requested: index.to_string(),
+                found: utxo_changes.index.to_string(),
+            }
+            .into());
+        }
+
+        Ok(utxo_changes)
     }
 
     // Peers routes.
@@ -420,8 +489,22 @@ impl ClientInner {
 impl Client {
     /// GET /api/core/v2/info endpoint
     pub async fn get_node_info(url: &str, auth: Option<NodeAuth>) -> Result<InfoResponse> {
-        let mut url = crate::client::node_manager::builder::validate_url(Url::parse(url)?)?;
-        if let Some(auth) = &auth {
+        Self::get_node_info_with(&HttpClient::new(DEFAULT_USER_AGENT.to_string()), url, auth).await
+    }
+
+    /// GET /api/core/v2/info endpoint, requested with the given http client
+    pub(crate) async fn get_node_info_with(
+        http_client: &HttpClient,
+        url: &str,
+        auth: Option<NodeAuth>,
+    ) -> Result<InfoResponse> {
+        let node = Node {
+            url: crate::client::node_manager::builder::validate_url(Url::parse(url)?)?,
+            auth,
+            disabled: false,
+        };
+        let mut url = node.url.clone();
+        if let Some(auth) = &node.auth {
             if let Some((name, password)) = &auth.basic_auth_name_pwd {
                 url.set_username(name)
                     .map_err(|_| crate::client::Error::UrlAuth("username"))?;
@@ -432,19 +515,11 @@ impl Client {
         let path = "api/core/v2/info";
         url.set_path(path);
 
-        let resp: InfoResponse =
-            crate::client::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
-                .get(
-                    Node {
-                        url,
-                        auth,
-                        disabled: false,
-                    },
-                    DEFAULT_API_TIMEOUT,
-                )
-                .await?
-                .into_json()
-                .await?;
+        let resp: InfoResponse = http_client
+            .get(&node, url, DEFAULT_API_TIMEOUT)
+            .await?
+            .into_json()
+            .await?;
 
         Ok(resp)
     }
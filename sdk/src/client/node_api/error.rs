@@ -10,6 +10,9 @@ pub enum Error {
     /// The requested data was not found. (404)
     #[error("The requested data {0} was not found.")]
     NotFound(String),
+    /// JSON error
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
     /// Reqwest error
     #[error("{0}")]
     Reqwest(#[from] reqwest::Error),
@@ -23,6 +26,9 @@ pub enum Error {
         /// The url of the API.
         url: String,
     },
+    /// Error of a custom transport
+    #[error("transport error: {0}")]
+    Transport(String),
     /// None of our nodes have remote Pow enabled
     #[error("No node available for remote Pow")]
     UnavailablePow,
@@ -6,10 +6,12 @@
 pub mod query_parameters;
 pub mod routes;
 
+use futures::{Stream, StreamExt, TryStreamExt};
+
 pub(crate) use self::query_parameters::{QueryParameter, QueryParameters};
 use crate::{
     client::{ClientInner, Result},
-    types::api::plugins::indexer::OutputIdsResponse,
+    types::{api::plugins::indexer::OutputIdsResponse, block::output::OutputWithMetadata},
 };
 
 impl ClientInner {
@@ -56,4 +58,52 @@ impl ClientInner {
 
         Ok(merged_output_ids_response)
     }
+
+    /// Get the output ids for a provided URL route and query parameters page by page, following the cursor until the
+    /// last page. If a `QueryParameter::Cursor(_)` is provided, the pages start from it.
+    pub(crate) fn get_output_ids_stream(
+        &self,
+        route: &'static str,
+        query_parameters: QueryParameters,
+        need_quorum: bool,
+        prefer_permanode: bool,
+    ) -> impl Stream<Item = Result<OutputIdsResponse>> + '_ {
+        futures::stream::try_unfold(Some(query_parameters), move |query_parameters| async move {
+            let Some(mut query_parameters) = query_parameters else {
+                return Ok(None);
+            };
+
+            let output_ids_response = self
+                .get_request::<OutputIdsResponse>(
+                    route,
+                    query_parameters.to_query_string().as_deref(),
+                    need_quorum,
+                    prefer_permanode,
+                )
+                .await?;
+
+            let next_query_parameters = output_ids_response.cursor.as_ref().map(|cursor| {
+                query_parameters.replace(QueryParameter::Cursor(cursor.to_string()));
+                query_parameters
+            });
+
+            Ok(Some((output_ids_response, next_query_parameters)))
+        })
+    }
+
+    /// Resolves pages of output ids, as returned by e.g. [`ClientInner::basic_output_ids_stream()`], to their outputs.
+    /// At most `concurrency` outputs are requested in parallel and only a single page is held at a time.
+    pub fn get_outputs_stream<'a>(
+        &'a self,
+        output_ids: impl Stream<Item = Result<OutputIdsResponse>> + 'a,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<Vec<OutputWithMetadata>>> + 'a {
+        output_ids.and_then(move |output_ids_response| async move {
+            futures::stream::iter(output_ids_response.items)
+                .map(|output_id| async move { self.get_output(&output_id).await })
+                .buffered(concurrency.max(1))
+                .try_collect()
+                .await
+        })
+    }
 }
@@ -319,6 +319,142 @@ pub(crate) fn verify_query_parameters_nft_outputs(query_parameters: Vec<QueryPar
     Ok(QueryParameters::new(query_parameters))
 }
 
+macro_rules! output_query {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($setter:ident($ty:ty) => $variant:ident),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        #[must_use]
+        pub struct $name(QueryParameters);
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self(QueryParameters::empty())
+            }
+        }
+
+        impl $name {
+            /// Creates a query without any filter.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            $(
+                #[doc = concat!("Sets the [`QueryParameter::", stringify!($variant), "`] parameter.")]
+                pub fn $setter(mut self, value: $ty) -> Self {
+                    self.0.replace(QueryParameter::$variant(value));
+                    self
+                }
+            )+
+        }
+
+        impl From<$name> for QueryParameters {
+            fn from(query: $name) -> Self {
+                query.0
+            }
+        }
+
+        impl From<$name> for Vec<QueryParameter> {
+            fn from(query: $name) -> Self {
+                query.0.0
+            }
+        }
+    };
+}
+
+output_query!(
+    /// Typed query for [`ClientInner::basic_output_ids()`](crate::client::ClientInner::basic_output_ids), only
+    /// exposing the parameters supported by the route.
+    BasicOutputQuery {
+        with_address(Bech32Address) => Address,
+        with_has_native_tokens(bool) => HasNativeTokens,
+        with_min_native_token_count(u32) => MinNativeTokenCount,
+        with_max_native_token_count(u32) => MaxNativeTokenCount,
+        with_has_storage_deposit_return(bool) => HasStorageDepositReturn,
+        with_storage_deposit_return_address(Bech32Address) => StorageDepositReturnAddress,
+        with_has_timelock(bool) => HasTimelock,
+        with_timelocked_before(u32) => TimelockedBefore,
+        with_timelocked_after(u32) => TimelockedAfter,
+        with_has_expiration(bool) => HasExpiration,
+        with_expires_before(u32) => ExpiresBefore,
+        with_expires_after(u32) => ExpiresAfter,
+        with_expiration_return_address(Bech32Address) => ExpirationReturnAddress,
+        with_sender(Bech32Address) => Sender,
+        with_tag(String) => Tag,
+        with_created_before(u32) => CreatedBefore,
+        with_created_after(u32) => CreatedAfter,
+        with_page_size(usize) => PageSize,
+        with_cursor(String) => Cursor,
+        with_unlockable_by_address(Bech32Address) => UnlockableByAddress,
+    }
+);
+
+output_query!(
+    /// Typed query for [`ClientInner::alias_output_ids()`](crate::client::ClientInner::alias_output_ids), only
+    /// exposing the parameters supported by the route.
+    AliasOutputQuery {
+        with_state_controller(Bech32Address) => StateController,
+        with_governor(Bech32Address) => Governor,
+        with_issuer(Bech32Address) => Issuer,
+        with_sender(Bech32Address) => Sender,
+        with_has_native_tokens(bool) => HasNativeTokens,
+        with_min_native_token_count(u32) => MinNativeTokenCount,
+        with_max_native_token_count(u32) => MaxNativeTokenCount,
+        with_created_before(u32) => CreatedBefore,
+        with_created_after(u32) => CreatedAfter,
+        with_page_size(usize) => PageSize,
+        with_cursor(String) => Cursor,
+        with_unlockable_by_address(Bech32Address) => UnlockableByAddress,
+    }
+);
+
+output_query!(
+    /// Typed query for [`ClientInner::foundry_output_ids()`](crate::client::ClientInner::foundry_output_ids), only
+    /// exposing the parameters supported by the route.
+    FoundryOutputQuery {
+        with_alias_address(Bech32Address) => AliasAddress,
+        with_has_native_tokens(bool) => HasNativeTokens,
+        with_min_native_token_count(u32) => MinNativeTokenCount,
+        with_max_native_token_count(u32) => MaxNativeTokenCount,
+        with_created_before(u32) => CreatedBefore,
+        with_created_after(u32) => CreatedAfter,
+        with_page_size(usize) => PageSize,
+        with_cursor(String) => Cursor,
+    }
+);
+
+output_query!(
+    /// Typed query for [`ClientInner::nft_output_ids()`](crate::client::ClientInner::nft_output_ids), only exposing
+    /// the parameters supported by the route.
+    NftOutputQuery {
+        with_address(Bech32Address) => Address,
+        with_has_native_tokens(bool) => HasNativeTokens,
+        with_min_native_token_count(u32) => MinNativeTokenCount,
+        with_max_native_token_count(u32) => MaxNativeTokenCount,
+        with_has_storage_deposit_return(bool) => HasStorageDepositReturn,
+        with_storage_deposit_return_address(Bech32Address) => StorageDepositReturnAddress,
+        with_has_timelock(bool) => HasTimelock,
+        with_timelocked_before(u32) => TimelockedBefore,
+        with_timelocked_after(u32) => TimelockedAfter,
+        with_has_expiration(bool) => HasExpiration,
+        with_expires_before(u32) => ExpiresBefore,
+        with_expires_after(u32) => ExpiresAfter,
+        with_expiration_return_address(Bech32Address) => ExpirationReturnAddress,
+        with_issuer(Bech32Address) => Issuer,
+        with_sender(Bech32Address) => Sender,
+        with_tag(String) => Tag,
+        with_created_before(u32) => CreatedBefore,
+        with_created_after(u32) => CreatedAfter,
+        with_page_size(usize) => PageSize,
+        with_cursor(String) => Cursor,
+        with_unlockable_by_address(Bech32Address) => UnlockableByAddress,
+    }
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,4 +485,31 @@ mod tests {
         // Contains no cursor query parameter
         assert!(!query_parameters.any(|param| matches!(param, QueryParameter::Cursor(_))));
     }
+
+    #[test]
+    fn output_query() {
+        let address =
+            Bech32Address::try_from_str("atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r").unwrap();
+
+        let query = BasicOutputQuery::new()
+            .with_has_expiration(true)
+            .with_address(address)
+            .with_has_expiration(false)
+            .with_page_size(10);
+        assert_eq!(
+            QueryParameters::from(query.clone()).to_query_string().as_deref(),
+            Some(
+                "address=atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r&hasExpiration=false&pageSize=10"
+            )
+        );
+        assert!(verify_query_parameters_basic_outputs(query.into()).is_ok());
+
+        assert!(verify_query_parameters_alias_outputs(AliasOutputQuery::new().with_governor(address).into()).is_ok());
+        assert!(
+            verify_query_parameters_foundry_outputs(FoundryOutputQuery::new().with_alias_address(address).into())
+                .is_ok()
+        );
+        assert!(verify_query_parameters_nft_outputs(NftOutputQuery::new().with_issuer(address).into()).is_ok());
+        assert_eq!(FoundryOutputQuery::new().0.to_query_string(), None);
+    }
 }
@@ -3,6 +3,8 @@
 
 //! IOTA node indexer routes
 
+use futures::Stream;
+
 use crate::{
     client::{
         node_api::indexer::{
@@ -23,6 +25,10 @@ use crate::{
 
 // hornet: https://github.com/gohornet/hornet/blob/develop/plugins/indexer/routes.go
 
+// The `*_stream` variants of the routes yield the output ids page by page instead of collecting all of them, so that
+// big results can be processed incrementally or stopped early. Use `QueryParameter::PageSize` to set the page size.
+// The streams need to be pinned to be polled, e.g. with `Box::pin()`.
+
 impl ClientInner {
     /// Get basic, alias, nft and foundry outputs filtered by the given parameters.
     /// GET with query parameter returns all outputIDs that fit these filter criteria.
@@ -41,6 +47,19 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Get the ids of basic, alias, nft and foundry outputs filtered by the given parameters page by page, see
+    /// [`ClientInner::output_ids()`].
+    pub fn output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>>,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let route = "api/indexer/v1/outputs";
+
+        let query_parameters = verify_query_parameters_outputs(query_parameters.into())?;
+
+        Ok(self.get_output_ids_stream(route, query_parameters, true, false))
+    }
+
     /// Get basic outputs filtered by the given parameters.
     /// GET with query parameter returns all outputIDs that fit these filter criteria.
     /// Query parameters: "address", "hasStorageDepositReturn", "storageDepositReturnAddress",
@@ -59,6 +78,19 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Get the ids of basic outputs filtered by the given parameters page by page, see
+    /// [`ClientInner::basic_output_ids()`].
+    pub fn basic_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>>,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let route = "api/indexer/v1/outputs/basic";
+
+        let query_parameters = verify_query_parameters_basic_outputs(query_parameters.into())?;
+
+        Ok(self.get_output_ids_stream(route, query_parameters, true, false))
+    }
+
     /// Get alias outputs filtered by the given parameters.
     /// GET with query parameter returns all outputIDs that fit these filter criteria.
     /// Query parameters: "stateController", "governor", "issuer", "sender", "createdBefore", "createdAfter"
@@ -75,6 +107,19 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Get the ids of alias outputs filtered by the given parameters page by page, see
+    /// [`ClientInner::alias_output_ids()`].
+    pub fn alias_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>>,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let route = "api/indexer/v1/outputs/alias";
+
+        let query_parameters = verify_query_parameters_alias_outputs(query_parameters.into())?;
+
+        Ok(self.get_output_ids_stream(route, query_parameters, true, false))
+    }
+
     /// Get alias output by its aliasID.
     /// api/indexer/v1/outputs/alias/:{AliasId}
     pub async fn alias_output_id(&self, alias_id: AliasId) -> Result<OutputId> {
@@ -103,6 +148,19 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Get the ids of foundry outputs filtered by the given parameters page by page, see
+    /// [`ClientInner::foundry_output_ids()`].
+    pub fn foundry_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>>,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let route = "api/indexer/v1/outputs/foundry";
+
+        let query_parameters = verify_query_parameters_foundry_outputs(query_parameters.into())?;
+
+        Ok(self.get_output_ids_stream(route, query_parameters, true, false))
+    }
+
     /// Get foundry output by its foundryID.
     /// api/indexer/v1/outputs/foundry/:{FoundryID}
     pub async fn foundry_output_id(&self, foundry_id: FoundryId) -> Result<OutputId> {
@@ -132,6 +190,19 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Get the ids of NFT outputs filtered by the given parameters page by page, see
+    /// [`ClientInner::nft_output_ids()`].
+    pub fn nft_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>>,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let route = "api/indexer/v1/outputs/nft";
+
+        let query_parameters = verify_query_parameters_nft_outputs(query_parameters.into())?;
+
+        Ok(self.get_output_ids_stream(route, query_parameters, true, false))
+    }
+
     /// Get NFT output by its nftID.
     /// api/indexer/v1/outputs/nft/:{NftId}
     pub async fn nft_output_id(&self, nft_id: NftId) -> Result<OutputId> {
@@ -16,4 +16,16 @@ pub enum Error {
     /// Invalid topic.
     #[error("invalid topic {0}")]
     InvalidTopic(String),
+    /// Block error, when a payload couldn't be decoded.
+    #[error("{0}")]
+    Block(#[from] crate::types::block::Error),
+    /// The stream wasn't polled fast enough and events were dropped.
+    #[error("stream lagged behind and {0} events were dropped")]
+    Lagged(u64),
+    /// JSON error, when a payload couldn't be decoded.
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+    /// A payload of the topic can't be decoded into the requested type.
+    #[error("unexpected payload for topic {0}")]
+    UnexpectedPayload(String),
 }
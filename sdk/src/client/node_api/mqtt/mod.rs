@@ -4,16 +4,22 @@
 //! IOTA node MQTT API
 
 mod error;
+mod resync;
 pub mod types;
 
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::{Arc, Weak},
+    time::Instant,
+};
 
 use crypto::utils;
+use futures::{Stream, StreamExt};
 use log::warn;
 use packable::PackableExt;
-use rumqttc::{AsyncClient, Event, EventLoop, Incoming, MqttOptions, NetworkOptions, QoS, SubscribeFilter, Transport};
-use tokio::sync::watch::Receiver as WatchReceiver;
+use rumqttc::{AsyncClient, Event, EventLoop, Incoming, MqttOptions, NetworkOptions, Publish, Transport};
+use tokio::sync::{mpsc, watch::Receiver as WatchReceiver};
 
+pub(crate) use self::resync::Resynchronization;
 pub use self::{error::Error, types::*};
 use crate::{
     client::{Client, ClientInner},
@@ -23,6 +29,9 @@ use crate::{
     },
 };
 
+/// Amount of events buffered per stream subscription before further events are dropped until the stream is polled.
+const STREAM_BUFFER_SIZE: usize = 100;
+
 impl Client {
     /// Returns a handle to the MQTT topics manager.
     pub fn subscriber(&self) -> MqttManager<'_> {
@@ -38,6 +47,45 @@ impl Client {
         MqttManager::new(self).with_topics(topics).subscribe(callback).await
     }
 
+    /// Subscribe to MQTT events as a stream of decoded values, e.g. [`Block`]s for block topics or
+    /// [`OutputWithMetadata`](crate::types::block::output::OutputWithMetadata)s for output topics.
+    /// Events are buffered up to a limit, after which they are dropped until the stream is polled again, which is
+    /// reported with an [`Error::Lagged`]. Like all subscriptions, the topic is resubscribed after a reconnection.
+    /// Dropping the stream ends the subscription.
+    pub async fn subscribe_stream<T: FromTopicEvent>(
+        &self,
+        topic: Topic,
+    ) -> Result<impl Stream<Item = Result<T, Error>>, Error> {
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER_SIZE);
+        let handler = Arc::new(TopicHandler::Stream(StreamSender::new(sender)));
+        let protocol_parameters = self.network_info.read().await.protocol_parameters.clone();
+
+        MqttManager::new(self)
+            .with_topic(topic.clone())
+            .subscribe_handler(handler.clone())
+            .await?;
+
+        let guard = StreamGuard {
+            client: self.clone(),
+            topic,
+            handler: Arc::downgrade(&handler),
+        };
+
+        Ok(
+            futures::stream::unfold((receiver, guard), |(mut receiver, guard)| async move {
+                receiver.recv().await.map(|event| (event, (receiver, guard)))
+            })
+            .flat_map(move |(dropped, event)| {
+                let lagged = (dropped > 0).then_some(Err(Error::Lagged(dropped)));
+                futures::stream::iter(
+                    lagged
+                        .into_iter()
+                        .chain([T::from_topic_event(event, &protocol_parameters)]),
+                )
+            }),
+        )
+    }
+
     /// Unsubscribe from MQTT events.
     pub async fn unsubscribe(&self, topics: impl IntoIterator<Item = Topic> + Send) -> Result<(), Error> {
         MqttManager::new(self).with_topics(topics).unsubscribe().await
@@ -158,17 +206,12 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
                         if !is_subscribed {
                             is_subscribed = true;
                             // resubscribe topics
-                            let topics = client
-                                .inner
-                                .mqtt
-                                .topic_handlers
-                                .read()
-                                .await
-                                .keys()
-                                .map(|t| SubscribeFilter::new(t.as_str().to_owned(), QoS::AtLeastOnce))
-                                .collect::<Vec<SubscribeFilter>>();
-                            if !topics.is_empty() {
-                                let _ = client
+                            let topics = {
+                                let topic_handlers = client.mqtt.topic_handlers.read().await;
+                                (!topic_handlers.is_empty()).then(|| resync::subscribe_filters(topic_handlers.keys()))
+                            };
+                            if let Some(topics) = topics {
+                                if let Err(e) = client
                                     .inner
                                     .mqtt
                                     .client
@@ -177,87 +220,34 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
                                     .as_mut()
                                     .unwrap()
                                     .subscribe_many(topics)
-                                    .await;
+                                    .await
+                                {
+                                    warn!("Resubscribing after reconnection failed: {:?}", e);
+                                }
                             }
+                            // The event loop has to be polled to receive the responses of the subscriptions, so
+                            // missed events are delivered in the background while live events are held back.
+                            resync::spawn_resynchronize(&client).await;
                         }
                     }
                     Ok(Event::Incoming(Incoming::Publish(p))) => {
-                        let client = client.clone();
-
-                        crate::client::async_runtime::spawn(async move {
-                            let mqtt_topic_handlers = client.mqtt.topic_handlers.read().await;
-
-                            if let Some(handlers) = mqtt_topic_handlers.get(&Topic::new_unchecked(&p.topic)) {
-                                let event = {
-                                    if p.topic.contains("blocks") || p.topic.contains("included-block") {
-                                        let payload = &*p.payload;
-                                        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
-
-                                        match Block::unpack_verified(payload, protocol_parameters) {
-                                            Ok(block) => Ok(TopicEvent {
-                                                topic: p.topic.clone(),
-                                                payload: MqttPayload::Block((&block).into()),
-                                            }),
-                                            Err(e) => {
-                                                warn!("Block unpacking failed: {:?}", e);
-                                                Err(())
-                                            }
-                                        }
-                                    } else if p.topic.contains("milestones") {
-                                        let payload = &*p.payload;
-                                        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
-
-                                        match Payload::unpack_verified(payload, protocol_parameters) {
-                                            Ok(Payload::Milestone(milestone)) => Ok(TopicEvent {
-                                                topic: p.topic.clone(),
-                                                payload: MqttPayload::MilestonePayload(milestone.as_ref().into()),
-                                            }),
-                                            Ok(p) => {
-                                                warn!(
-                                                    "'milestone' topic returned non-milestone payload, kind: {:?}",
-                                                    p.kind()
-                                                );
-                                                Err(())
-                                            }
-                                            Err(e) => {
-                                                warn!("MilestonePayload unpacking failed: {:?}", e);
-                                                Err(())
-                                            }
-                                        }
-                                    } else if p.topic.contains("receipts") {
-                                        let payload = &*p.payload;
-                                        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
-
-                                        match ReceiptMilestoneOption::unpack_verified(payload, protocol_parameters) {
-                                            Ok(receipt) => Ok(TopicEvent {
-                                                topic: p.topic.clone(),
-                                                payload: MqttPayload::Receipt((&receipt).into()),
-                                            }),
-                                            Err(e) => {
-                                                warn!("Receipt unpacking failed: {:?}", e);
-                                                Err(())
-                                            }
-                                        }
-                                    } else {
-                                        match serde_json::from_slice(&p.payload) {
-                                            Ok(value) => Ok(TopicEvent {
-                                                topic: p.topic.clone(),
-                                                payload: MqttPayload::Json(value),
-                                            }),
-                                            Err(e) => {
-                                                warn!("Cannot parse JSON: {:?}", e);
-                                                Err(())
-                                            }
-                                        }
-                                    }
-                                };
-                                if let Ok(event) = event {
-                                    for handler in handlers {
-                                        handler(&event);
-                                    }
-                                };
+                        let Some(p) = resync::hold_back_publish(&client, p).await else {
+                            continue;
+                        };
+                        let handlers = client
+                            .mqtt
+                            .topic_handlers
+                            .read()
+                            .await
+                            .get(&Topic::new_unchecked(&p.topic))
+                            .cloned();
+
+                        resync::observe_publish(&client, &p).await;
+                        if let Some(handlers) = handlers {
+                            if let Some(event) = decode_event(&client, &p).await {
+                                dispatch_event(&client, event, handlers).await;
                             }
-                        });
+                        }
                     }
                     Err(_) => {
                         if error_instant.elapsed().as_secs() < 5 {
@@ -280,6 +270,138 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
     });
 }
 
+async fn decode_event(client: &Client, p: &Publish) -> Option<TopicEvent> {
+    if p.topic.contains("blocks") || p.topic.contains("included-block") {
+        let payload = &*p.payload;
+        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
+
+        match Block::unpack_verified(payload, protocol_parameters) {
+            Ok(block) => Some(TopicEvent {
+                topic: p.topic.clone(),
+                payload: MqttPayload::Block((&block).into()),
+            }),
+            Err(e) => {
+                warn!("Block unpacking failed: {:?}", e);
+                None
+            }
+        }
+    } else if p.topic.contains("milestones") {
+        let payload = &*p.payload;
+        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
+
+        match Payload::unpack_verified(payload, protocol_parameters) {
+            Ok(Payload::Milestone(milestone)) => Some(TopicEvent {
+                topic: p.topic.clone(),
+                payload: MqttPayload::MilestonePayload(milestone.as_ref().into()),
+            }),
+            Ok(p) => {
+                warn!("'milestone' topic returned non-milestone payload, kind: {:?}", p.kind());
+                None
+            }
+            Err(e) => {
+                warn!("MilestonePayload unpacking failed: {:?}", e);
+                None
+            }
+        }
+    } else if p.topic.contains("receipts") {
+        let payload = &*p.payload;
+        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
+
+        match ReceiptMilestoneOption::unpack_verified(payload, protocol_parameters) {
+            Ok(receipt) => Some(TopicEvent {
+                topic: p.topic.clone(),
+                payload: MqttPayload::Receipt((&receipt).into()),
+            }),
+            Err(e) => {
+                warn!("Receipt unpacking failed: {:?}", e);
+                None
+            }
+        }
+    } else {
+        match serde_json::from_slice(&p.payload) {
+            Ok(value) => Some(TopicEvent {
+                topic: p.topic.clone(),
+                payload: MqttPayload::Json(value),
+            }),
+            Err(e) => {
+                warn!("Cannot parse JSON: {:?}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Calls the callbacks of a topic in a separate task and sends the event to its streams, without waiting for them to
+/// be polled.
+async fn dispatch_event(client: &Client, event: TopicEvent, handlers: Vec<Arc<TopicHandler>>) {
+    let (callbacks, streams): (Vec<_>, Vec<_>) = handlers
+        .into_iter()
+        .partition(|handler| matches!(**handler, TopicHandler::Callback(_)));
+
+    if !callbacks.is_empty() {
+        let event = event.clone();
+        crate::client::async_runtime::spawn(async move {
+            for handler in callbacks {
+                if let TopicHandler::Callback(callback) = &*handler {
+                    callback(&event);
+                }
+            }
+        });
+    }
+
+    for handler in streams {
+        if let TopicHandler::Stream(sender) = &*handler {
+            if !sender.send(event.clone()) {
+                remove_closed_stream(client, &event.topic, &handler).await;
+            }
+        }
+    }
+}
+
+/// Removes the handler of a stream when the stream is dropped.
+struct StreamGuard {
+    client: Client,
+    topic: Topic,
+    handler: Weak<TopicHandler>,
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        // The handler is gone already if the topic was unsubscribed.
+        if let Some(handler) = self.handler.upgrade() {
+            let client = self.client.clone();
+            let topic = self.topic.clone();
+            crate::client::async_runtime::spawn(async move {
+                remove_closed_stream(&client, topic.as_str(), &handler).await;
+            });
+        }
+    }
+}
+
+/// Removes the handler of a dropped stream and unsubscribes from its topic if it was the last handler.
+async fn remove_closed_stream(client: &Client, topic: &str, handler: &Arc<TopicHandler>) {
+    let topic = Topic::new_unchecked(topic);
+    let is_last_handler = {
+        let mut mqtt_topic_handlers = client.mqtt.topic_handlers.write().await;
+        mqtt_topic_handlers.get_mut(&topic).is_some_and(|handlers| {
+            let count = handlers.len();
+            handlers.retain(|h| !Arc::ptr_eq(h, handler));
+            // Both a failed send and dropping the stream remove the handler, only the first removal unsubscribes.
+            handlers.len() < count && handlers.is_empty()
+        })
+    };
+
+    if is_last_handler {
+        // Unsubscribing goes through the event loop, so it can't be awaited while the event loop is waiting for it.
+        let client = client.clone();
+        crate::client::async_runtime::spawn(async move {
+            if let Err(e) = client.unsubscribe([topic]).await {
+                warn!("Unsubscribing from a dropped stream failed: {:?}", e);
+            }
+        });
+    }
+}
+
 /// MQTT subscriber.
 pub struct MqttManager<'a> {
     client: &'a Client,
@@ -312,6 +434,7 @@ impl<'a> MqttManager<'a> {
         if let Some(client) = &*self.client.mqtt.client.write().await {
             client.disconnect().await?;
             self.client.mqtt.topic_handlers.write().await.clear();
+            *self.client.mqtt.last_milestone_index.write().await = None;
         }
 
         *self.client.mqtt.client.write().await = None;
@@ -353,11 +476,11 @@ impl<'a> MqttTopicManager<'a> {
         self,
         callback: C,
     ) -> Result<(), Error> {
-        let cb =
-            Arc::new(Box::new(callback)
-                as Box<
-                    dyn Fn(&crate::client::node_api::mqtt::TopicEvent) + Send + Sync + 'static,
-                >);
+        self.subscribe_handler(Arc::new(TopicHandler::Callback(Box::new(callback))))
+            .await
+    }
+
+    async fn subscribe_handler(self, handler: Arc<TopicHandler>) -> Result<(), Error> {
         set_mqtt_client(self.client).await?;
         self.client
             .inner
@@ -367,18 +490,15 @@ impl<'a> MqttTopicManager<'a> {
             .await
             .as_ref()
             .ok_or(Error::ConnectionNotFound)?
-            .subscribe_many(
-                self.topics
-                    .iter()
-                    .map(|t| SubscribeFilter::new(t.as_str().to_owned(), QoS::AtLeastOnce)),
-            )
+            .subscribe_many(resync::subscribe_filters(&self.topics))
             .await?;
         {
             let mut mqtt_topic_handlers = self.client.mqtt.topic_handlers.write().await;
             for topic in self.topics {
-                mqtt_topic_handlers.entry(topic).or_default().push(cb.clone());
+                mqtt_topic_handlers.entry(topic).or_default().push(handler.clone());
             }
         }
+        resync::observe_subscription(self.client).await;
         Ok(())
     }
 
@@ -394,20 +514,32 @@ impl<'a> MqttTopicManager<'a> {
             }
         };
 
-        if let Some(client) = &*self.client.mqtt.client.write().await {
-            for topic in &topics {
-                client.unsubscribe(topic.as_str()).await?;
-            }
-        }
-
         let empty_topic_handlers = {
             let mut mqtt_topic_handlers = self.client.mqtt.topic_handlers.write().await;
-            for topic in topics {
-                mqtt_topic_handlers.remove(&topic);
+            for topic in &topics {
+                mqtt_topic_handlers.remove(topic);
             }
             mqtt_topic_handlers.is_empty()
         };
 
+        if let Some(client) = &*self.client.mqtt.client.write().await {
+            // The confirmed milestones stay subscribed as long as any topic is.
+            for topic in topics
+                .iter()
+                .filter(|topic| empty_topic_handlers || !resync::is_tracking_topic(topic))
+            {
+                client.unsubscribe(topic.as_str()).await?;
+            }
+            if empty_topic_handlers {
+                client.unsubscribe(resync::CONFIRMED_MILESTONE_INFO_TOPIC).await?;
+            }
+        }
+
+        if empty_topic_handlers {
+            // Events of a later subscription are only backfilled from the milestone confirmed when subscribing.
+            *self.client.mqtt.last_milestone_index.write().await = None;
+        }
+
         if self.client.mqtt.broker_options.read().await.automatic_disconnect && empty_topic_handlers {
             MqttManager::new(self.client).disconnect().await?;
         }
@@ -415,3 +547,44 @@ impl<'a> MqttTopicManager<'a> {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "mock_node"))]
+mod tests {
+    use super::*;
+    use crate::client::mock_node::MockNode;
+
+    #[tokio::test]
+    async fn dispatch_to_lagging_and_dropped_streams() {
+        let node = MockNode::builder().finish().await.unwrap();
+        let client = Client::builder().with_node(node.url()).unwrap().finish().await.unwrap();
+        let topic = Topic::new("milestone-info/latest").unwrap();
+        let event = |index: u32| TopicEvent {
+            topic: topic.as_str().to_owned(),
+            payload: MqttPayload::Json(serde_json::json!({ "index": index })),
+        };
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        let handler = Arc::new(TopicHandler::Stream(StreamSender::new(sender)));
+        client
+            .mqtt
+            .topic_handlers
+            .write()
+            .await
+            .insert(topic.clone(), vec![handler.clone()]);
+
+        // Events that don't fit in the buffer are dropped instead of waiting for the stream.
+        for index in 0..3 {
+            dispatch_event(&client, event(index), vec![handler.clone()]).await;
+        }
+        assert_eq!(receiver.try_recv().unwrap(), (0, event(0)));
+        assert!(receiver.try_recv().is_err());
+
+        dispatch_event(&client, event(3), vec![handler.clone()]).await;
+        assert_eq!(receiver.try_recv().unwrap(), (2, event(3)));
+
+        drop(receiver);
+        dispatch_event(&client, event(4), vec![handler.clone()]).await;
+        let topic_handlers = client.mqtt.topic_handlers.read().await;
+        assert!(topic_handlers.get(&topic).into_iter().flatten().next().is_none());
+    }
+}
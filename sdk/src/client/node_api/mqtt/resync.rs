@@ -0,0 +1,463 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Backfilling of the milestone and address output events missed while the broker connection was down.
+
+use log::warn;
+use rumqttc::{Publish, QoS, SubscribeFilter};
+
+use super::{decode_event, dispatch_event, MqttEvent, MqttPayload, Topic, TopicEvent};
+use crate::{
+    client::{Client, Result},
+    types::{
+        api::core::response::{LatestMilestoneResponse, OutputWithMetadataResponse},
+        block::{
+            address::{Address, Bech32Address},
+            output::{unlock_condition::UnlockCondition, Output, OutputId},
+            payload::milestone::MilestonePayload,
+        },
+    },
+};
+
+const MILESTONES_TOPIC: &str = "milestones";
+pub(crate) const CONFIRMED_MILESTONE_INFO_TOPIC: &str = "milestone-info/confirmed";
+const ADDRESS_OUTPUTS_TOPIC_PREFIX: &str = "outputs/unlock/";
+
+/// The state of the backfill of missed events.
+#[derive(Debug, Default)]
+pub(crate) struct Resynchronization {
+    /// The live publishes held back until the missed events are delivered, `Some` while a backfill is running.
+    buffered: Option<Vec<Publish>>,
+    /// Whether the connection was restored again while backfilling, so more events may have been missed.
+    restarted: bool,
+}
+
+/// A subscribed `outputs/unlock/{condition}/{address}[/spent]` topic.
+struct AddressOutputsTopic {
+    topic: Topic,
+    condition: String,
+    address: Address,
+    spent: bool,
+}
+
+impl AddressOutputsTopic {
+    fn parse(topic: &Topic) -> Option<Self> {
+        let rest = topic.as_str().strip_prefix(ADDRESS_OUTPUTS_TOPIC_PREFIX)?;
+        let (condition, rest) = rest.split_once('/')?;
+        let (address, spent) = rest
+            .strip_suffix("/spent")
+            .map_or((rest, false), |address| (address, true));
+
+        Some(Self {
+            topic: topic.clone(),
+            condition: condition.to_owned(),
+            address: *Bech32Address::try_from_str(address).ok()?.inner(),
+            spent,
+        })
+    }
+
+    /// Whether the output has an unlock condition of the topic with its address.
+    fn matches(&self, output: &Output) -> bool {
+        let Some(unlock_conditions) = output.unlock_conditions() else {
+            return false;
+        };
+
+        unlock_conditions.iter().any(|unlock_condition| {
+            let address = match (self.condition.as_str(), unlock_condition) {
+                ("+" | "address", UnlockCondition::Address(uc)) => uc.address(),
+                ("+" | "storage-return", UnlockCondition::StorageDepositReturn(uc)) => uc.return_address(),
+                ("+" | "expiration", UnlockCondition::Expiration(uc)) => uc.return_address(),
+                ("+" | "state-controller", UnlockCondition::StateControllerAddress(uc)) => uc.address(),
+                ("+" | "governor", UnlockCondition::GovernorAddress(uc)) => uc.address(),
+                ("+" | "immutable-alias", UnlockCondition::ImmutableAliasAddress(uc)) => uc.address(),
+                _ => return false,
+            };
+            *address == self.address
+        })
+    }
+}
+
+/// Returns the subscriptions of the topics, together with the confirmed milestones which are always subscribed to, so
+/// that the index from which missed events are backfilled advances even if no milestone topic is subscribed.
+pub(crate) fn subscribe_filters<'a>(topics: impl IntoIterator<Item = &'a Topic>) -> Vec<SubscribeFilter> {
+    topics
+        .into_iter()
+        .map(Topic::as_str)
+        .chain([CONFIRMED_MILESTONE_INFO_TOPIC])
+        .map(|topic| SubscribeFilter::new(topic.to_owned(), QoS::AtLeastOnce))
+        .collect()
+}
+
+/// Whether the broker subscription of the topic has to be kept while other topics are subscribed.
+pub(crate) fn is_tracking_topic(topic: &Topic) -> bool {
+    topic.as_str() == CONFIRMED_MILESTONE_INFO_TOPIC
+}
+
+/// Holds back a live publish while missed events are backfilled, returns it if it can be delivered right away.
+pub(crate) async fn hold_back_publish(client: &Client, publish: Publish) -> Option<Publish> {
+    match &mut client.mqtt.resynchronization.lock().await.buffered {
+        Some(buffered) => {
+            buffered.push(publish);
+            None
+        }
+        None => Some(publish),
+    }
+}
+
+/// Remembers the index of the confirmed milestones received from the broker, from which missed events are backfilled.
+pub(crate) async fn observe_publish(client: &Client, publish: &Publish) {
+    if publish.topic != CONFIRMED_MILESTONE_INFO_TOPIC {
+        return;
+    }
+
+    match serde_json::from_slice::<LatestMilestoneResponse>(&publish.payload) {
+        Ok(milestone) => advance(client, milestone.index).await,
+        Err(e) => warn!("Can't parse the confirmed milestone to backfill missed events from: {e}"),
+    }
+}
+
+async fn advance(client: &Client, index: u32) {
+    let mut last_milestone_index = client.mqtt.last_milestone_index.write().await;
+    if *last_milestone_index < Some(index) {
+        *last_milestone_index = Some(index);
+    }
+}
+
+/// Remembers the index of the confirmed milestone when subscribing, if no milestone was seen yet.
+pub(crate) async fn observe_subscription(client: &Client) {
+    if client.mqtt.last_milestone_index.read().await.is_some() {
+        return;
+    }
+
+    match client.get_info().await {
+        Ok(info) => {
+            client
+                .mqtt
+                .last_milestone_index
+                .write()
+                .await
+                .get_or_insert(info.node_info.status.confirmed_milestone.index);
+        }
+        Err(e) => warn!("Can't get the confirmed milestone to backfill missed events from: {e}"),
+    }
+}
+
+/// Delivers the events of the milestones confirmed since the last seen one to the subscribed milestone and address
+/// output topics in a separate task, then the live events held back in the meantime, then emits
+/// [`MqttEvent::Resynchronized`], or [`MqttEvent::ResynchronizationFailed`] if not all of them could be delivered.
+pub(crate) async fn spawn_resynchronize(client: &Client) {
+    if !start_resynchronization(client).await {
+        return;
+    }
+
+    let client = client.clone();
+    crate::client::async_runtime::spawn(async move { resynchronize(&client).await });
+}
+
+// Holds back live publishes from now on, returns false if a backfill is already running, which then continues up to
+// the milestone confirmed after this reconnection.
+async fn start_resynchronization(client: &Client) -> bool {
+    let mut resynchronization = client.mqtt.resynchronization.lock().await;
+
+    if resynchronization.buffered.is_some() {
+        resynchronization.restarted = true;
+        false
+    } else {
+        resynchronization.buffered = Some(Vec::new());
+        true
+    }
+}
+
+async fn resynchronize(client: &Client) {
+    let result = loop {
+        let result = backfill(client).await;
+        let mut resynchronization = client.mqtt.resynchronization.lock().await;
+
+        if std::mem::take(&mut resynchronization.restarted) && result.is_ok() {
+            continue;
+        }
+
+        // Delivered while holding the lock, so that newer live publishes don't overtake them.
+        let buffered = resynchronization.buffered.take().unwrap_or_default();
+        deliver_held_back(client, buffered, result.is_ok()).await;
+
+        break result;
+    };
+
+    let event = match result {
+        Ok(()) => MqttEvent::Resynchronized,
+        Err(e) => {
+            warn!("Backfilling missed MQTT events failed: {e}");
+            MqttEvent::ResynchronizationFailed
+        }
+    };
+
+    let _ = client.mqtt.sender.read().await.send(event);
+}
+
+// Delivers the live publishes held back during a backfill, except the ones it delivered already. After a failed
+// backfill, they don't advance the index from which missed events are backfilled, so the next one starts over.
+async fn deliver_held_back(client: &Client, publishes: Vec<Publish>, backfilled: bool) {
+    let last_milestone_index = *client.mqtt.last_milestone_index.read().await;
+
+    for publish in publishes {
+        let Some(event) = decode_event(client, &publish).await else {
+            continue;
+        };
+
+        if milestone_index(&event).is_some_and(|index| Some(index) <= last_milestone_index) {
+            continue;
+        }
+
+        if backfilled {
+            observe_publish(client, &publish).await;
+        }
+
+        let handlers = client
+            .mqtt
+            .topic_handlers
+            .read()
+            .await
+            .get(&Topic::new_unchecked(&publish.topic))
+            .cloned();
+
+        if let Some(handlers) = handlers {
+            dispatch_event(client, event, handlers).await;
+        }
+    }
+}
+
+// Returns the index of the milestone of an event of a backfilled topic, to tell whether the backfill delivered it.
+fn milestone_index(event: &TopicEvent) -> Option<u32> {
+    match &event.payload {
+        MqttPayload::MilestonePayload(milestone) => Some(milestone.index),
+        MqttPayload::Json(value) if event.topic == CONFIRMED_MILESTONE_INFO_TOPIC => {
+            serde_json::from_value::<LatestMilestoneResponse>(value.clone())
+                .ok()
+                .map(|milestone| milestone.index)
+        }
+        MqttPayload::Json(value) => {
+            let topic = AddressOutputsTopic::parse(&Topic::new_unchecked(&event.topic))?;
+            let metadata = serde_json::from_value::<OutputWithMetadataResponse>(value.clone())
+                .ok()?
+                .metadata;
+
+            if topic.spent {
+                metadata.milestone_index_spent()
+            } else {
+                Some(metadata.milestone_index_booked())
+            }
+        }
+        _ => None,
+    }
+}
+
+async fn backfill(client: &Client) -> Result<()> {
+    let Some(last_milestone_index) = *client.mqtt.last_milestone_index.read().await else {
+        return Ok(());
+    };
+    let confirmed_milestone_index = client.get_info().await?.node_info.status.confirmed_milestone.index;
+
+    let (milestone_topics, address_topics) = {
+        let topic_handlers = client.mqtt.topic_handlers.read().await;
+        let milestone_topics = topic_handlers
+            .keys()
+            .filter(|topic| [MILESTONES_TOPIC, CONFIRMED_MILESTONE_INFO_TOPIC].contains(&topic.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let address_topics = topic_handlers
+            .keys()
+            .filter_map(AddressOutputsTopic::parse)
+            .collect::<Vec<_>>();
+        (milestone_topics, address_topics)
+    };
+
+    for index in last_milestone_index + 1..=confirmed_milestone_index {
+        if !milestone_topics.is_empty() {
+            let milestone = client.get_milestone_by_index(index).await?;
+            for topic in &milestone_topics {
+                emit(client, topic, milestone_event(topic, &milestone)?).await;
+            }
+        }
+
+        if !address_topics.is_empty() {
+            let utxo_changes = client.get_utxo_changes_by_index(index).await?;
+            for (output_ids, spent) in [
+                (&utxo_changes.created_outputs, false),
+                (&utxo_changes.consumed_outputs, true),
+            ] {
+                emit_address_outputs(client, output_ids, address_topics.iter().filter(|t| t.spent == spent)).await?;
+            }
+        }
+
+        advance(client, index).await;
+    }
+
+    Ok(())
+}
+
+fn milestone_event(topic: &Topic, milestone: &MilestonePayload) -> Result<MqttPayload> {
+    Ok(if topic.as_str() == MILESTONES_TOPIC {
+        MqttPayload::MilestonePayload(milestone.into())
+    } else {
+        MqttPayload::Json(serde_json::to_value(LatestMilestoneResponse {
+            index: *milestone.essence().index(),
+            timestamp: Some(milestone.essence().timestamp()),
+            milestone_id: Some(milestone.id()),
+        })?)
+    })
+}
+
+async fn emit_address_outputs<'a>(
+    client: &Client,
+    output_ids: &[OutputId],
+    topics: impl Iterator<Item = &'a AddressOutputsTopic>,
+) -> Result<()> {
+    let topics = topics.collect::<Vec<_>>();
+    if topics.is_empty() || output_ids.is_empty() {
+        return Ok(());
+    }
+
+    // Consumed outputs may be pruned already, in which case their events can't be backfilled.
+    for output in client.get_outputs_ignore_errors(output_ids).await? {
+        for topic in topics.iter().filter(|topic| topic.matches(output.output())) {
+            let payload = MqttPayload::Json(serde_json::to_value(OutputWithMetadataResponse::from(&output))?);
+            emit(client, &topic.topic, payload).await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn emit(client: &Client, topic: &Topic, payload: MqttPayload) {
+    let handlers = client.mqtt.topic_handlers.read().await.get(topic).cloned();
+
+    if let Some(handlers) = handlers {
+        let event = TopicEvent {
+            topic: topic.as_str().to_owned(),
+            payload,
+        };
+        dispatch_event(client, event, handlers).await;
+    }
+}
+
+#[cfg(all(test, feature = "mock_node"))]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{
+        client::{
+            mock_node::MockNode,
+            mqtt::FromTopicEvent,
+            node_api::mqtt::{StreamSender, TopicHandler},
+        },
+        types::block::{address::Ed25519Address, output::OutputWithMetadata},
+    };
+
+    #[tokio::test]
+    async fn backfill_missed_events() {
+        let node = MockNode::builder().finish().await.unwrap();
+        let client = Client::builder().with_node(node.url()).unwrap().finish().await.unwrap();
+        let address = Address::from(Ed25519Address::new([1; 32]));
+        let bech32_address = Bech32Address::new(client.get_bech32_hrp().await.unwrap(), address);
+
+        // Subscribing to topics without a broker, as if the connection dropped right after.
+        let (milestones_sender, mut milestones) = mpsc::channel(10);
+        let (outputs_sender, mut outputs) = mpsc::channel(10);
+        client.mqtt.topic_handlers.write().await.extend([
+            (
+                Topic::new(MILESTONES_TOPIC).unwrap(),
+                vec![Arc::new(TopicHandler::Stream(StreamSender::new(milestones_sender)))],
+            ),
+            (
+                Topic::new(format!("outputs/unlock/address/{bech32_address}")).unwrap(),
+                vec![Arc::new(TopicHandler::Stream(StreamSender::new(outputs_sender)))],
+            ),
+        ]);
+        observe_subscription(&client).await;
+        assert_eq!(*client.mqtt.last_milestone_index.read().await, Some(1));
+
+        let output_id = node.fund(address, 1_000_000).await.unwrap();
+        node.issue_milestone().await;
+
+        let mut mqtt_events = client.mqtt_event_receiver().await;
+        resynchronize(&client).await;
+        assert_eq!(*mqtt_events.borrow_and_update(), MqttEvent::Resynchronized);
+        assert_eq!(*client.mqtt.last_milestone_index.read().await, Some(3));
+
+        for index in [2, 3] {
+            let (_, event) = milestones.try_recv().unwrap();
+            assert!(matches!(event.payload, MqttPayload::MilestonePayload(milestone) if milestone.index == index));
+        }
+        assert!(milestones.try_recv().is_err());
+
+        let output =
+            OutputWithMetadata::from_topic_event(outputs.try_recv().unwrap().1, &node.protocol_parameters().await)
+                .unwrap();
+        assert_eq!(output.metadata().output_id(), &output_id);
+        assert!(outputs.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn live_events_during_backfill() {
+        let node = MockNode::builder().finish().await.unwrap();
+        let client = Client::builder().with_node(node.url()).unwrap().finish().await.unwrap();
+
+        let (sender, mut milestones) = mpsc::channel(10);
+        client.mqtt.topic_handlers.write().await.insert(
+            Topic::new(CONFIRMED_MILESTONE_INFO_TOPIC).unwrap(),
+            vec![Arc::new(TopicHandler::Stream(StreamSender::new(sender)))],
+        );
+        observe_subscription(&client).await;
+        node.issue_milestone().await;
+
+        // Milestone 2 was missed, 2 and 3 are received live while backfilling.
+        assert!(start_resynchronization(&client).await);
+        for index in [2, 3] {
+            let payload = serde_json::to_vec(&LatestMilestoneResponse {
+                index,
+                timestamp: None,
+                milestone_id: None,
+            })
+            .unwrap();
+            let publish = Publish::new(CONFIRMED_MILESTONE_INFO_TOPIC, QoS::AtLeastOnce, payload);
+            assert!(hold_back_publish(&client, publish).await.is_none());
+        }
+        assert_eq!(*client.mqtt.last_milestone_index.read().await, Some(1));
+
+        resynchronize(&client).await;
+        assert_eq!(*client.mqtt.last_milestone_index.read().await, Some(3));
+
+        for index in [2, 3] {
+            let (_, event) = milestones.try_recv().unwrap();
+            assert!(matches!(event.payload, MqttPayload::Json(value) if value["index"] == index));
+        }
+        assert!(milestones.try_recv().is_err());
+
+        // Live events are delivered right away again.
+        let publish = Publish::new(CONFIRMED_MILESTONE_INFO_TOPIC, QoS::AtLeastOnce, "{}");
+        assert!(hold_back_publish(&client, publish).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn backfill_failure() {
+        let node = MockNode::builder().finish().await.unwrap();
+        let client = Client::builder().with_node(node.url()).unwrap().finish().await.unwrap();
+
+        let (sender, _milestones) = mpsc::channel(10);
+        client.mqtt.topic_handlers.write().await.insert(
+            Topic::new(MILESTONES_TOPIC).unwrap(),
+            vec![Arc::new(TopicHandler::Stream(StreamSender::new(sender)))],
+        );
+        observe_subscription(&client).await;
+        drop(node);
+
+        let mut mqtt_events = client.mqtt_event_receiver().await;
+        resynchronize(&client).await;
+        assert_eq!(*mqtt_events.borrow_and_update(), MqttEvent::ResynchronizationFailed);
+        assert_eq!(*client.mqtt.last_milestone_index.read().await, Some(1));
+    }
+}
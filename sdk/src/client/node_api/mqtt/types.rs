@@ -3,19 +3,75 @@
 
 //! MQTT types
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use regex::RegexSet;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
+use tokio::sync::mpsc::{error::TrySendError, Sender};
 
 use super::Error;
-use crate::types::block::{
-    payload::{dto::MilestonePayloadDto, milestone::option::dto::ReceiptMilestoneOptionDto},
-    BlockDto,
+use crate::types::{
+    api::core::response::{BlockMetadataResponse, OutputWithMetadataResponse},
+    block::{
+        output::{Output, OutputWithMetadata},
+        payload::{
+            dto::MilestonePayloadDto,
+            milestone::{option::dto::ReceiptMilestoneOptionDto, MilestonePayload},
+        },
+        protocol::ProtocolParameters,
+        Block, BlockDto,
+    },
+    TryFromDto,
 };
 
-type TopicHandler = Box<dyn Fn(&TopicEvent) + Send + Sync>;
+/// A subscriber of a topic.
+pub(crate) enum TopicHandler {
+    /// A callback, called for every event.
+    Callback(Box<dyn Fn(&TopicEvent) + Send + Sync>),
+    /// The sending half of a stream, see [`Client::subscribe_stream()`](crate::client::Client::subscribe_stream).
+    Stream(StreamSender),
+}
+
+/// Sends events to a stream without waiting for it to be polled. Events that don't fit in the buffer of the stream
+/// are dropped, their number is sent along with the next event.
+pub(crate) struct StreamSender {
+    sender: Sender<(u64, TopicEvent)>,
+    dropped: AtomicU64,
+}
+
+impl StreamSender {
+    pub(crate) fn new(sender: Sender<(u64, TopicEvent)>) -> Self {
+        Self {
+            sender,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Sends an event, returns false if the stream was dropped.
+    pub(crate) fn send(&self, event: TopicEvent) -> bool {
+        let dropped = self.dropped.load(Ordering::Relaxed);
+
+        match self.sender.try_send((dropped, event)) {
+            Ok(()) => {
+                self.dropped.fetch_sub(dropped, Ordering::Relaxed);
+                true
+            }
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+}
 
 pub(crate) type TopicHandlerMap = HashMap<Topic, Vec<Arc<TopicHandler>>>;
 
@@ -44,6 +100,60 @@ pub enum MqttPayload {
     Receipt(ReceiptMilestoneOptionDto),
 }
 
+/// A type the events of a topic can be decoded into, see
+/// [`Client::subscribe_stream()`](crate::client::Client::subscribe_stream).
+pub trait FromTopicEvent: Sized {
+    /// Decodes an event, using the protocol parameters to verify it.
+    fn from_topic_event(event: TopicEvent, protocol_parameters: &ProtocolParameters) -> Result<Self, Error>;
+}
+
+impl FromTopicEvent for TopicEvent {
+    fn from_topic_event(event: TopicEvent, _: &ProtocolParameters) -> Result<Self, Error> {
+        Ok(event)
+    }
+}
+
+impl FromTopicEvent for Block {
+    fn from_topic_event(event: TopicEvent, protocol_parameters: &ProtocolParameters) -> Result<Self, Error> {
+        match event.payload {
+            MqttPayload::Block(dto) => Ok(Self::try_from_dto_with_params(dto, protocol_parameters)?),
+            _ => Err(Error::UnexpectedPayload(event.topic)),
+        }
+    }
+}
+
+impl FromTopicEvent for MilestonePayload {
+    fn from_topic_event(event: TopicEvent, protocol_parameters: &ProtocolParameters) -> Result<Self, Error> {
+        match event.payload {
+            MqttPayload::MilestonePayload(dto) => Ok(Self::try_from_dto_with_params(dto, protocol_parameters)?),
+            _ => Err(Error::UnexpectedPayload(event.topic)),
+        }
+    }
+}
+
+impl FromTopicEvent for OutputWithMetadata {
+    fn from_topic_event(event: TopicEvent, protocol_parameters: &ProtocolParameters) -> Result<Self, Error> {
+        match event.payload {
+            MqttPayload::Json(value) => {
+                let response = serde_json::from_value::<OutputWithMetadataResponse>(value)?;
+                let output = Output::try_from_dto_with_params(response.output, protocol_parameters)?;
+
+                Ok(Self::new(output, response.metadata))
+            }
+            _ => Err(Error::UnexpectedPayload(event.topic)),
+        }
+    }
+}
+
+impl FromTopicEvent for BlockMetadataResponse {
+    fn from_topic_event(event: TopicEvent, _: &ProtocolParameters) -> Result<Self, Error> {
+        match event.payload {
+            MqttPayload::Json(value) => Ok(serde_json::from_value(value)?),
+            _ => Err(Error::UnexpectedPayload(event.topic)),
+        }
+    }
+}
+
 /// Mqtt events.
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -52,6 +162,11 @@ pub enum MqttEvent {
     Connected,
     /// Client was disconnected.
     Disconnected,
+    /// Client was reconnected and the milestone and address output events missed in the meantime were delivered.
+    Resynchronized,
+    /// Client was reconnected but not all the milestone and address output events missed in the meantime could be
+    /// delivered.
+    ResynchronizationFailed,
 }
 
 /// The MQTT broker options.
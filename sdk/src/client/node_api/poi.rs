@@ -0,0 +1,47 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! IOTA node proof of inclusion routes.
+//! <https://github.com/iotaledger/inx-poi/blob/develop/components/poi/routes.go>
+
+use crate::{
+    client::{ClientInner, Error, Result},
+    types::{
+        block::{
+            payload::milestone::{dto::InclusionProofDto, InclusionProof},
+            BlockId,
+        },
+        TryFromDto,
+    },
+};
+
+impl ClientInner {
+    /// Returns the proof that a block was included in the milestone referencing it.
+    /// The proof is verified like in [`ClientInner::verify_inclusion_proof()`].
+    /// GET /api/poi/v1/create/{blockId}
+    pub async fn get_inclusion_proof(&self, block_id: &BlockId) -> Result<InclusionProof> {
+        let path = &format!("api/poi/v1/create/{block_id}");
+
+        let dto = self
+            .get_request_cached::<InclusionProofDto>(path, false, false, |_| true)
+            .await?;
+        let proof = InclusionProof::try_from_dto_with_params(dto, self.get_protocol_parameters().await?)?;
+
+        if proof.block().id() != *block_id {
+            return Err(Error::InvalidInclusionProof(*block_id));
+        }
+        self.verify_inclusion_proof(&proof).await?;
+
+        Ok(proof)
+    }
+
+    /// Verifies that an inclusion proof leads to the inclusion Merkle root of its milestone, and verifies the milestone
+    /// if milestone verification is enabled.
+    pub async fn verify_inclusion_proof(&self, proof: &InclusionProof) -> Result<()> {
+        if !proof.verify() {
+            return Err(Error::InvalidInclusionProof(proof.block().id()));
+        }
+
+        self.verify_milestone(proof.milestone()).await
+    }
+}
@@ -19,6 +19,9 @@ use crate::{
         node_manager::{
             http_client::HttpClient,
             node::{Node, NodeAuth, NodeDto},
+            retry::RetryPolicy,
+            statistics::CircuitBreakerOptions,
+            transport::SharedTransport,
             NodeManager,
         },
     },
@@ -58,9 +61,18 @@ pub struct NodeManagerBuilder {
     /// % of nodes that have to return the same response so it gets accepted
     #[serde(default = "default_quorum_threshold")]
     pub quorum_threshold: usize,
+    /// Options of the circuit breaker that leaves out nodes after repeated failures
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerOptions,
+    /// How requests are retried when all nodes failed with a transient error
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
     /// The User-Agent header for requests
     #[serde(default = "default_user_agent")]
     pub user_agent: String,
+    /// The transport requests are sent with, reqwest if not set
+    #[serde(skip)]
+    pub(crate) transport: Option<SharedTransport>,
 }
 
 fn default_user_agent() -> String {
@@ -206,11 +218,26 @@ impl NodeManagerBuilder {
         self
     }
 
+    pub(crate) fn with_circuit_breaker(mut self, options: CircuitBreakerOptions) -> Self {
+        self.circuit_breaker = options;
+        self
+    }
+
+    pub(crate) fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     pub(crate) fn with_user_agent(mut self, user_agent: String) -> Self {
         self.user_agent = user_agent;
         self
     }
 
+    pub(crate) fn with_transport(mut self, transport: SharedTransport) -> Self {
+        self.transport.replace(transport);
+        self
+    }
+
     pub(crate) fn build(self, healthy_nodes: HashMap<Node, InfoResponse>) -> NodeManager {
         NodeManager {
             primary_node: self.primary_node.map(Into::into),
@@ -223,7 +250,10 @@ impl NodeManagerBuilder {
             quorum: self.quorum,
             min_quorum_size: self.min_quorum_size,
             quorum_threshold: self.quorum_threshold,
-            http_client: HttpClient::new(self.user_agent),
+            retry_policy: self.retry_policy,
+            http_client: HttpClient::new(self.user_agent)
+                .with_transport(self.transport)
+                .with_circuit_breaker(self.circuit_breaker),
         }
     }
 }
@@ -240,7 +270,10 @@ impl Default for NodeManagerBuilder {
             quorum: false,
             min_quorum_size: DEFAULT_MIN_QUORUM_SIZE,
             quorum_threshold: DEFAULT_QUORUM_THRESHOLD,
+            circuit_breaker: CircuitBreakerOptions::default(),
+            retry_policy: RetryPolicy::default(),
             user_agent: DEFAULT_USER_AGENT.to_string(),
+            transport: None,
         }
     }
 }
@@ -265,7 +298,10 @@ impl From<&NodeManager> for NodeManagerBuilder {
             quorum: value.quorum,
             min_quorum_size: value.min_quorum_size,
             quorum_threshold: value.quorum_threshold,
+            circuit_breaker: value.http_client.statistics.options(),
+            retry_policy: value.retry_policy.clone(),
             user_agent: value.http_client.user_agent.clone(),
+            transport: value.http_client.transport.clone(),
         }
     }
 }
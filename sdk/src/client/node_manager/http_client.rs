@@ -3,57 +3,75 @@
 
 //! The node manager that takes care of sending requests with healthy nodes and quorum if enabled
 
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
-use reqwest::RequestBuilder;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use url::Url;
 
 use crate::client::{
     node_api::error::{Error, Result},
-    node_manager::node::Node,
+    node_manager::{
+        node::Node,
+        statistics::{CircuitBreakerOptions, NodeStatisticsMap},
+        transport::{HttpMethod, HttpRequest, HttpResponse, ReqwestTransport, SharedTransport, Transport},
+    },
 };
-pub(crate) struct Response(reqwest::Response);
+pub(crate) struct Response(HttpResponse);
 
 impl Response {
     pub(crate) fn status(&self) -> u16 {
-        self.0.status().as_u16()
+        self.0.status
     }
 
     pub(crate) async fn into_json<T: DeserializeOwned>(self) -> Result<T> {
-        self.0.json().await.map_err(Into::into)
+        serde_json::from_slice(&self.0.body).map_err(Into::into)
     }
 
     #[cfg(not(target_family = "wasm"))]
     pub(crate) async fn into_text(self) -> Result<String> {
-        self.0.text().await.map_err(Into::into)
+        Ok(String::from_utf8_lossy(&self.0.body).into_owned())
     }
 
     pub(crate) async fn into_bytes(self) -> Result<Vec<u8>> {
-        self.0.bytes().await.map(|b| b.to_vec()).map_err(Into::into)
+        Ok(self.0.body)
     }
 }
 
 #[derive(Clone)]
 pub(crate) struct HttpClient {
-    client: reqwest::Client,
+    reqwest: ReqwestTransport,
+    pub(crate) transport: Option<SharedTransport>,
+    pub(crate) statistics: Arc<NodeStatisticsMap>,
     pub(crate) user_agent: String,
 }
 
 impl HttpClient {
     pub(crate) fn new(user_agent: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            reqwest: ReqwestTransport::new(),
+            transport: None,
+            statistics: Default::default(),
             user_agent,
         }
     }
 
-    async fn parse_response(response: reqwest::Response, url: &url::Url) -> Result<Response> {
-        let status = response.status();
-        if status.is_success() {
+    pub(crate) fn with_transport(mut self, transport: Option<SharedTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub(crate) fn with_circuit_breaker(mut self, options: CircuitBreakerOptions) -> Self {
+        self.statistics = Arc::new(NodeStatisticsMap::new(options));
+        self
+    }
+
+    async fn parse_response(response: HttpResponse, url: &url::Url) -> Result<Response> {
+        let status = response.status;
+        if (200..300).contains(&status) {
             Ok(Response(response))
         } else {
-            let text = response.text().await?;
+            let text = String::from_utf8_lossy(&response.body).into_owned();
             // Different urls, nodes and versions give different replies
             if text == *"no available nodes with remote Pow"
                 || text.contains("proof of work is not available on this node")
@@ -61,11 +79,11 @@ impl HttpClient {
                 || text.contains("`Pow` not enabled")
             {
                 Err(Error::UnavailablePow)
-            } else if status.as_u16() == 404 {
+            } else if status == 404 {
                 Err(Error::NotFound(url.to_string()))
             } else {
                 Err(Error::ResponseError {
-                    code: status.as_u16(),
+                    code: status,
                     text,
                     url: url.to_string(),
                 })
@@ -73,54 +91,79 @@ impl HttpClient {
         }
     }
 
-    fn build_request(&self, request_builder: RequestBuilder, node: &Node, _timeout: Duration) -> RequestBuilder {
-        let mut request_builder = request_builder.header(reqwest::header::USER_AGENT, &self.user_agent);
+    fn build_request(&self, method: HttpMethod, node: &Node, url: Url, timeout: Duration) -> HttpRequest {
+        let mut headers = vec![("user-agent".to_string(), self.user_agent.clone())];
 
         if let Some(node_auth) = &node.auth {
             if let Some(jwt) = &node_auth.jwt {
-                request_builder = request_builder.bearer_auth(jwt);
+                headers.push(("authorization".to_string(), format!("Bearer {jwt}")));
             }
         }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            request_builder = request_builder.timeout(_timeout);
+
+        HttpRequest {
+            method,
+            url,
+            headers,
+            body: None,
+            timeout,
         }
-        request_builder
     }
 
-    pub(crate) async fn get(&self, node: Node, timeout: Duration) -> Result<Response> {
-        let mut request_builder = self.client.get(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
+    async fn send(&self, node: &Node, request: HttpRequest) -> Result<Response> {
+        let url = request.url.clone();
+        let start_time = instant::Instant::now();
+        let response = match &self.transport {
+            Some(transport) => transport.0.send(request).await,
+            None => self.reqwest.send(request).await,
+        };
+        let response = match response {
+            Ok(response) => Self::parse_response(response, &url).await,
+            Err(e) => Err(e),
+        };
+        self.statistics.record(&node.url, start_time.elapsed(), &response);
+        response
+    }
+
+    pub(crate) async fn get(&self, node: &Node, url: Url, timeout: Duration) -> Result<Response> {
+        let request = self.build_request(HttpMethod::Get, node, url, timeout);
+        let url = request.url.clone();
         let start_time = instant::Instant::now();
-        let resp = request_builder.send().await?;
+        let resp = self.send(node, request).await;
         log::debug!(
             "GET: {:?} ms for {} {}",
             start_time.elapsed().as_millis(),
-            resp.status(),
-            node.url
+            resp.as_ref()
+                .map_or_else(ToString::to_string, |resp| resp.status().to_string()),
+            url
         );
-        Self::parse_response(resp, &node.url).await
+        resp
     }
 
     // Get with header: "accept", "application/vnd.iota.serializer-v1"
-    pub(crate) async fn get_bytes(&self, node: Node, timeout: Duration) -> Result<Response> {
-        let mut request_builder = self.client.get(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
-        request_builder = request_builder.header("accept", "application/vnd.iota.serializer-v1");
-        let resp = request_builder.send().await?;
-        Self::parse_response(resp, &node.url).await
+    pub(crate) async fn get_bytes(&self, node: &Node, url: Url, timeout: Duration) -> Result<Response> {
+        let mut request = self.build_request(HttpMethod::Get, node, url, timeout);
+        request
+            .headers
+            .push(("accept".to_string(), "application/vnd.iota.serializer-v1".to_string()));
+        self.send(node, request).await
     }
 
-    pub(crate) async fn post_json(&self, node: Node, timeout: Duration, json: Value) -> Result<Response> {
-        let mut request_builder = self.client.post(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
-        Self::parse_response(request_builder.json(&json).send().await?, &node.url).await
+    pub(crate) async fn post_json(&self, node: &Node, url: Url, timeout: Duration, json: Value) -> Result<Response> {
+        let mut request = self.build_request(HttpMethod::Post, node, url, timeout);
+        request
+            .headers
+            .push(("content-type".to_string(), "application/json".to_string()));
+        request.body = Some(serde_json::to_vec(&json)?);
+        self.send(node, request).await
     }
 
-    pub(crate) async fn post_bytes(&self, node: Node, timeout: Duration, body: &[u8]) -> Result<Response> {
-        let mut request_builder = self.client.post(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
-        request_builder = request_builder.header("Content-Type", "application/vnd.iota.serializer-v1");
-        Self::parse_response(request_builder.body(body.to_vec()).send().await?, &node.url).await
+    pub(crate) async fn post_bytes(&self, node: &Node, url: Url, timeout: Duration, body: &[u8]) -> Result<Response> {
+        let mut request = self.build_request(HttpMethod::Post, node, url, timeout);
+        request.headers.push((
+            "content-type".to_string(),
+            "application/vnd.iota.serializer-v1".to_string(),
+        ));
+        request.body = Some(body.to_vec());
+        self.send(node, request).await
     }
 }
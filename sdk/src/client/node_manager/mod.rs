@@ -7,7 +7,10 @@ pub mod builder;
 pub(crate) mod http_client;
 /// Structs for nodes
 pub mod node;
+pub mod retry;
+pub mod statistics;
 pub(crate) mod syncing;
+pub mod transport;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -18,8 +21,9 @@ use std::{
 
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
+use url::Url;
 
-use self::{http_client::HttpClient, node::Node};
+use self::{http_client::HttpClient, node::Node, retry::RetryPolicy};
 use super::ClientInner;
 #[cfg(not(target_family = "wasm"))]
 use crate::client::request_pool::RateLimitExt;
@@ -44,6 +48,7 @@ pub struct NodeManager {
     quorum: bool,
     min_quorum_size: usize,
     quorum_threshold: usize,
+    retry_policy: RetryPolicy,
     pub(crate) http_client: HttpClient,
 }
 
@@ -59,7 +64,8 @@ impl Debug for NodeManager {
         d.field("healthy_nodes", &self.healthy_nodes);
         d.field("quorum", &self.quorum);
         d.field("min_quorum_size", &self.min_quorum_size);
-        d.field("quorum_threshold", &self.quorum_threshold).finish()
+        d.field("quorum_threshold", &self.quorum_threshold);
+        d.field("retry_policy", &self.retry_policy).finish()
     }
 }
 
@@ -93,7 +99,8 @@ impl ClientInner {
         local_pow: bool,
     ) -> Result<T> {
         let node_manager = self.node_manager.read().await;
-        let request = node_manager.post_request_json(path, self.get_timeout().await, json, local_pow);
+        // Plugin routes may not be idempotent
+        let request = node_manager.post_request_json(path, self.get_timeout().await, json, local_pow, false);
         #[cfg(not(target_family = "wasm"))]
         let request = request.rate_limit(&self.request_pool);
         request.await
@@ -111,7 +118,7 @@ impl NodeManager {
         query: Option<&str>,
         use_pow_nodes: bool,
         prefer_permanode: bool,
-    ) -> Result<Vec<Node>> {
+    ) -> Result<Vec<(Node, Url)>> {
         let mut nodes_with_modified_url: Vec<Node> = Vec::new();
 
         if prefer_permanode || (path == "api/core/v2/blocks" && query.is_some()) {
@@ -168,8 +175,10 @@ impl NodeManager {
             self.nodes.clone()
         };
 
-        // Add remaining nodes in random order
-        for node in nodes_random_order {
+        // Add remaining nodes, the fastest ones first
+        let mut remaining_nodes = nodes_random_order.into_iter().collect::<Vec<_>>();
+        remaining_nodes.sort_by_cached_key(|node| self.http_client.statistics.rank(&node.url));
+        for node in remaining_nodes {
             if !nodes_with_modified_url.iter().any(|n| n.url == node.url) {
                 nodes_with_modified_url.push(node);
             }
@@ -178,6 +187,14 @@ impl NodeManager {
         // remove disabled nodes
         nodes_with_modified_url.retain(|n| !n.disabled);
 
+        // remove nodes whose circuit breaker tripped, unless there are no others
+        if nodes_with_modified_url
+            .iter()
+            .any(|n| !self.http_client.statistics.is_tripped(&n.url))
+        {
+            nodes_with_modified_url.retain(|n| !self.http_client.statistics.is_tripped(&n.url));
+        }
+
         if nodes_with_modified_url.is_empty() {
             if use_pow_nodes {
                 return Err(crate::client::Error::Node(
@@ -187,23 +204,24 @@ impl NodeManager {
             return Err(crate::client::Error::HealthyNodePoolEmpty);
         }
 
-        // Set path and query parameters
-        for node in &mut nodes_with_modified_url {
-            node.url.set_path(path);
-            node.url.set_query(query);
-            if let Some(auth) = &node.auth {
-                if let Some((name, password)) = &auth.basic_auth_name_pwd {
-                    node.url
-                        .set_username(name)
-                        .map_err(|_| crate::client::Error::UrlAuth("username"))?;
-                    node.url
-                        .set_password(Some(password))
-                        .map_err(|_| crate::client::Error::UrlAuth("password"))?;
+        // Set path and query parameters, the node keeps its url for the statistics
+        nodes_with_modified_url
+            .into_iter()
+            .map(|node| {
+                let mut url = node.url.clone();
+                url.set_path(path);
+                url.set_query(query);
+                if let Some(auth) = &node.auth {
+                    if let Some((name, password)) = &auth.basic_auth_name_pwd {
+                        url.set_username(name)
+                            .map_err(|_| crate::client::Error::UrlAuth("username"))?;
+                        url.set_password(Some(password))
+                            .map_err(|_| crate::client::Error::UrlAuth("password"))?;
+                    }
                 }
-            }
-        }
-
-        Ok(nodes_with_modified_url)
+                Ok((node, url))
+            })
+            .collect()
     }
 
     pub(crate) async fn get_request<T: DeserializeOwned + Debug + Serialize>(
@@ -213,6 +231,21 @@ impl NodeManager {
         timeout: Duration,
         need_quorum: bool,
         prefer_permanode: bool,
+    ) -> Result<T> {
+        self.retry_policy
+            .retry(true, || {
+                self.get_request_once(path, query, timeout, need_quorum, prefer_permanode)
+            })
+            .await
+    }
+
+    async fn get_request_once<T: DeserializeOwned + Debug + Serialize>(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        timeout: Duration,
+        need_quorum: bool,
+        prefer_permanode: bool,
     ) -> Result<T> {
         let mut result: HashMap<String, usize> = HashMap::new();
         // primary_pow_node should only be used for post request with remote PoW
@@ -237,10 +270,12 @@ impl NodeManager {
             #[cfg(not(target_family = "wasm"))]
             {
                 let mut tasks = Vec::new();
-                for (index, node) in nodes.into_iter().enumerate() {
+                for (index, (node, url)) in nodes.into_iter().enumerate() {
                     if index < self.min_quorum_size {
                         let client_ = self.http_client.clone();
-                        tasks.push(async move { tokio::spawn(async move { client_.get(node, timeout).await }).await });
+                        tasks.push(
+                            async move { tokio::spawn(async move { client_.get(&node, url, timeout).await }).await },
+                        );
                     }
                 }
                 for res in futures::future::try_join_all(tasks).await? {
@@ -263,8 +298,8 @@ impl NodeManager {
             }
         } else {
             // Send requests
-            for node in nodes {
-                match self.http_client.get(node.clone(), timeout).await {
+            for (node, url) in nodes {
+                match self.http_client.get(&node, url, timeout).await {
                     Ok(res) => {
                         // Handle node_info extra because we also want to return the url
                         if path == crate::client::node_api::core::routes::INFO_PATH {
@@ -331,13 +366,19 @@ impl NodeManager {
         query: Option<&str>,
         timeout: Duration,
     ) -> Result<Vec<u8>> {
+        self.retry_policy
+            .retry(true, || self.get_request_bytes_once(path, query, timeout))
+            .await
+    }
+
+    async fn get_request_bytes_once(&self, path: &str, query: Option<&str>, timeout: Duration) -> Result<Vec<u8>> {
         // primary_pow_node should only be used for post request with remote Pow
         // Get node urls and set path
         let nodes = self.get_nodes(path, query, false, false)?;
         let mut error = None;
         // Send requests
-        for node in nodes {
-            match self.http_client.get_bytes(node, timeout).await {
+        for (node, url) in nodes {
+            match self.http_client.get_bytes(&node, url, timeout).await {
                 Ok(res) => {
                     match res.into_bytes().await {
                         Ok(res_text) => return Ok(res_text),
@@ -360,13 +401,26 @@ impl NodeManager {
         timeout: Duration,
         body: &[u8],
         local_pow: bool,
+    ) -> Result<T> {
+        // Only used to submit blocks, which can safely be submitted again
+        self.retry_policy
+            .retry(true, || self.post_request_bytes_once(path, timeout, body, local_pow))
+            .await
+    }
+
+    async fn post_request_bytes_once<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        timeout: Duration,
+        body: &[u8],
+        local_pow: bool,
     ) -> Result<T> {
         // primary_pow_node should only be used for post request with remote PoW
         let nodes = self.get_nodes(path, None, !local_pow, false)?;
         let mut error = None;
         // Send requests
-        for node in nodes {
-            match self.http_client.post_bytes(node, timeout, body).await {
+        for (node, url) in nodes {
+            match self.http_client.post_bytes(&node, url, timeout, body).await {
                 Ok(res) => {
                     match res.into_json::<T>().await {
                         Ok(res) => return Ok(res),
@@ -389,13 +443,28 @@ impl NodeManager {
         timeout: Duration,
         json: Value,
         local_pow: bool,
+        idempotent: bool,
+    ) -> Result<T> {
+        self.retry_policy
+            .retry(idempotent, || {
+                self.post_request_json_once(path, timeout, &json, local_pow)
+            })
+            .await
+    }
+
+    async fn post_request_json_once<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        timeout: Duration,
+        json: &Value,
+        local_pow: bool,
     ) -> Result<T> {
         // primary_pow_node should only be used for post request with remote PoW
         let nodes = self.get_nodes(path, None, !local_pow, false)?;
         let mut error = None;
         // Send requests
-        for node in nodes {
-            match self.http_client.post_json(node, timeout, json.clone()).await {
+        for (node, url) in nodes {
+            match self.http_client.post_json(&node, url, timeout, json.clone()).await {
                 Ok(res) => {
                     match res.into_json::<T>().await {
                         Ok(res) => return Ok(res),
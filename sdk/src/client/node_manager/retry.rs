@@ -0,0 +1,152 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retrying of requests that failed because of transient node errors.
+
+use std::{future::Future, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{
+    constants::{DEFAULT_RETRY_INITIAL_BACKOFF, DEFAULT_RETRY_MAX_ATTEMPTS, DEFAULT_RETRY_MAX_BACKOFF},
+    node_api::error::Error as NodeApiError,
+    Error, Result,
+};
+
+/// How requests to nodes are retried when all nodes failed with a transient error.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryPolicy {
+    /// Maximum amount of attempts of a request, 1 to never retry
+    pub max_attempts: u32,
+    /// Backoff before the first retry, doubled for every further retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the backoff
+    pub max_backoff: Duration,
+    /// Whether the backoff is randomized between half and the full backoff, so that clients don't retry in lockstep
+    pub jitter: bool,
+    /// Status codes of node responses after which a request is retried
+    pub retryable_status_codes: Vec<u16>,
+    /// Whether requests that couldn't be sent or timed out are retried
+    pub retry_transport_errors: bool,
+    /// Whether only POST requests that can safely be sent again, like block submissions, are retried
+    pub idempotent_only: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RETRY_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_RETRY_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_RETRY_MAX_BACKOFF,
+            jitter: true,
+            retryable_status_codes: vec![429, 500, 502, 503, 504],
+            retry_transport_errors: true,
+            idempotent_only: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum amount of attempts of a request.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the backoff before the first retry and its upper bound.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets whether the backoff is randomized.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the status codes after which a request is retried.
+    pub fn with_retryable_status_codes(mut self, status_codes: impl Into<Vec<u16>>) -> Self {
+        self.retryable_status_codes = status_codes.into();
+        self
+    }
+
+    /// Sets whether requests that couldn't be sent or timed out are retried.
+    pub fn with_retry_transport_errors(mut self, retry_transport_errors: bool) -> Self {
+        self.retry_transport_errors = retry_transport_errors;
+        self
+    }
+
+    /// Sets whether only idempotent POST requests are retried.
+    pub fn with_idempotent_only(mut self, idempotent_only: bool) -> Self {
+        self.idempotent_only = idempotent_only;
+        self
+    }
+
+    /// Returns whether a request that failed with the given error should be retried.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error {
+            Error::Node(NodeApiError::ResponseError { code, .. }) => self.retryable_status_codes.contains(code),
+            Error::Node(NodeApiError::Reqwest(_) | NodeApiError::Transport(_)) => self.retry_transport_errors,
+            _ => false,
+        }
+    }
+
+    /// Returns the backoff before the given retry, starting at 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let mut random = [0; 4];
+            if crypto::utils::rand::fill(&mut random).is_ok() {
+                let factor = f64::from(u32::from_le_bytes(random)) / f64::from(u32::MAX);
+                return backoff / 2 + (backoff / 2).mul_f64(factor);
+            }
+        }
+
+        backoff
+    }
+
+    /// Sends a request until it succeeds, fails with an error that isn't retryable or runs out of attempts.
+    pub(crate) async fn retry<T, F, Fut>(&self, idempotent: bool, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let max_attempts = if idempotent || !self.idempotent_only {
+            self.max_attempts.max(1)
+        } else {
+            1
+        };
+        let mut attempt = 1;
+
+        loop {
+            let error = match request().await {
+                Err(e) if attempt < max_attempts && self.is_retryable(&e) => e,
+                res => return res,
+            };
+            let backoff = self.backoff(attempt);
+            log::debug!("[retry] attempt {attempt} failed with {error}, retrying in {backoff:?}");
+
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(backoff).await;
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(backoff.as_millis().try_into().unwrap_or(u32::MAX)).await;
+
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Request statistics of nodes, used to prefer fast nodes and to trip the circuit breaker of failing ones.
+
+use std::{collections::HashMap, sync::RwLock, time::Duration};
+
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::client::{
+    constants::{DEFAULT_NODE_COOL_DOWN, DEFAULT_NODE_FAILURE_THRESHOLD},
+    node_api::error::Error,
+    ClientInner,
+};
+
+/// Weight of the latest request in the rolling average latency of a node.
+const LATENCY_WEIGHT: f64 = 0.2;
+
+/// Options of the circuit breaker that leaves out failing nodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerOptions {
+    /// Amount of consecutive failed requests after which a node is left out, 0 to never leave out nodes
+    pub failure_threshold: u32,
+    /// How long a node is left out before it's tried again
+    pub cool_down: Duration,
+}
+
+impl Default for CircuitBreakerOptions {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_NODE_FAILURE_THRESHOLD,
+            cool_down: DEFAULT_NODE_COOL_DOWN,
+        }
+    }
+}
+
+/// Request statistics of a node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatistics {
+    /// The url of the node, without credentials.
+    pub url: String,
+    /// Amount of requests sent to the node.
+    pub requests: u64,
+    /// Amount of requests that failed, because the node couldn't be reached or answered with a server error.
+    pub failures: u64,
+    /// Amount of requests that failed since the last successful one.
+    pub consecutive_failures: u32,
+    /// Rolling average of the latency of successful requests.
+    pub average_latency: Option<Duration>,
+    /// Whether the circuit breaker of the node tripped, in which case it is left out until the cool-down is over.
+    pub tripped: bool,
+}
+
+#[derive(Debug, Default)]
+struct Statistics {
+    requests: u64,
+    failures: u64,
+    consecutive_failures: u32,
+    average_latency: Option<Duration>,
+    tripped_until: Option<Instant>,
+}
+
+impl Statistics {
+    fn is_tripped(&self, now: Instant) -> bool {
+        self.tripped_until.is_some_and(|until| now < until)
+    }
+}
+
+/// The statistics of all nodes a node manager sent requests to.
+#[derive(Debug, Default)]
+pub(crate) struct NodeStatisticsMap {
+    options: CircuitBreakerOptions,
+    nodes: RwLock<HashMap<String, Statistics>>,
+}
+
+/// Identifies a node by its url without credentials, so that nodes behind the same host with different paths are told
+/// apart.
+fn node_key(url: &Url) -> String {
+    let mut url = url.clone();
+    // Can only fail for urls that can't have credentials anyway.
+    url.set_username("").ok();
+    url.set_password(None).ok();
+    url.as_str().trim_end_matches('/').to_string()
+}
+
+impl NodeStatisticsMap {
+    pub(crate) fn new(options: CircuitBreakerOptions) -> Self {
+        Self {
+            options,
+            nodes: Default::default(),
+        }
+    }
+
+    pub(crate) fn options(&self) -> CircuitBreakerOptions {
+        self.options
+    }
+
+    /// Records the outcome of a request. Only failures of the node itself count, not e.g. requests for data it
+    /// doesn't have.
+    pub(crate) fn record<T>(&self, url: &Url, latency: Duration, result: &Result<T, Error>) {
+        let failed = match result {
+            Ok(_) => false,
+            Err(Error::ResponseError { code, .. }) => *code >= 500,
+            Err(Error::Reqwest(_) | Error::Transport(_)) => true,
+            Err(_) => false,
+        };
+        let Ok(mut nodes) = self.nodes.write() else {
+            return;
+        };
+        let statistics = nodes.entry(node_key(url)).or_default();
+
+        statistics.requests += 1;
+        if failed {
+            statistics.failures += 1;
+            statistics.consecutive_failures += 1;
+            if self.options.failure_threshold != 0 && statistics.consecutive_failures >= self.options.failure_threshold
+            {
+                if !statistics.is_tripped(Instant::now()) {
+                    log::warn!(
+                        "{url} failed {} times in a row, leaving it out",
+                        statistics.consecutive_failures
+                    );
+                }
+                statistics.tripped_until = Some(Instant::now() + self.options.cool_down);
+            }
+        } else {
+            statistics.consecutive_failures = 0;
+            statistics.tripped_until = None;
+            statistics.average_latency = Some(statistics.average_latency.map_or(latency, |average| {
+                average.mul_f64(1.0 - LATENCY_WEIGHT) + latency.mul_f64(LATENCY_WEIGHT)
+            }));
+        }
+    }
+
+    pub(crate) fn is_tripped(&self, url: &Url) -> bool {
+        self.nodes.read().is_ok_and(|nodes| {
+            nodes
+                .get(&node_key(url))
+                .is_some_and(|statistics| statistics.is_tripped(Instant::now()))
+        })
+    }
+
+    /// Returns how a node ranks when choosing between nodes, lower is better. Nodes that failed recently come last,
+    /// nodes without successful requests yet first, so that their latency gets known.
+    pub(crate) fn rank(&self, url: &Url) -> (bool, Duration) {
+        self.nodes
+            .read()
+            .ok()
+            .and_then(|nodes| {
+                nodes.get(&node_key(url)).map(|statistics| {
+                    (
+                        statistics.consecutive_failures > 0,
+                        statistics.average_latency.unwrap_or_default(),
+                    )
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get(&self, url: &Url) -> NodeStatistics {
+        let key = node_key(url);
+        let nodes = self.nodes.read().ok();
+        let statistics = nodes.as_ref().and_then(|nodes| nodes.get(&key));
+
+        NodeStatistics {
+            requests: statistics.map_or(0, |s| s.requests),
+            failures: statistics.map_or(0, |s| s.failures),
+            consecutive_failures: statistics.map_or(0, |s| s.consecutive_failures),
+            average_latency: statistics.and_then(|s| s.average_latency),
+            tripped: statistics.is_some_and(|s| s.is_tripped(Instant::now())),
+            url: key,
+        }
+    }
+}
+
+impl ClientInner {
+    /// Returns the request statistics of all configured nodes, to see which ones are slow or failing.
+    pub async fn node_statistics(&self) -> Vec<NodeStatistics> {
+        let node_manager = self.node_manager.read().await;
+        let mut urls = node_manager
+            .primary_node
+            .iter()
+            .chain(node_manager.primary_pow_node.iter())
+            .chain(node_manager.nodes.iter())
+            .chain(node_manager.permanodes.iter())
+            .map(|node| &node.url)
+            .collect::<Vec<_>>();
+        urls.sort_by_key(|url| node_key(url));
+        urls.dedup_by_key(|url| node_key(url));
+
+        urls.into_iter()
+            .map(|url| node_manager.http_client.statistics.get(url))
+            .collect()
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The transport over which the node manager sends its HTTP requests.
+//!
+//! By default requests are sent with [`ReqwestTransport`]. A custom [`Transport`] can be set with
+//! [`ClientBuilder::with_transport()`](crate::client::ClientBuilder::with_transport) to add middleware, e.g. a signing
+//! proxy, or to reach nodes over something else than TCP. [`RecordingTransport`] and [`ReplayTransport`] allow
+//! capturing a session to disk and replaying it deterministically in tests.
+
+#[cfg(not(target_family = "wasm"))]
+mod record;
+
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[cfg(not(target_family = "wasm"))]
+pub use self::record::{RecordedExchange, RecordingTransport, ReplayTransport};
+use crate::client::node_api::error::Result;
+
+/// The method of an [`HttpRequest`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    /// GET
+    Get,
+    /// POST
+    Post,
+}
+
+impl core::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Get => write!(f, "GET"),
+            Self::Post => write!(f, "POST"),
+        }
+    }
+}
+
+/// A request sent to a node through a [`Transport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// The method of the request.
+    pub method: HttpMethod,
+    /// The url of the request, containing the basic authentication credentials of the node if any.
+    pub url: Url,
+    /// The headers of the request, e.g. the user agent or the JWT of the node.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request.
+    pub body: Option<Vec<u8>>,
+    /// The time after which the request should be aborted.
+    pub timeout: Duration,
+}
+
+/// A response received from a node through a [`Transport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+/// Sends HTTP requests to nodes.
+///
+/// Responses with an unsuccessful status code must be returned as such, errors are reserved for requests that
+/// couldn't be sent or whose response couldn't be received.
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+pub trait Transport: Debug + Send + Sync {
+    /// Sends a request and returns the response of the node.
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.as_ref().send(request).await
+    }
+}
+
+/// The default [`Transport`], sending requests with [`reqwest`].
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a new [`ReqwestTransport`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut request_builder = match request.method {
+            HttpMethod::Get => self.client.get(request.url),
+            HttpMethod::Post => self.client.post(request.url),
+        };
+        for (name, value) in request.headers {
+            request_builder = request_builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            request_builder = request_builder.body(body);
+        }
+        #[cfg(not(target_family = "wasm"))]
+        {
+            request_builder = request_builder.timeout(request.timeout);
+        }
+
+        let response = request_builder.send().await?;
+
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+}
+
+/// A [`Transport`] shared between clients, compared by identity.
+#[derive(Clone)]
+pub(crate) struct SharedTransport(pub(crate) Arc<dyn Transport>);
+
+impl Debug for SharedTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for SharedTransport {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::as_ptr(&self.0).cast::<()>() == Arc::as_ptr(&other.0).cast::<()>()
+    }
+}
+
+impl Eq for SharedTransport {}
@@ -0,0 +1,222 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, VecDeque},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::{HttpMethod, HttpRequest, HttpResponse, ReqwestTransport, Transport};
+use crate::client::node_api::error::{Error, Result};
+
+/// A request and the response it got, as captured by a [`RecordingTransport`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedExchange {
+    /// The method of the request.
+    pub method: HttpMethod,
+    /// The url of the request, without authentication credentials.
+    pub url: Url,
+    /// The body of the request.
+    #[serde(default, with = "option_body", skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Vec<u8>>,
+    /// The status code of the response.
+    pub status: u16,
+    /// The body of the response.
+    #[serde(with = "body")]
+    pub response_body: Vec<u8>,
+}
+
+/// What a request is identified by when replayed. Headers are left out, as they hold secrets and the user agent.
+type RequestKey = (HttpMethod, Url, Option<Vec<u8>>);
+
+fn request_key(method: HttpMethod, url: Url, body: Option<&[u8]>) -> RequestKey {
+    // Bodies are compared the way they are saved, so that a session replays the same after a round trip to disk.
+    (
+        method,
+        strip_credentials(url),
+        body.map(|body| RecordedBody::from(body).into_bytes()),
+    )
+}
+
+fn strip_credentials(mut url: Url) -> Url {
+    // Only fails for urls that can't have credentials in the first place.
+    url.set_username("").ok();
+    url.set_password(None).ok();
+    url
+}
+
+/// A [`Transport`] capturing the requests sent through another transport and the responses they got.
+///
+/// The recorder is cheap to clone, so that one clone can be given to the client and the other kept to save the
+/// session afterwards with [`RecordingTransport::save()`]. Requests that fail on the transport level are not recorded.
+#[derive(Clone, Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    exchanges: Arc<Mutex<Vec<RecordedExchange>>>,
+}
+
+impl Default for RecordingTransport {
+    fn default() -> Self {
+        Self::new(ReqwestTransport::default())
+    }
+}
+
+impl RecordingTransport {
+    /// Creates a new [`RecordingTransport`] sending its requests through `inner`.
+    pub fn new(inner: impl Transport + 'static) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            exchanges: Default::default(),
+        }
+    }
+
+    /// Returns the exchanges recorded so far, in the order in which the responses were received.
+    pub fn exchanges(&self) -> Vec<RecordedExchange> {
+        self.exchanges.lock().expect("recorder lock poisoned").clone()
+    }
+
+    /// Saves the exchanges recorded so far as JSON to a file, to be replayed with [`ReplayTransport::from_file()`].
+    pub fn save(&self, path: impl AsRef<Path>) -> crate::client::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(&self.exchanges())?)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let method = request.method;
+        let url = strip_credentials(request.url.clone());
+        let request_body = request.body.clone();
+        let response = self.inner.send(request).await?;
+
+        self.exchanges
+            .lock()
+            .expect("recorder lock poisoned")
+            .push(RecordedExchange {
+                method,
+                url,
+                request_body,
+                status: response.status,
+                response_body: response.body.clone(),
+            });
+
+        Ok(response)
+    }
+}
+
+/// A [`Transport`] answering requests with previously recorded responses, without any network access.
+///
+/// Requests are matched by method, url and body. Responses recorded for the same request are returned in the
+/// recorded order, and the last one is repeated once they are exhausted, so that polling stays deterministic.
+/// Requests that weren't recorded fail with [`Error::Transport`].
+#[derive(Debug)]
+pub struct ReplayTransport {
+    responses: Mutex<HashMap<RequestKey, VecDeque<HttpResponse>>>,
+}
+
+impl ReplayTransport {
+    /// Creates a new [`ReplayTransport`] from recorded exchanges.
+    pub fn new(exchanges: impl IntoIterator<Item = RecordedExchange>) -> Self {
+        let mut responses = HashMap::<_, VecDeque<_>>::new();
+
+        for exchange in exchanges {
+            responses
+                .entry(request_key(
+                    exchange.method,
+                    exchange.url,
+                    exchange.request_body.as_deref(),
+                ))
+                .or_default()
+                .push_back(HttpResponse {
+                    status: exchange.status,
+                    body: exchange.response_body,
+                });
+        }
+
+        Self {
+            responses: Mutex::new(responses),
+        }
+    }
+
+    /// Creates a new [`ReplayTransport`] from a file written by [`RecordingTransport::save()`].
+    pub fn from_file(path: impl AsRef<Path>) -> crate::client::Result<Self> {
+        let exchanges: Vec<RecordedExchange> = serde_json::from_slice(&std::fs::read(path)?)?;
+        Ok(Self::new(exchanges))
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let key = request_key(request.method, request.url, request.body.as_deref());
+        let mut responses = self.responses.lock().expect("replay lock poisoned");
+
+        match responses.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => Ok(queue.pop_front().expect("queue is not empty")),
+            Some(queue) if !queue.is_empty() => Ok(queue[0].clone()),
+            _ => Err(Error::Transport(format!(
+                "no recorded response for {} {}",
+                key.0, key.1
+            ))),
+        }
+    }
+}
+
+/// Bodies are kept readable if they are JSON, as most node responses are, and hex encoded otherwise. JSON bodies are
+/// replayed in compact form.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RecordedBody {
+    Json(serde_json::Value),
+    Bytes(#[serde(with = "crate::utils::serde::prefix_hex_bytes")] Vec<u8>),
+}
+
+impl From<&[u8]> for RecordedBody {
+    fn from(bytes: &[u8]) -> Self {
+        serde_json::from_slice(bytes).map_or_else(|_| Self::Bytes(bytes.to_vec()), Self::Json)
+    }
+}
+
+impl RecordedBody {
+    fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Json(value) => serde_json::to_vec(&value).expect("JSON values are serializable"),
+            Self::Bytes(bytes) => bytes,
+        }
+    }
+}
+
+mod body {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::RecordedBody;
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        RecordedBody::from(bytes).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Ok(RecordedBody::deserialize(deserializer)?.into_bytes())
+    }
+}
+
+mod option_body {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::RecordedBody;
+
+    pub(super) fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        bytes.as_deref().map(RecordedBody::from).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+        Ok(Option::<RecordedBody>::deserialize(deserializer)?.map(RecordedBody::into_bytes))
+    }
+}
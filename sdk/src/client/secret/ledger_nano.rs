@@ -5,7 +5,7 @@
 //!
 //! Ledger status codes: <https://github.com/iotaledger/ledger-iota-app/blob/53c1f96d15f8b014ba8ba31a85f0401bb4d33e18/src/iota_io.h#L54>.
 
-use std::{collections::HashMap, ops::Range};
+use std::ops::Range;
 
 use async_trait::async_trait;
 use crypto::{
@@ -22,16 +22,15 @@ use tokio::sync::Mutex;
 use super::{GenerateAddressOptions, SecretManage, SecretManagerConfig};
 use crate::{
     client::secret::{
-        is_alias_transition,
         types::{LedgerApp, LedgerDeviceType},
         LedgerNanoStatus, PreparedTransactionData,
     },
     types::block::{
-        address::{Address, AliasAddress, Ed25519Address, NftAddress},
+        address::{Address, Ed25519Address},
         output::Output,
         payload::transaction::{TransactionEssence, TransactionPayload},
         signature::{Ed25519Signature, Signature},
-        unlock::{AliasUnlock, NftUnlock, ReferenceUnlock, SignatureUnlock, Unlock, Unlocks},
+        unlock::{SignatureUnlock, Unlock, Unlocks},
     },
     utils::unix_timestamp_now,
 };
@@ -524,72 +523,21 @@ fn merge_unlocks(
 
     let time = time.unwrap_or_else(|| unix_timestamp_now().as_secs() as u32);
 
-    let mut merged_unlocks = Vec::new();
-    let mut block_indexes = HashMap::<Address, usize>::new();
-
-    // Assuming inputs_data is ordered by address type
-    for (current_block_index, input) in prepared_transaction_data.inputs_data.iter().enumerate() {
-        // Get the address that is required to unlock the input
-        let TransactionEssence::Regular(regular) = &prepared_transaction_data.essence;
-        let alias_transition = is_alias_transition(&input.output, *input.output_id(), regular.outputs(), None);
-        let (input_address, _) =
-            input
-                .output
-                .required_and_unlocked_address(time, input.output_metadata.output_id(), alias_transition)?;
-
-        // Check if we already added an [Unlock] for this address
-        match block_indexes.get(&input_address) {
-            // If we already have an [Unlock] for this address, add a [Unlock] based on the address type
-            Some(block_index) => match input_address {
-                Address::Alias(_alias) => merged_unlocks.push(Unlock::Alias(AliasUnlock::new(*block_index as u16)?)),
-                Address::Ed25519(_ed25519) => {
-                    merged_unlocks.push(Unlock::Reference(ReferenceUnlock::new(*block_index as u16)?));
-                }
-                Address::Nft(_nft) => merged_unlocks.push(Unlock::Nft(NftUnlock::new(*block_index as u16)?)),
-            },
-            None => {
-                // We can only sign ed25519 addresses and block_indexes needs to contain the alias or nft
-                // address already at this point, because the reference index needs to be lower
-                // than the current block index
-                if !input_address.is_ed25519() {
-                    return Err(Error::MissingInputWithEd25519Address);
-                }
-
-                let unlock = unlocks.next().ok_or(Error::MissingInputWithEd25519Address)?;
-
-                if let Unlock::Signature(signature_unlock) = &unlock {
-                    let Signature::Ed25519(ed25519_signature) = signature_unlock.signature();
-                    let ed25519_address = match input_address {
-                        Address::Ed25519(ed25519_address) => ed25519_address,
-                        _ => return Err(Error::MissingInputWithEd25519Address)?,
-                    };
-                    ed25519_signature.is_valid(&hashed_essence, &ed25519_address)?;
-                }
+    super::merge_unlocks(prepared_transaction_data, time, |_, input_address| {
+        // We can only sign ed25519 addresses
+        let Address::Ed25519(ed25519_address) = input_address else {
+            return Err(Error::MissingInputWithEd25519Address);
+        };
 
-                merged_unlocks.push(unlock);
+        let unlock = unlocks.next().ok_or(Error::MissingInputWithEd25519Address)?;
 
-                // Add the ed25519 address to the block_indexes, so it gets referenced if further inputs have
-                // the same address in their unlock condition
-                block_indexes.insert(input_address, current_block_index);
-            }
+        if let Unlock::Signature(signature_unlock) = &unlock {
+            let Signature::Ed25519(ed25519_signature) = signature_unlock.signature();
+            ed25519_signature.is_valid(&hashed_essence, ed25519_address)?;
         }
 
-        // When we have an alias or Nft output, we will add their alias or nft address to block_indexes,
-        // because they can be used to unlock outputs via [Unlock::Alias] or [Unlock::Nft],
-        // that have the corresponding alias or nft address in their unlock condition
-        match &input.output {
-            Output::Alias(alias_output) => block_indexes.insert(
-                Address::Alias(AliasAddress::new(alias_output.alias_id_non_null(input.output_id()))),
-                current_block_index,
-            ),
-            Output::Nft(nft_output) => block_indexes.insert(
-                Address::Nft(NftAddress::new(nft_output.nft_id_non_null(input.output_id()))),
-                current_block_index,
-            ),
-            _ => None,
-        };
-    }
-    Ok(merged_unlocks)
+        Ok(unlock)
+    })
 }
 
 #[cfg(test)]
@@ -8,7 +8,11 @@ use std::ops::Range;
 use async_trait::async_trait;
 use crypto::{
     hashes::{blake2b::Blake2b256, Digest},
-    keys::{bip39::Mnemonic, bip44::Bip44, slip10::Seed},
+    keys::{
+        bip39::{Mnemonic, Passphrase},
+        bip44::Bip44,
+        slip10::Seed,
+    },
     signatures::{
         ed25519,
         secp256k1_ecdsa::{self, EvmAddress},
@@ -145,6 +149,17 @@ impl MnemonicSecretManager {
         Ok(Self(Client::mnemonic_to_seed(mnemonic.into())?.into()))
     }
 
+    /// Create a new [`MnemonicSecretManager`] from a BIP-39 mnemonic in English and a BIP-39 passphrase, also known as
+    /// the "25th word".
+    pub fn try_from_mnemonic_with_passphrase(
+        mnemonic: impl Into<Mnemonic>,
+        passphrase: impl Into<Passphrase>,
+    ) -> Result<Self, Error> {
+        Ok(Self(
+            Client::mnemonic_to_seed_with_passphrase(mnemonic.into(), passphrase.into())?.into(),
+        ))
+    }
+
     /// Create a new [`MnemonicSecretManager`] from a hex-encoded raw seed string.
     pub fn try_from_hex_seed(hex: impl Into<Zeroizing<String>>) -> Result<Self, Error> {
         let hex = hex.into();
@@ -177,6 +192,38 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn address_with_passphrase() {
+        use crate::client::constants::IOTA_COIN_TYPE;
+
+        let mnemonic = "giant dynamic museum toddler six deny defense ostrich bomb access mercy blood explain muscle shoot shallow glad autumn author calm heavy hawk abuse rally";
+        let secret_manager = MnemonicSecretManager::try_from_mnemonic(mnemonic.to_owned()).unwrap();
+        let empty_passphrase_secret_manager =
+            MnemonicSecretManager::try_from_mnemonic_with_passphrase(mnemonic.to_owned(), "").unwrap();
+        let passphrase_secret_manager =
+            MnemonicSecretManager::try_from_mnemonic_with_passphrase(mnemonic.to_owned(), "passphrase").unwrap();
+
+        let addresses = secret_manager
+            .generate_ed25519_addresses(IOTA_COIN_TYPE, 0, 0..1, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            addresses,
+            empty_passphrase_secret_manager
+                .generate_ed25519_addresses(IOTA_COIN_TYPE, 0, 0..1, None)
+                .await
+                .unwrap()
+        );
+        assert_ne!(
+            addresses,
+            passphrase_secret_manager
+                .generate_ed25519_addresses(IOTA_COIN_TYPE, 0, 0..1, None)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn seed_address() {
         use crate::client::constants::IOTA_COIN_TYPE;
@@ -9,6 +9,8 @@
 pub mod ledger_nano;
 /// Module for mnemonic based secret management.
 pub mod mnemonic;
+/// Module for enforcing signing policies on top of another secret manager.
+pub mod policy;
 /// Module for single private key based secret management.
 #[cfg(feature = "private_key_secret_manager")]
 #[cfg_attr(docsrs, doc(cfg(feature = "private_key_secret_manager")))]
@@ -19,6 +21,8 @@ pub mod private_key;
 pub mod stronghold;
 /// Signing related types
 pub mod types;
+/// Module for watch-only secret management based on public keys.
+pub mod watch_only;
 
 #[cfg(feature = "stronghold")]
 use std::time::Duration;
@@ -26,7 +30,10 @@ use std::{collections::HashMap, fmt::Debug, ops::Range, str::FromStr};
 
 use async_trait::async_trait;
 use crypto::{
-    keys::{bip39::Mnemonic, bip44::Bip44},
+    keys::{
+        bip39::{Mnemonic, Passphrase},
+        bip44::Bip44,
+    },
     signatures::secp256k1_ecdsa::{self, EvmAddress},
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -34,12 +41,16 @@ use zeroize::Zeroizing;
 
 #[cfg(feature = "ledger_nano")]
 use self::ledger_nano::LedgerSecretManager;
-use self::mnemonic::MnemonicSecretManager;
 #[cfg(feature = "private_key_secret_manager")]
 use self::private_key::PrivateKeySecretManager;
 #[cfg(feature = "stronghold")]
 use self::stronghold::StrongholdSecretManager;
 pub use self::types::{GenerateAddressOptions, LedgerNanoStatus};
+use self::{
+    mnemonic::MnemonicSecretManager,
+    types::InputSigningData,
+    watch_only::{WatchOnlyAccountDto, WatchOnlySecretManager},
+};
 #[cfg(feature = "stronghold")]
 use crate::client::secret::types::StrongholdDto;
 use crate::{
@@ -103,6 +114,24 @@ pub trait SecretManage: Send + Sync {
         ))))
     }
 
+    /// Signs a transaction essence with some of the chains of its inputs, the other inputs being unlocked by other
+    /// parties, returning a signature [`Unlock`] per chain.
+    async fn sign_transaction_essence_with_chains(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        _time: u32,
+        chains: &[Bip44],
+    ) -> Result<Vec<Unlock>, Self::Error> {
+        let essence_hash = prepared_transaction_data.essence.hash();
+        let mut unlocks = Vec::with_capacity(chains.len());
+
+        for chain in chains {
+            unlocks.push(self.signature_unlock(&essence_hash, *chain).await?);
+        }
+
+        Ok(unlocks)
+    }
+
     /// Signs a transaction essence.
     async fn sign_transaction_essence(
         &self,
@@ -149,6 +178,10 @@ pub enum SecretManager {
     #[cfg_attr(docsrs, doc(cfg(feature = "private_key_secret_manager")))]
     PrivateKey(Box<PrivateKeySecretManager>),
 
+    /// Secret manager that only holds public keys, so it can generate the addresses of an online wallet, but can't be
+    /// used for signing.
+    WatchOnly(WatchOnlySecretManager),
+
     /// Secret manager that's just a placeholder, so it can be provided to an online wallet, but can't be used for
     /// signing.
     Placeholder,
@@ -181,6 +214,12 @@ impl From<PrivateKeySecretManager> for SecretManager {
     }
 }
 
+impl From<WatchOnlySecretManager> for SecretManager {
+    fn from(secret_manager: WatchOnlySecretManager) -> Self {
+        Self::WatchOnly(secret_manager)
+    }
+}
+
 impl Debug for SecretManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -191,6 +230,7 @@ impl Debug for SecretManager {
             Self::Mnemonic(_) => f.debug_tuple("Mnemonic").field(&"...").finish(),
             #[cfg(feature = "private_key_secret_manager")]
             Self::PrivateKey(_) => f.debug_tuple("PrivateKey").field(&"...").finish(),
+            Self::WatchOnly(secret_manager) => f.debug_tuple("WatchOnly").field(secret_manager).finish(),
             Self::Placeholder => f.debug_struct("Placeholder").finish(),
         }
     }
@@ -221,6 +261,14 @@ pub enum SecretManagerDto {
     /// Mnemonic
     #[serde(alias = "mnemonic")]
     Mnemonic(Zeroizing<String>),
+    /// Mnemonic with a BIP-39 passphrase
+    #[serde(alias = "mnemonicWithPassphrase")]
+    MnemonicWithPassphrase {
+        /// Mnemonic
+        mnemonic: Zeroizing<String>,
+        /// BIP-39 passphrase from which the seed is derived together with the mnemonic
+        passphrase: Zeroizing<String>,
+    },
     /// Private Key
     #[cfg(feature = "private_key_secret_manager")]
     #[cfg_attr(docsrs, doc(cfg(feature = "private_key_secret_manager")))]
@@ -229,6 +277,9 @@ pub enum SecretManagerDto {
     /// Hex seed
     #[serde(alias = "hexSeed")]
     HexSeed(Zeroizing<String>),
+    /// Public keys of the addresses of accounts
+    #[serde(alias = "watchOnly")]
+    WatchOnly(Vec<WatchOnlyAccountDto>),
     /// Placeholder
     #[serde(alias = "placeholder")]
     Placeholder,
@@ -261,6 +312,10 @@ impl TryFrom<SecretManagerDto> for SecretManager {
                 Self::Mnemonic(MnemonicSecretManager::try_from_mnemonic(mnemonic.as_str().to_owned())?)
             }
 
+            SecretManagerDto::MnemonicWithPassphrase { mnemonic, passphrase } => Self::Mnemonic(
+                MnemonicSecretManager::try_from_mnemonic_with_passphrase(mnemonic.as_str().to_owned(), passphrase)?,
+            ),
+
             #[cfg(feature = "private_key_secret_manager")]
             SecretManagerDto::PrivateKey(private_key) => {
                 Self::PrivateKey(Box::new(PrivateKeySecretManager::try_from_hex(private_key)?))
@@ -271,6 +326,8 @@ impl TryFrom<SecretManagerDto> for SecretManager {
                 Self::Mnemonic(MnemonicSecretManager::try_from_hex_seed(hex_seed)?)
             }
 
+            SecretManagerDto::WatchOnly(accounts) => Self::WatchOnly(WatchOnlySecretManager::from_config(&accounts)?),
+
             SecretManagerDto::Placeholder => Self::Placeholder,
         })
     }
@@ -302,6 +359,10 @@ impl From<&SecretManager> for SecretManagerDto {
             #[cfg(feature = "private_key_secret_manager")]
             SecretManager::PrivateKey(_private_key) => Self::PrivateKey("...".to_string().into()),
 
+            SecretManager::WatchOnly(watch_only) => {
+                Self::WatchOnly(watch_only.accounts().iter().map(WatchOnlyAccountDto::from).collect())
+            }
+
             SecretManager::Placeholder => Self::Placeholder,
         }
     }
@@ -338,6 +399,11 @@ impl SecretManage for SecretManager {
                     .generate_ed25519_addresses(coin_type, account_index, address_indexes, options)
                     .await
             }
+            Self::WatchOnly(secret_manager) => {
+                secret_manager
+                    .generate_ed25519_addresses(coin_type, account_index, address_indexes, options)
+                    .await
+            }
             Self::Placeholder => Err(Error::PlaceholderSecretManager),
         }
     }
@@ -369,6 +435,11 @@ impl SecretManage for SecretManager {
                     .generate_evm_addresses(coin_type, account_index, address_indexes, options)
                     .await
             }
+            Self::WatchOnly(secret_manager) => {
+                secret_manager
+                    .generate_evm_addresses(coin_type, account_index, address_indexes, options)
+                    .await
+            }
             Self::Placeholder => Err(Error::PlaceholderSecretManager),
         }
     }
@@ -382,6 +453,7 @@ impl SecretManage for SecretManager {
             Self::Mnemonic(secret_manager) => secret_manager.sign_ed25519(msg, chain).await,
             #[cfg(feature = "private_key_secret_manager")]
             Self::PrivateKey(secret_manager) => secret_manager.sign_ed25519(msg, chain).await,
+            Self::WatchOnly(secret_manager) => secret_manager.sign_ed25519(msg, chain).await,
             Self::Placeholder => Err(Error::PlaceholderSecretManager),
         }
     }
@@ -399,6 +471,7 @@ impl SecretManage for SecretManager {
             Self::Mnemonic(secret_manager) => secret_manager.sign_secp256k1_ecdsa(msg, chain).await,
             #[cfg(feature = "private_key_secret_manager")]
             Self::PrivateKey(secret_manager) => secret_manager.sign_secp256k1_ecdsa(msg, chain).await,
+            Self::WatchOnly(secret_manager) => secret_manager.sign_secp256k1_ecdsa(msg, chain).await,
             Self::Placeholder => Err(Error::PlaceholderSecretManager),
         }
     }
@@ -428,6 +501,11 @@ impl SecretManage for SecretManager {
                     .sign_transaction_essence(prepared_transaction_data, time)
                     .await
             }
+            Self::WatchOnly(secret_manager) => {
+                secret_manager
+                    .sign_transaction_essence(prepared_transaction_data, time)
+                    .await
+            }
             Self::Placeholder => Err(Error::PlaceholderSecretManager),
         }
     }
@@ -444,6 +522,7 @@ impl SecretManage for SecretManager {
             Self::Mnemonic(secret_manager) => secret_manager.sign_transaction(prepared_transaction_data).await,
             #[cfg(feature = "private_key_secret_manager")]
             Self::PrivateKey(secret_manager) => secret_manager.sign_transaction(prepared_transaction_data).await,
+            Self::WatchOnly(secret_manager) => secret_manager.sign_transaction(prepared_transaction_data).await,
             Self::Placeholder => Err(Error::PlaceholderSecretManager),
         }
     }
@@ -471,6 +550,7 @@ impl SecretManagerConfig for SecretManager {
             Self::Mnemonic(_) => None,
             #[cfg(feature = "private_key_secret_manager")]
             Self::PrivateKey(_) => None,
+            Self::WatchOnly(s) => s.to_config().map(Self::Config::WatchOnly),
             Self::Placeholder => None,
         }
     }
@@ -487,10 +567,17 @@ impl SecretManagerConfig for SecretManager {
             SecretManagerDto::Mnemonic(mnemonic) => {
                 Self::Mnemonic(MnemonicSecretManager::try_from_mnemonic(mnemonic.as_str().to_owned())?)
             }
+            SecretManagerDto::MnemonicWithPassphrase { mnemonic, passphrase } => {
+                Self::Mnemonic(MnemonicSecretManager::try_from_mnemonic_with_passphrase(
+                    mnemonic.as_str().to_owned(),
+                    passphrase.as_str(),
+                )?)
+            }
             #[cfg(feature = "private_key_secret_manager")]
             SecretManagerDto::PrivateKey(private_key) => {
                 Self::PrivateKey(Box::new(PrivateKeySecretManager::try_from_hex(private_key.to_owned())?))
             }
+            SecretManagerDto::WatchOnly(config) => Self::WatchOnly(WatchOnlySecretManager::from_config(config)?),
             SecretManagerDto::Placeholder => Self::Placeholder,
         })
     }
@@ -502,6 +589,16 @@ impl SecretManager {
         Ok(Self::Mnemonic(MnemonicSecretManager::try_from_mnemonic(mnemonic)?))
     }
 
+    /// Tries to create a [`SecretManager`] from a mnemonic string and a BIP-39 passphrase.
+    pub fn try_from_mnemonic_with_passphrase(
+        mnemonic: impl Into<Mnemonic>,
+        passphrase: impl Into<Passphrase>,
+    ) -> crate::client::Result<Self> {
+        Ok(Self::Mnemonic(
+            MnemonicSecretManager::try_from_mnemonic_with_passphrase(mnemonic, passphrase)?,
+        ))
+    }
+
     /// Tries to create a [`SecretManager`] from a seed hex string.
     pub fn try_from_hex_seed(seed: impl Into<Zeroizing<String>>) -> crate::client::Result<Self> {
         Ok(Self::Mnemonic(MnemonicSecretManager::try_from_hex_seed(seed)?))
@@ -518,7 +615,87 @@ where
 {
     // The hashed_essence gets signed
     let hashed_essence = prepared_transaction_data.essence.hash();
-    let mut blocks = Vec::new();
+    let time = time.unwrap_or_else(|| unix_timestamp_now().as_secs() as u32);
+
+    // The signatures are only requested afterwards, as the secret manager signs asynchronously
+    let pending_unlocks = merge_unlocks(prepared_transaction_data, time, |input, input_address| {
+        // We can only sign ed25519 addresses
+        if !input_address.is_ed25519() {
+            Err(InputSelectionError::MissingInputWithEd25519Address)?;
+        }
+
+        Ok::<_, Error>(PendingUnlock::Signature(input.chain.ok_or(Error::MissingBip32Chain)?))
+    })?;
+    let mut blocks = Vec::with_capacity(pending_unlocks.len());
+
+    for pending_unlock in pending_unlocks {
+        blocks.push(match pending_unlock {
+            PendingUnlock::Signature(chain) => secret_manager.signature_unlock(&hashed_essence, chain).await?,
+            PendingUnlock::Unlock(unlock) => unlock,
+        });
+    }
+
+    Ok(Unlocks::new(blocks)?)
+}
+
+// An unlock of [default_sign_transaction_essence()], of which the signature still has to be requested.
+enum PendingUnlock {
+    Signature(Bip44),
+    Unlock(Unlock),
+}
+
+impl From<Unlock> for PendingUnlock {
+    fn from(unlock: Unlock) -> Self {
+        Self::Unlock(unlock)
+    }
+}
+
+pub(crate) async fn default_sign_transaction<M: SecretManage>(
+    secret_manager: &M,
+    prepared_transaction_data: PreparedTransactionData,
+) -> crate::client::Result<TransactionPayload>
+where
+    crate::client::Error: From<M::Error>,
+{
+    log::debug!("[sign_transaction] {:?}", prepared_transaction_data);
+    let current_time = unix_timestamp_now().as_secs() as u32;
+
+    let unlocks = secret_manager
+        .sign_transaction_essence(&prepared_transaction_data, Some(current_time))
+        .await?;
+
+    let PreparedTransactionData {
+        essence, inputs_data, ..
+    } = prepared_transaction_data;
+    let tx_payload = TransactionPayload::new(essence, unlocks)?;
+
+    validate_transaction_payload_length(&tx_payload)?;
+
+    let conflict = verify_semantic(&inputs_data, &tx_payload, current_time)?;
+
+    if conflict != ConflictReason::None {
+        log::debug!("[sign_transaction] conflict: {conflict:?} for {:#?}", tx_payload);
+        return Err(Error::TransactionSemantic(conflict));
+    }
+
+    Ok(tx_payload)
+}
+
+/// Merges signature unlocks with Alias/Nft/Reference unlocks, for the inputs unlocked at `time`.
+///
+/// `signature_unlock` is called in input order with each input whose address has to be unlocked with a signature, as it
+/// isn't unlocked by a previous input. Only Ed25519 addresses can be unlocked with a signature, other addresses have to
+/// be rejected.
+pub(crate) fn merge_unlocks<U, E>(
+    prepared_transaction_data: &PreparedTransactionData,
+    time: u32,
+    mut signature_unlock: impl FnMut(&InputSigningData, &Address) -> Result<U, E>,
+) -> Result<Vec<U>, E>
+where
+    U: From<Unlock>,
+    E: From<crate::types::block::Error>,
+{
+    let mut merged_unlocks = Vec::new();
     let mut block_indexes = HashMap::<Address, usize>::new();
 
     // Assuming inputs_data is ordered by address type
@@ -526,34 +703,27 @@ where
         // Get the address that is required to unlock the input
         let TransactionEssence::Regular(regular) = &prepared_transaction_data.essence;
         let alias_transition = is_alias_transition(&input.output, *input.output_id(), regular.outputs(), None);
-        let (input_address, _) = input.output.required_and_unlocked_address(
-            time.unwrap_or_else(|| unix_timestamp_now().as_secs() as u32),
-            input.output_metadata.output_id(),
-            alias_transition,
-        )?;
+        let (input_address, _) =
+            input
+                .output
+                .required_and_unlocked_address(time, input.output_metadata.output_id(), alias_transition)?;
 
         // Check if we already added an [Unlock] for this address
         match block_indexes.get(&input_address) {
             // If we already have an [Unlock] for this address, add a [Unlock] based on the address type
             Some(block_index) => match input_address {
-                Address::Alias(_alias) => blocks.push(Unlock::Alias(AliasUnlock::new(*block_index as u16)?)),
+                Address::Alias(_alias) => {
+                    merged_unlocks.push(Unlock::Alias(AliasUnlock::new(*block_index as u16)?).into());
+                }
                 Address::Ed25519(_ed25519) => {
-                    blocks.push(Unlock::Reference(ReferenceUnlock::new(*block_index as u16)?));
+                    merged_unlocks.push(Unlock::Reference(ReferenceUnlock::new(*block_index as u16)?).into());
                 }
-                Address::Nft(_nft) => blocks.push(Unlock::Nft(NftUnlock::new(*block_index as u16)?)),
+                Address::Nft(_nft) => merged_unlocks.push(Unlock::Nft(NftUnlock::new(*block_index as u16)?).into()),
             },
             None => {
-                // We can only sign ed25519 addresses and block_indexes needs to contain the alias or nft
-                // address already at this point, because the reference index needs to be lower
-                // than the current block index
-                if !input_address.is_ed25519() {
-                    Err(InputSelectionError::MissingInputWithEd25519Address)?;
-                }
-
-                let chain = input.chain.ok_or(Error::MissingBip32Chain)?;
-
-                let block = secret_manager.signature_unlock(&hashed_essence, chain).await?;
-                blocks.push(block);
+                // block_indexes needs to contain the alias or nft address already at this point, because the
+                // reference index needs to be lower than the current block index
+                merged_unlocks.push(signature_unlock(input, &input_address)?);
 
                 // Add the ed25519 address to the block_indexes, so it gets referenced if further inputs have
                 // the same address in their unlock condition
@@ -577,36 +747,5 @@ where
         };
     }
 
-    Ok(Unlocks::new(blocks)?)
-}
-
-pub(crate) async fn default_sign_transaction<M: SecretManage>(
-    secret_manager: &M,
-    prepared_transaction_data: PreparedTransactionData,
-) -> crate::client::Result<TransactionPayload>
-where
-    crate::client::Error: From<M::Error>,
-{
-    log::debug!("[sign_transaction] {:?}", prepared_transaction_data);
-    let current_time = unix_timestamp_now().as_secs() as u32;
-
-    let unlocks = secret_manager
-        .sign_transaction_essence(&prepared_transaction_data, Some(current_time))
-        .await?;
-
-    let PreparedTransactionData {
-        essence, inputs_data, ..
-    } = prepared_transaction_data;
-    let tx_payload = TransactionPayload::new(essence, unlocks)?;
-
-    validate_transaction_payload_length(&tx_payload)?;
-
-    let conflict = verify_semantic(&inputs_data, &tx_payload, current_time)?;
-
-    if conflict != ConflictReason::None {
-        log::debug!("[sign_transaction] conflict: {conflict:?} for {:#?}", tx_payload);
-        return Err(Error::TransactionSemantic(conflict));
-    }
-
-    Ok(tx_payload)
+    Ok(merged_unlocks)
 }
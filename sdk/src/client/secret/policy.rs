@@ -0,0 +1,767 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of [`PolicySecretManager`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Debug,
+    fs::OpenOptions,
+    future::Future,
+    io::{BufRead, BufReader, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use crypto::{
+    keys::bip44::Bip44,
+    signatures::secp256k1_ecdsa::{self, EvmAddress},
+};
+use primitive_types::U256;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{GenerateAddressOptions, SecretManage, SecretManagerConfig};
+use crate::{
+    client::{
+        api::{input_selection::is_alias_transition, summary::owner, PreparedTransactionData},
+        Error,
+    },
+    types::block::{
+        address::{dto::AddressDto, Address, AliasAddress, Ed25519Address, NftAddress},
+        output::{NativeToken, Output, TokenId},
+        payload::{transaction::TransactionEssence, TransactionPayload},
+        signature::Ed25519Signature,
+        unlock::{Unlock, Unlocks},
+    },
+    utils::unix_timestamp_now,
+};
+
+/// The window of the daily spending limits, in seconds.
+const DAY: u32 = 24 * 60 * 60;
+
+/// A token whose spending can be limited.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PolicyToken {
+    /// The base coin.
+    BaseCoin,
+    /// A native token.
+    NativeToken(TokenId),
+}
+
+/// Limits of the amount of a token sent to other addresses.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingLimit {
+    /// The maximum amount sent by a single transaction.
+    #[serde(default)]
+    pub per_transaction: Option<U256>,
+    /// The maximum amount sent by the transactions signed within the last 24 hours.
+    #[serde(default)]
+    pub daily: Option<U256>,
+}
+
+/// The rules a transaction has to follow to be signed by a [`PolicySecretManager`].
+///
+/// An output is sent when an address other than the ones unlocking the inputs signed by the secret manager can unlock
+/// it, now or later: its owner, the return address of its expiration or the state controller of an alias. Outputs
+/// locked to an alias or NFT address can be unlocked by the controllers of that chain. These addresses are the
+/// destinations of the transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SigningPolicy {
+    /// Limits of the amounts sent per token, tokens without limit can be sent freely.
+    pub spending_limits: BTreeMap<PolicyToken, SpendingLimit>,
+    /// The only addresses allowed as destinations, all addresses are allowed if not set.
+    pub allowed_destinations: Option<BTreeSet<Address>>,
+    /// Forbids burning or melting native tokens and destroying aliases, foundries and NFTs.
+    pub forbid_burns: bool,
+    /// The maximum number of outputs of a transaction.
+    pub max_outputs: Option<usize>,
+    /// Forbids attaching tagged data to a transaction.
+    pub forbid_tagged_data: bool,
+    /// Allows signing arbitrary messages, which bypasses the rules of the policy.
+    pub allow_raw_signing: bool,
+}
+
+impl SigningPolicy {
+    /// Creates a policy allowing every transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the spending limit of a token.
+    pub fn with_spending_limit(mut self, token: PolicyToken, limit: SpendingLimit) -> Self {
+        self.spending_limits.insert(token, limit);
+        self
+    }
+
+    /// Sets the only addresses allowed as destinations.
+    pub fn with_allowed_destinations(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.allowed_destinations = Some(addresses.into_iter().collect());
+        self
+    }
+
+    /// Sets whether burns are forbidden.
+    pub fn with_forbid_burns(mut self, forbid_burns: bool) -> Self {
+        self.forbid_burns = forbid_burns;
+        self
+    }
+
+    /// Sets the maximum number of outputs of a transaction.
+    pub fn with_max_outputs(mut self, max_outputs: impl Into<Option<usize>>) -> Self {
+        self.max_outputs = max_outputs.into();
+        self
+    }
+
+    /// Sets whether tagged data is forbidden.
+    pub fn with_forbid_tagged_data(mut self, forbid_tagged_data: bool) -> Self {
+        self.forbid_tagged_data = forbid_tagged_data;
+        self
+    }
+
+    /// Sets whether arbitrary messages can be signed.
+    pub fn with_allow_raw_signing(mut self, allow_raw_signing: bool) -> Self {
+        self.allow_raw_signing = allow_raw_signing;
+        self
+    }
+}
+
+/// Dto for a [`SigningPolicy`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningPolicyDto {
+    #[serde(default)]
+    pub spending_limits: Vec<(PolicyToken, SpendingLimit)>,
+    #[serde(default)]
+    pub allowed_destinations: Option<Vec<AddressDto>>,
+    #[serde(default)]
+    pub forbid_burns: bool,
+    #[serde(default)]
+    pub max_outputs: Option<usize>,
+    #[serde(default)]
+    pub forbid_tagged_data: bool,
+    #[serde(default)]
+    pub allow_raw_signing: bool,
+}
+
+impl From<&SigningPolicy> for SigningPolicyDto {
+    fn from(value: &SigningPolicy) -> Self {
+        Self {
+            spending_limits: value.spending_limits.iter().map(|(t, l)| (*t, *l)).collect(),
+            allowed_destinations: value
+                .allowed_destinations
+                .as_ref()
+                .map(|addresses| addresses.iter().map(AddressDto::from).collect()),
+            forbid_burns: value.forbid_burns,
+            max_outputs: value.max_outputs,
+            forbid_tagged_data: value.forbid_tagged_data,
+            allow_raw_signing: value.allow_raw_signing,
+        }
+    }
+}
+
+impl TryFrom<SigningPolicyDto> for SigningPolicy {
+    type Error = Error;
+
+    fn try_from(value: SigningPolicyDto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            spending_limits: value.spending_limits.into_iter().collect(),
+            allowed_destinations: value
+                .allowed_destinations
+                .map(|addresses| addresses.into_iter().map(Address::try_from).collect())
+                .transpose()?,
+            forbid_burns: value.forbid_burns,
+            max_outputs: value.max_outputs,
+            forbid_tagged_data: value.forbid_tagged_data,
+            allow_raw_signing: value.allow_raw_signing,
+        })
+    }
+}
+
+/// A rule of a [`SigningPolicy`] broken by a signing request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyViolation {
+    /// A transaction sends more than the per transaction limit of a token.
+    TransactionLimitExceeded {
+        /// The token.
+        token: PolicyToken,
+        /// The amount sent by the transaction.
+        amount: U256,
+        /// The limit.
+        limit: U256,
+    },
+    /// A transaction would send more than the daily limit of a token.
+    DailyLimitExceeded {
+        /// The token.
+        token: PolicyToken,
+        /// The amount sent within the last 24 hours, including the transaction.
+        amount: U256,
+        /// The limit.
+        limit: U256,
+    },
+    /// A transaction sends to an address that isn't allowed.
+    DestinationNotAllowed(Address),
+    /// A transaction burns native tokens or destroys aliases, foundries or NFTs.
+    Burn,
+    /// A transaction has too many outputs.
+    TooManyOutputs {
+        /// The number of outputs.
+        count: usize,
+        /// The maximum number of outputs.
+        max: usize,
+    },
+    /// A transaction has tagged data attached.
+    TaggedData,
+    /// An arbitrary message was requested to be signed.
+    RawSigning,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TransactionLimitExceeded { token, amount, limit } => {
+                write!(f, "{token:?} amount {amount} exceeds the per transaction limit {limit}")
+            }
+            Self::DailyLimitExceeded { token, amount, limit } => {
+                write!(f, "{token:?} amount {amount} exceeds the daily limit {limit}")
+            }
+            Self::DestinationNotAllowed(address) => write!(f, "destination {address:?} is not allowed"),
+            Self::Burn => write!(f, "burns are forbidden"),
+            Self::TooManyOutputs { count, max } => write!(f, "{count} outputs exceed the maximum of {max}"),
+            Self::TaggedData => write!(f, "tagged data is forbidden"),
+            Self::RawSigning => write!(f, "signing arbitrary messages is forbidden"),
+        }
+    }
+}
+
+/// The record of a transaction signed by a [`PolicySecretManager`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditRecord {
+    /// The unix timestamp in seconds at which the transaction was signed.
+    pub timestamp: u32,
+    /// The hash of the signed transaction essence.
+    pub essence_hash: [u8; 32],
+    /// The amounts sent by the transaction.
+    pub sent: BTreeMap<PolicyToken, U256>,
+    /// The destinations of the transaction.
+    pub destinations: Vec<Address>,
+}
+
+/// Dto for an [`AuditRecord`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecordDto {
+    pub timestamp: u32,
+    pub essence_hash: String,
+    #[serde(with = "crate::utils::serde::string")]
+    pub base_coin_sent: u64,
+    pub native_tokens_sent: Vec<NativeToken>,
+    pub destinations: Vec<AddressDto>,
+}
+
+impl From<&AuditRecord> for AuditRecordDto {
+    fn from(value: &AuditRecord) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            essence_hash: prefix_hex::encode(value.essence_hash),
+            base_coin_sent: value
+                .sent
+                .get(&PolicyToken::BaseCoin)
+                .map_or(0, |amount| amount.low_u64()),
+            native_tokens_sent: value
+                .sent
+                .iter()
+                .filter_map(|(token, amount)| match token {
+                    PolicyToken::NativeToken(token_id) => NativeToken::new(*token_id, *amount).ok(),
+                    PolicyToken::BaseCoin => None,
+                })
+                .collect(),
+            destinations: value.destinations.iter().map(AddressDto::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<AuditRecordDto> for AuditRecord {
+    type Error = Error;
+
+    fn try_from(value: AuditRecordDto) -> Result<Self, Self::Error> {
+        let mut sent = value
+            .native_tokens_sent
+            .iter()
+            .map(|native_token| {
+                (
+                    PolicyToken::NativeToken(*native_token.token_id()),
+                    native_token.amount(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        if value.base_coin_sent != 0 {
+            sent.insert(PolicyToken::BaseCoin, value.base_coin_sent.into());
+        }
+
+        Ok(Self {
+            timestamp: value.timestamp,
+            essence_hash: prefix_hex::decode(value.essence_hash)?,
+            sent,
+            destinations: value
+                .destinations
+                .into_iter()
+                .map(Address::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+// What a transaction sends, to check it against a policy.
+struct TransactionEffects {
+    sent: BTreeMap<PolicyToken, U256>,
+    destinations: Vec<Address>,
+    burns: bool,
+}
+
+impl TransactionEffects {
+    fn new(prepared_transaction_data: &PreparedTransactionData, time: u32) -> crate::client::Result<Self> {
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        let summary = prepared_transaction_data.summary();
+
+        // The addresses unlocking the inputs signed by the secret manager, and the chains they unlock.
+        let mut own_addresses = BTreeSet::new();
+        for input in prepared_transaction_data
+            .inputs_data
+            .iter()
+            .filter(|input| input.chain.is_some())
+        {
+            let alias_transition = is_alias_transition(&input.output, *input.output_id(), essence.outputs(), None);
+            let (required_address, unlocked_address) =
+                input
+                    .output
+                    .required_and_unlocked_address(time, input.output_id(), alias_transition)?;
+            own_addresses.insert(required_address);
+            own_addresses.extend(unlocked_address);
+        }
+
+        let chain_outputs = essence
+            .outputs()
+            .iter()
+            .filter_map(|output| match output {
+                Output::Alias(alias) if !alias.alias_id().is_null() => {
+                    Some((Address::Alias(AliasAddress::new(*alias.alias_id())), output))
+                }
+                Output::Nft(nft) if !nft.nft_id().is_null() => {
+                    Some((Address::Nft(NftAddress::new(*nft.nft_id())), output))
+                }
+                _ => None,
+            })
+            .collect::<BTreeMap<_, _>>();
+        let mut sent = BTreeMap::<PolicyToken, U256>::new();
+        let mut destinations = BTreeSet::new();
+
+        for output in essence.outputs() {
+            let mut unlock_addresses = BTreeSet::new();
+            resolve_unlock_addresses(output, &chain_outputs, &mut BTreeSet::new(), &mut unlock_addresses);
+            let foreign_addresses = unlock_addresses.difference(&own_addresses).collect::<Vec<_>>();
+
+            if foreign_addresses.is_empty() {
+                continue;
+            }
+
+            let amounts = std::iter::once((PolicyToken::BaseCoin, U256::from(output.amount()))).chain(
+                output.native_tokens().into_iter().flat_map(|native_tokens| {
+                    native_tokens.iter().map(|native_token| {
+                        (
+                            PolicyToken::NativeToken(*native_token.token_id()),
+                            native_token.amount(),
+                        )
+                    })
+                }),
+            );
+
+            for (token, amount) in amounts.filter(|(_, amount)| !amount.is_zero()) {
+                let total = sent.entry(token).or_default();
+                *total = total.saturating_add(amount);
+            }
+            destinations.extend(foreign_addresses);
+        }
+
+        let (mut chains_sent, mut chains_received) = (BTreeSet::new(), BTreeSet::new());
+        for address_summary in &summary.addresses {
+            chains_sent.extend(address_summary.aliases_sent.iter().map(|id| id.to_string()));
+            chains_sent.extend(address_summary.foundries_sent.iter().map(|id| id.to_string()));
+            chains_sent.extend(address_summary.nfts_sent.iter().map(|id| id.to_string()));
+            chains_received.extend(address_summary.aliases_received.iter().map(|id| id.to_string()));
+            chains_received.extend(address_summary.foundries_received.iter().map(|id| id.to_string()));
+            chains_received.extend(address_summary.nfts_received.iter().map(|id| id.to_string()));
+        }
+
+        Ok(Self {
+            sent,
+            destinations: destinations.into_iter().collect(),
+            burns: !summary.burned_native_tokens.is_empty() || !chains_sent.is_subset(&chains_received),
+        })
+    }
+}
+
+// Returns every address that can unlock an output, now or after its expiration.
+fn unlock_addresses(output: &Output) -> Vec<Address> {
+    let mut addresses = owner(output).into_iter().collect::<Vec<_>>();
+
+    if let Output::Alias(alias) = output {
+        addresses.push(*alias.state_controller_address());
+    }
+    if let Some(expiration) = output
+        .unlock_conditions()
+        .and_then(|unlock_conditions| unlock_conditions.expiration())
+    {
+        addresses.push(*expiration.return_address());
+    }
+
+    addresses
+}
+
+// Collects the addresses that can unlock an output, replacing the address of an alias or NFT created by the
+// transaction with the addresses that can unlock that chain.
+fn resolve_unlock_addresses(
+    output: &Output,
+    chain_outputs: &BTreeMap<Address, &Output>,
+    visited: &mut BTreeSet<Address>,
+    resolved: &mut BTreeSet<Address>,
+) {
+    for address in unlock_addresses(output) {
+        if !visited.insert(address) {
+            continue;
+        }
+
+        match chain_outputs.get(&address) {
+            Some(chain_output) => resolve_unlock_addresses(chain_output, chain_outputs, visited, resolved),
+            None => {
+                resolved.insert(address);
+            }
+        }
+    }
+}
+
+/// Secret manager enforcing a [`SigningPolicy`] before another secret manager signs a transaction.
+///
+/// Transactions breaking a rule are rejected with [`Error::PolicyViolation`]. Every signed transaction is recorded,
+/// records of the last 24 hours are kept to enforce the daily limits and all records are appended to the audit file,
+/// if set.
+pub struct PolicySecretManager<S> {
+    secret_manager: S,
+    policy: SigningPolicy,
+    audit_file: Option<PathBuf>,
+    recent_audit_records: Mutex<VecDeque<AuditRecord>>,
+}
+
+impl<S> Debug for PolicySecretManager<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicySecretManager")
+            .field("policy", &self.policy)
+            .field("audit_file", &self.audit_file)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: SecretManage> PolicySecretManager<S>
+where
+    S::Error: Into<Error>,
+{
+    /// Creates a [`PolicySecretManager`] enforcing a policy before `secret_manager` signs.
+    pub fn new(secret_manager: S, policy: SigningPolicy) -> Self {
+        Self {
+            secret_manager,
+            policy,
+            audit_file: None,
+            recent_audit_records: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Sets the file audit records are appended to as JSON lines. The records of the last 24 hours are read from it, so
+    /// the daily limits hold across restarts.
+    pub fn with_audit_file(mut self, path: impl AsRef<Path>) -> crate::client::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut recent_audit_records = VecDeque::new();
+
+        if path.exists() {
+            let since = unix_timestamp_now().as_secs() as u32 - DAY;
+
+            for line in BufReader::new(std::fs::File::open(&path)?).lines() {
+                let record = AuditRecord::try_from(serde_json::from_str::<AuditRecordDto>(&line?)?)?;
+
+                if record.timestamp > since {
+                    recent_audit_records.push_back(record);
+                }
+            }
+        }
+
+        self.audit_file = Some(path);
+        self.recent_audit_records = Mutex::new(recent_audit_records);
+
+        Ok(self)
+    }
+
+    /// Returns the secret manager signing the transactions.
+    pub fn secret_manager(&self) -> &S {
+        &self.secret_manager
+    }
+
+    /// Returns the enforced policy.
+    pub fn policy(&self) -> &SigningPolicy {
+        &self.policy
+    }
+
+    /// Returns the records of the transactions signed within the last 24 hours.
+    pub async fn recent_audit_records(&self) -> Vec<AuditRecord> {
+        self.recent_audit_records.lock().await.iter().cloned().collect()
+    }
+
+    // Checks a transaction against the policy, given the records of the last 24 hours.
+    fn check(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        effects: &TransactionEffects,
+        recent_audit_records: &VecDeque<AuditRecord>,
+    ) -> Result<(), PolicyViolation> {
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+
+        if let Some(max) = self.policy.max_outputs {
+            if essence.outputs().len() > max {
+                return Err(PolicyViolation::TooManyOutputs {
+                    count: essence.outputs().len(),
+                    max,
+                });
+            }
+        }
+        if self.policy.forbid_tagged_data && essence.payload().is_some() {
+            return Err(PolicyViolation::TaggedData);
+        }
+        if self.policy.forbid_burns && effects.burns {
+            return Err(PolicyViolation::Burn);
+        }
+        if let Some(allowed_destinations) = &self.policy.allowed_destinations {
+            if let Some(address) = effects
+                .destinations
+                .iter()
+                .find(|address| !allowed_destinations.contains(address))
+            {
+                return Err(PolicyViolation::DestinationNotAllowed(*address));
+            }
+        }
+
+        for (token, limit) in &self.policy.spending_limits {
+            let amount = effects.sent.get(token).copied().unwrap_or_default();
+
+            if let Some(per_transaction) = limit.per_transaction {
+                if amount > per_transaction {
+                    return Err(PolicyViolation::TransactionLimitExceeded {
+                        token: *token,
+                        amount,
+                        limit: per_transaction,
+                    });
+                }
+            }
+            if let Some(daily) = limit.daily {
+                let amount = recent_audit_records
+                    .iter()
+                    .filter_map(|record| record.sent.get(token))
+                    .fold(amount, |total, amount| total.saturating_add(*amount));
+
+                if amount > daily {
+                    return Err(PolicyViolation::DailyLimitExceeded {
+                        token: *token,
+                        amount,
+                        limit: daily,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks a transaction against the policy, signs it with `sign` and records it.
+    async fn sign_checked<T>(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        time: u32,
+        sign: impl Future<Output = Result<T, S::Error>> + Send,
+    ) -> Result<T, Error> {
+        let effects = TransactionEffects::new(prepared_transaction_data, time)?;
+        // Held until the record is added, so concurrent signing requests can't exceed the daily limits together.
+        let mut recent_audit_records = self.recent_audit_records.lock().await;
+        let timestamp = unix_timestamp_now().as_secs() as u32;
+
+        while recent_audit_records
+            .front()
+            .is_some_and(|record| record.timestamp <= timestamp - DAY)
+        {
+            recent_audit_records.pop_front();
+        }
+
+        if let Err(violation) = self.check(prepared_transaction_data, &effects, &recent_audit_records) {
+            log::debug!("[PolicySecretManager] rejected transaction: {violation}");
+            return Err(Error::PolicyViolation(violation));
+        }
+
+        let signed = sign.await.map_err(Into::into)?;
+
+        let record = AuditRecord {
+            timestamp,
+            essence_hash: prepared_transaction_data.essence.hash(),
+            sent: effects.sent,
+            destinations: effects.destinations,
+        };
+        self.write_audit_record(&record)?;
+        recent_audit_records.push_back(record);
+
+        Ok(signed)
+    }
+
+    fn write_audit_record(&self, record: &AuditRecord) -> crate::client::Result<()> {
+        if let Some(path) = &self.audit_file {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(&AuditRecordDto::from(record))?)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S: SecretManage> SecretManage for PolicySecretManager<S>
+where
+    S::Error: Into<Error>,
+{
+    type Error = Error;
+
+    async fn generate_ed25519_addresses(
+        &self,
+        coin_type: u32,
+        account_index: u32,
+        address_indexes: Range<u32>,
+        options: impl Into<Option<GenerateAddressOptions>> + Send,
+    ) -> Result<Vec<Ed25519Address>, Self::Error> {
+        self.secret_manager
+            .generate_ed25519_addresses(coin_type, account_index, address_indexes, options)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn generate_evm_addresses(
+        &self,
+        coin_type: u32,
+        account_index: u32,
+        address_indexes: Range<u32>,
+        options: impl Into<Option<GenerateAddressOptions>> + Send,
+    ) -> Result<Vec<EvmAddress>, Self::Error> {
+        self.secret_manager
+            .generate_evm_addresses(coin_type, account_index, address_indexes, options)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn sign_ed25519(&self, msg: &[u8], chain: Bip44) -> Result<Ed25519Signature, Self::Error> {
+        if !self.policy.allow_raw_signing {
+            return Err(Error::PolicyViolation(PolicyViolation::RawSigning));
+        }
+
+        self.secret_manager.sign_ed25519(msg, chain).await.map_err(Into::into)
+    }
+
+    async fn sign_secp256k1_ecdsa(
+        &self,
+        msg: &[u8],
+        chain: Bip44,
+    ) -> Result<(secp256k1_ecdsa::PublicKey, secp256k1_ecdsa::RecoverableSignature), Self::Error> {
+        if !self.policy.allow_raw_signing {
+            return Err(Error::PolicyViolation(PolicyViolation::RawSigning));
+        }
+
+        self.secret_manager
+            .sign_secp256k1_ecdsa(msg, chain)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn sign_transaction_essence(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        time: Option<u32>,
+    ) -> Result<Unlocks, Self::Error> {
+        let time = time.unwrap_or_else(|| unix_timestamp_now().as_secs() as u32);
+
+        self.sign_checked(
+            prepared_transaction_data,
+            time,
+            self.secret_manager
+                .sign_transaction_essence(prepared_transaction_data, Some(time)),
+        )
+        .await
+    }
+
+    async fn sign_transaction_essence_with_chains(
+        &self,
+        prepared_transaction_data: &PreparedTransactionData,
+        time: u32,
+        chains: &[Bip44],
+    ) -> Result<Vec<Unlock>, Self::Error> {
+        self.sign_checked(
+            prepared_transaction_data,
+            time,
+            self.secret_manager
+                .sign_transaction_essence_with_chains(prepared_transaction_data, time, chains),
+        )
+        .await
+    }
+
+    async fn sign_transaction(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+    ) -> Result<TransactionPayload, Self::Error> {
+        // Signs through `sign_transaction_essence` of the policy, not the one of the wrapped secret manager.
+        super::default_sign_transaction(self, prepared_transaction_data).await
+    }
+}
+
+/// Config of a [`PolicySecretManager`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicySecretManagerConfig<C> {
+    /// The config of the secret manager signing the transactions.
+    pub secret_manager: C,
+    /// The enforced policy.
+    pub policy: SigningPolicyDto,
+    /// The file audit records are appended to.
+    #[serde(default)]
+    pub audit_file: Option<PathBuf>,
+}
+
+impl<S: SecretManagerConfig> SecretManagerConfig for PolicySecretManager<S>
+where
+    S::Error: Into<Error>,
+    S::Config: Serialize + DeserializeOwned,
+{
+    type Config = PolicySecretManagerConfig<S::Config>;
+
+    fn to_config(&self) -> Option<Self::Config> {
+        Some(PolicySecretManagerConfig {
+            secret_manager: self.secret_manager.to_config()?,
+            policy: SigningPolicyDto::from(&self.policy),
+            audit_file: self.audit_file.clone(),
+        })
+    }
+
+    fn from_config(config: &Self::Config) -> Result<Self, Self::Error> {
+        let secret_manager = Self::new(
+            S::from_config(&config.secret_manager).map_err(Into::into)?,
+            SigningPolicy::try_from(config.policy.clone())?,
+        );
+
+        match &config.audit_file {
+            Some(path) => secret_manager.with_audit_file(path),
+            None => Ok(secret_manager),
+        }
+    }
+}
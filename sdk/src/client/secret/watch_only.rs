@@ -0,0 +1,260 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of [`WatchOnlySecretManager`].
+//!
+//! Ed25519 keys are derived with SLIP-10, which only supports hardened derivation, so addresses can't be derived from
+//! an extended public key of an account. Accounts are exported with the public keys of their addresses instead, see
+//! [`WatchOnlySecretManager::export_account()`].
+
+use std::ops::Range;
+
+use async_trait::async_trait;
+use crypto::{
+    hashes::{blake2b::Blake2b256, Digest},
+    keys::bip44::Bip44,
+    signatures::{
+        ed25519::PublicKey,
+        secp256k1_ecdsa::{self, EvmAddress},
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use super::{GenerateAddressOptions, SecretManage, SecretManagerConfig};
+use crate::{
+    client::{api::PreparedTransactionData, Error},
+    types::block::{
+        address::Ed25519Address, payload::transaction::TransactionPayload, signature::Ed25519Signature, unlock::Unlocks,
+    },
+};
+
+/// The message whose hash is signed to export the public keys of an account.
+const EXPORT_MESSAGE: &[u8] = b"watch-only export";
+
+/// The public keys of the addresses of an account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WatchOnlyAccount {
+    /// The coin type of the account.
+    pub coin_type: u32,
+    /// The index of the account.
+    pub account_index: u32,
+    /// The public keys of the public addresses, by address index.
+    pub public_keys: Vec<PublicKey>,
+    /// The public keys of the internal addresses, by address index.
+    pub internal_public_keys: Vec<PublicKey>,
+}
+
+impl WatchOnlyAccount {
+    fn public_key(&self, internal: bool, address_index: u32) -> Option<&PublicKey> {
+        let public_keys = if internal {
+            &self.internal_public_keys
+        } else {
+            &self.public_keys
+        };
+
+        public_keys.get(address_index as usize)
+    }
+}
+
+/// Dto for a [`WatchOnlyAccount`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchOnlyAccountDto {
+    /// The coin type of the account.
+    pub coin_type: u32,
+    /// The index of the account.
+    pub account_index: u32,
+    /// The hex encoded public keys of the public addresses, by address index.
+    pub public_keys: Vec<String>,
+    /// The hex encoded public keys of the internal addresses, by address index.
+    #[serde(default)]
+    pub internal_public_keys: Vec<String>,
+}
+
+impl From<&WatchOnlyAccount> for WatchOnlyAccountDto {
+    fn from(value: &WatchOnlyAccount) -> Self {
+        let encode = |public_keys: &[PublicKey]| {
+            public_keys
+                .iter()
+                .map(|public_key| prefix_hex::encode(public_key.to_bytes()))
+                .collect()
+        };
+
+        Self {
+            coin_type: value.coin_type,
+            account_index: value.account_index,
+            public_keys: encode(&value.public_keys),
+            internal_public_keys: encode(&value.internal_public_keys),
+        }
+    }
+}
+
+impl TryFrom<&WatchOnlyAccountDto> for WatchOnlyAccount {
+    type Error = Error;
+
+    fn try_from(value: &WatchOnlyAccountDto) -> Result<Self, Self::Error> {
+        let decode = |public_keys: &[String]| {
+            public_keys
+                .iter()
+                .map(|public_key| Ok(PublicKey::try_from_bytes(prefix_hex::decode(public_key)?)?))
+                .collect::<Result<Vec<_>, Error>>()
+        };
+
+        Ok(Self {
+            coin_type: value.coin_type,
+            account_index: value.account_index,
+            public_keys: decode(&value.public_keys)?,
+            internal_public_keys: decode(&value.internal_public_keys)?,
+        })
+    }
+}
+
+/// Secret manager that only holds the public keys of the addresses of accounts.
+///
+/// It generates the addresses of the accounts, e.g. for an online wallet watching them, but can't sign.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WatchOnlySecretManager {
+    accounts: Vec<WatchOnlyAccount>,
+}
+
+impl WatchOnlySecretManager {
+    /// Creates a [`WatchOnlySecretManager`] from the public keys of accounts.
+    pub fn new(accounts: impl Into<Vec<WatchOnlyAccount>>) -> Self {
+        Self {
+            accounts: accounts.into(),
+        }
+    }
+
+    /// Returns the accounts.
+    pub fn accounts(&self) -> &[WatchOnlyAccount] {
+        &self.accounts
+    }
+
+    /// Exports the public keys of the first `address_count` public and internal addresses of an account with a secret
+    /// manager holding its keys.
+    ///
+    /// The public keys are taken from signatures of the hash of a fixed message, so hardware wallets may ask to confirm
+    /// them. Hashing it makes the message 32 bytes long, like the essence hashes a Ledger Nano signs.
+    pub async fn export_account<M: SecretManage>(
+        secret_manager: &M,
+        coin_type: u32,
+        account_index: u32,
+        address_count: u32,
+    ) -> Result<WatchOnlyAccount, M::Error> {
+        let message = Blake2b256::digest(EXPORT_MESSAGE);
+        let mut public_keys = Vec::new();
+        let mut internal_public_keys = Vec::new();
+
+        for internal in [false, true] {
+            for address_index in 0..address_count {
+                let chain = Bip44::new(coin_type)
+                    .with_account(account_index)
+                    .with_change(internal as _)
+                    .with_address_index(address_index);
+                let public_key = *secret_manager.sign_ed25519(&message, chain).await?.public_key();
+
+                if internal {
+                    internal_public_keys.push(public_key);
+                } else {
+                    public_keys.push(public_key);
+                }
+            }
+        }
+
+        Ok(WatchOnlyAccount {
+            coin_type,
+            account_index,
+            public_keys,
+            internal_public_keys,
+        })
+    }
+}
+
+#[async_trait]
+impl SecretManage for WatchOnlySecretManager {
+    type Error = Error;
+
+    async fn generate_ed25519_addresses(
+        &self,
+        coin_type: u32,
+        account_index: u32,
+        address_indexes: Range<u32>,
+        options: impl Into<Option<GenerateAddressOptions>> + Send,
+    ) -> Result<Vec<Ed25519Address>, Self::Error> {
+        let internal = options.into().map(|o| o.internal).unwrap_or_default();
+        let account = self
+            .accounts
+            .iter()
+            .find(|account| account.coin_type == coin_type && account.account_index == account_index);
+
+        address_indexes
+            .map(|address_index| {
+                let public_key = account
+                    .and_then(|account| account.public_key(internal, address_index))
+                    .ok_or(Error::MissingPublicKey {
+                        coin_type,
+                        account_index,
+                        internal,
+                        address_index,
+                    })?;
+
+                // Hash the public key to get the address
+                Ok(Ed25519Address::new(Blake2b256::digest(public_key.to_bytes()).into()))
+            })
+            .collect()
+    }
+
+    async fn generate_evm_addresses(
+        &self,
+        _coin_type: u32,
+        _account_index: u32,
+        _address_indexes: Range<u32>,
+        _options: impl Into<Option<GenerateAddressOptions>> + Send,
+    ) -> Result<Vec<EvmAddress>, Self::Error> {
+        Err(Error::WatchOnlySecretManager)
+    }
+
+    async fn sign_ed25519(&self, _msg: &[u8], _chain: Bip44) -> Result<Ed25519Signature, Self::Error> {
+        Err(Error::WatchOnlySecretManager)
+    }
+
+    async fn sign_secp256k1_ecdsa(
+        &self,
+        _msg: &[u8],
+        _chain: Bip44,
+    ) -> Result<(secp256k1_ecdsa::PublicKey, secp256k1_ecdsa::RecoverableSignature), Self::Error> {
+        Err(Error::WatchOnlySecretManager)
+    }
+
+    async fn sign_transaction_essence(
+        &self,
+        _prepared_transaction_data: &PreparedTransactionData,
+        _time: Option<u32>,
+    ) -> Result<Unlocks, Self::Error> {
+        Err(Error::WatchOnlySecretManager)
+    }
+
+    async fn sign_transaction(
+        &self,
+        _prepared_transaction_data: PreparedTransactionData,
+    ) -> Result<TransactionPayload, Self::Error> {
+        Err(Error::WatchOnlySecretManager)
+    }
+}
+
+impl SecretManagerConfig for WatchOnlySecretManager {
+    type Config = Vec<WatchOnlyAccountDto>;
+
+    fn to_config(&self) -> Option<Self::Config> {
+        Some(self.accounts.iter().map(WatchOnlyAccountDto::from).collect())
+    }
+
+    fn from_config(config: &Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            config
+                .iter()
+                .map(WatchOnlyAccount::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// SLIP-39 errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Crypto.rs error
+    #[error("{0}")]
+    Crypto(#[from] crypto::Error),
+    /// Not enough shares to recover the master secret
+    #[error("insufficient shares: {0}")]
+    InsufficientShares(String),
+    /// Invalid share checksum
+    #[error("invalid share checksum")]
+    InvalidChecksum,
+    /// The digest of a recovered secret doesn't match, the shares are invalid or don't belong together
+    #[error("invalid digest of the recovered secret")]
+    InvalidDigest,
+    /// Invalid master secret length
+    #[error("invalid master secret length {0}, must be an even number of at least 16 bytes")]
+    InvalidMasterSecretLength(usize),
+    /// Invalid share padding
+    #[error("invalid share padding")]
+    InvalidPadding,
+    /// Invalid splitting parameters
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(&'static str),
+    /// Invalid share length
+    #[error("invalid share length of {0} words")]
+    InvalidShareLength(usize),
+    /// Invalid share word
+    #[error("invalid share word {0}")]
+    InvalidWord(String),
+    /// Shares of different master secrets or with different parameters
+    #[error("shares mismatch: {0}")]
+    SharesMismatch(&'static str),
+}
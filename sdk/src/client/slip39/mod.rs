@@ -0,0 +1,486 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! [SLIP-39] Shamir's secret sharing of a master secret into mnemonic shares.
+//!
+//! The master secret is split into groups, a threshold of which is required to recover it. Each group is itself split
+//! into member shares, a threshold of which is required to recover the group. Every share is encoded as a mnemonic of
+//! the SLIP-39 wordlist, which is different from the BIP-39 one.
+//!
+//! [SLIP-39]: https://github.com/satoshilabs/slips/blob/master/slip-0039.md
+
+mod error;
+mod shamir;
+mod wordlist;
+
+use std::{collections::BTreeMap, num::NonZeroU32};
+
+use crypto::keys::pbkdf::PBKDF2_HMAC_SHA256;
+use zeroize::Zeroizing;
+
+pub use self::error::Error;
+use self::wordlist::WORDLIST;
+
+/// The default iteration exponent of the encryption of the master secret.
+pub const DEFAULT_ITERATION_EXPONENT: u8 = 1;
+
+/// The number of bits encoded by a word.
+const RADIX_BITS: usize = 10;
+/// The number of words of the identifier and share parameters.
+const HEADER_LENGTH_WORDS: usize = 4;
+/// The number of words of the checksum.
+const CHECKSUM_LENGTH_WORDS: usize = 3;
+/// The minimum length of a master secret.
+const MIN_MASTER_SECRET_LENGTH_BYTES: usize = 16;
+/// The minimum number of words of a share, for a master secret of minimum length.
+const MIN_SHARE_LENGTH_WORDS: usize =
+    HEADER_LENGTH_WORDS + CHECKSUM_LENGTH_WORDS + (MIN_MASTER_SECRET_LENGTH_BYTES * 8).div_ceil(RADIX_BITS);
+/// The maximum number of groups and of members of a group.
+const MAX_SHARE_COUNT: usize = 16;
+/// The maximum iteration exponent, encoded on 4 bits.
+const MAX_ITERATION_EXPONENT: u8 = 15;
+/// The number of PBKDF2 iterations of the encryption for an iteration exponent of 0.
+const BASE_ITERATION_COUNT: u32 = 10000;
+/// The number of rounds of the Feistel network of the encryption.
+const ROUND_COUNT: u8 = 4;
+/// The generator of the RS1024 checksum.
+const CHECKSUM_GENERATOR: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48, 0x21B1F890, 0x3F3F120,
+];
+
+/// The member threshold and count of a group of shares.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ShareGroup {
+    /// The number of member shares required to recover the group.
+    pub member_threshold: u8,
+    /// The number of member shares of the group.
+    pub member_count: u8,
+}
+
+impl ShareGroup {
+    /// Creates a new [`ShareGroup`].
+    pub fn new(member_threshold: u8, member_count: u8) -> Self {
+        Self {
+            member_threshold,
+            member_count,
+        }
+    }
+}
+
+/// Splits a master secret into mnemonic shares, by group.
+///
+/// `group_threshold` groups are required to recover the master secret, which is encrypted with the `passphrase` and
+/// `2^iteration_exponent * 10000` PBKDF2 iterations beforehand.
+pub fn split_master_secret(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    group_threshold: u8,
+    groups: &[ShareGroup],
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<Zeroizing<String>>>, Error> {
+    if master_secret.len() < MIN_MASTER_SECRET_LENGTH_BYTES || !master_secret.len().is_multiple_of(2) {
+        return Err(Error::InvalidMasterSecretLength(master_secret.len()));
+    }
+    if groups.is_empty() || groups.len() > MAX_SHARE_COUNT {
+        return Err(Error::InvalidParameters(
+            "the number of groups must be between 1 and 16",
+        ));
+    }
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(Error::InvalidParameters(
+            "the group threshold must be between 1 and the number of groups",
+        ));
+    }
+    for group in groups {
+        if group.member_count as usize > MAX_SHARE_COUNT
+            || group.member_threshold == 0
+            || group.member_threshold > group.member_count
+        {
+            return Err(Error::InvalidParameters(
+                "the member threshold must be between 1 and the member count, which can't exceed 16",
+            ));
+        }
+        if group.member_threshold == 1 && group.member_count > 1 {
+            return Err(Error::InvalidParameters(
+                "a group with a member threshold of 1 must have a single member",
+            ));
+        }
+    }
+    if iteration_exponent > MAX_ITERATION_EXPONENT {
+        return Err(Error::InvalidParameters("the iteration exponent can't exceed 15"));
+    }
+
+    let mut identifier = [0; 2];
+    crypto::utils::rand::fill(&mut identifier)?;
+    let identifier = u16::from_be_bytes(identifier) & 0x7FFF;
+    let encrypted_master_secret = encrypt(master_secret, passphrase, iteration_exponent, identifier, true);
+    let group_secrets = shamir::split_secret(group_threshold, groups.len() as u8, &encrypted_master_secret)?;
+
+    group_secrets
+        .into_iter()
+        .zip(groups)
+        .map(|((group_index, group_secret), group)| {
+            Ok(
+                shamir::split_secret(group.member_threshold, group.member_count, &group_secret)?
+                    .into_iter()
+                    .map(|(member_index, value)| {
+                        Share {
+                            identifier,
+                            extendable: true,
+                            iteration_exponent,
+                            group_index,
+                            group_threshold,
+                            group_count: groups.len() as u8,
+                            member_index,
+                            member_threshold: group.member_threshold,
+                            value,
+                        }
+                        .to_mnemonic()
+                    })
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Recovers a master secret from mnemonic shares, decrypting it with the `passphrase`.
+///
+/// Shares beyond the group and member thresholds are ignored, as well as incomplete groups.
+pub fn recover_master_secret(shares: &[impl AsRef<str>], passphrase: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let shares = shares
+        .iter()
+        .map(|share| Share::from_mnemonic(share.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let first = shares
+        .first()
+        .ok_or_else(|| Error::InsufficientShares("no shares provided".to_string()))?;
+
+    if shares.iter().any(|share| {
+        share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exponent != first.iteration_exponent
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.len() != first.value.len()
+    }) {
+        return Err(Error::SharesMismatch(
+            "all shares must have the same identifier, iteration exponent, group parameters and length",
+        ));
+    }
+
+    let mut groups = BTreeMap::<u8, Vec<&Share>>::new();
+    for share in &shares {
+        let members = groups.entry(share.group_index).or_default();
+        if let Some(member) = members.first() {
+            if member.member_threshold != share.member_threshold {
+                return Err(Error::SharesMismatch(
+                    "all shares of a group must have the same member threshold",
+                ));
+            }
+        }
+        match members.iter().find(|member| member.member_index == share.member_index) {
+            Some(member) if member.value != share.value => {
+                return Err(Error::SharesMismatch("shares with the same index must be identical"));
+            }
+            Some(_) => {}
+            None => members.push(share),
+        }
+    }
+
+    let complete_groups = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() >= members[0].member_threshold as usize)
+        .take(first.group_threshold as usize)
+        .collect::<Vec<_>>();
+    if complete_groups.len() < first.group_threshold as usize {
+        return Err(Error::InsufficientShares(format!(
+            "{} complete groups out of the {} required",
+            complete_groups.len(),
+            first.group_threshold
+        )));
+    }
+
+    let group_secrets = complete_groups
+        .iter()
+        .map(|(group_index, members)| {
+            let member_threshold = members[0].member_threshold;
+            let members = members
+                .iter()
+                .take(member_threshold as usize)
+                .map(|member| (member.member_index, member.value.as_slice()))
+                .collect::<Vec<_>>();
+
+            Ok((*group_index, shamir::recover_secret(member_threshold, &members)?))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let group_secrets = group_secrets
+        .iter()
+        .map(|(group_index, group_secret)| (*group_index, group_secret.as_slice()))
+        .collect::<Vec<_>>();
+    let encrypted_master_secret = shamir::recover_secret(first.group_threshold, &group_secrets)?;
+
+    Ok(decrypt(
+        &encrypted_master_secret,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+    ))
+}
+
+/// A member share of a group.
+struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    fn to_mnemonic(&self) -> Zeroizing<String> {
+        let header = (self.identifier as u64) << 25
+            | (self.extendable as u64) << 24
+            | (self.iteration_exponent as u64) << 20
+            | (self.group_index as u64) << 16
+            | (self.group_threshold as u64 - 1) << 12
+            | (self.group_count as u64 - 1) << 8
+            | (self.member_index as u64) << 4
+            | (self.member_threshold as u64 - 1);
+        let mut words = Zeroizing::new(
+            (0..HEADER_LENGTH_WORDS)
+                .rev()
+                .map(|i| ((header >> (i * RADIX_BITS)) & 0x3FF) as u16)
+                .collect::<Vec<_>>(),
+        );
+        let value_length_words = (self.value.len() * 8).div_ceil(RADIX_BITS);
+        words.extend_from_slice(&bytes_to_words(
+            value_length_words * RADIX_BITS - self.value.len() * 8,
+            &self.value,
+        ));
+
+        let checksum = checksum_polymod(self.extendable, words.iter().copied().chain([0; CHECKSUM_LENGTH_WORDS])) ^ 1;
+        words.extend(
+            (0..CHECKSUM_LENGTH_WORDS)
+                .rev()
+                .map(|i| ((checksum >> (i * RADIX_BITS)) & 0x3FF) as u16),
+        );
+
+        Zeroizing::new(
+            words
+                .iter()
+                .map(|word| WORDLIST[*word as usize])
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    fn from_mnemonic(mnemonic: &str) -> Result<Self, Error> {
+        let words = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                WORDLIST
+                    .binary_search(&word.to_lowercase().as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| Error::InvalidWord(word.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Zeroizing::new)?;
+
+        if words.len() < MIN_SHARE_LENGTH_WORDS {
+            return Err(Error::InvalidShareLength(words.len()));
+        }
+        let value_length_words = words.len() - HEADER_LENGTH_WORDS - CHECKSUM_LENGTH_WORDS;
+        let padding = value_length_words * RADIX_BITS % 16;
+        if padding > 8 {
+            return Err(Error::InvalidShareLength(words.len()));
+        }
+
+        let header = words[..HEADER_LENGTH_WORDS]
+            .iter()
+            .fold(0u64, |header, word| header << RADIX_BITS | *word as u64);
+        let extendable = (header >> 24) & 1 == 1;
+        if checksum_polymod(extendable, words.iter().copied()) != 1 {
+            return Err(Error::InvalidChecksum);
+        }
+
+        let share = Self {
+            identifier: (header >> 25) as u16,
+            extendable,
+            iteration_exponent: ((header >> 20) & 0xF) as u8,
+            group_index: ((header >> 16) & 0xF) as u8,
+            group_threshold: ((header >> 12) & 0xF) as u8 + 1,
+            group_count: ((header >> 8) & 0xF) as u8 + 1,
+            member_index: ((header >> 4) & 0xF) as u8,
+            member_threshold: (header & 0xF) as u8 + 1,
+            value: words_to_bytes(
+                padding,
+                &words[HEADER_LENGTH_WORDS..words.len() - CHECKSUM_LENGTH_WORDS],
+            )?,
+        };
+        if share.group_threshold > share.group_count {
+            return Err(Error::InvalidParameters(
+                "the group threshold can't exceed the number of groups",
+            ));
+        }
+
+        Ok(share)
+    }
+}
+
+// Encodes bytes as words, preceded by `padding` zero bits.
+fn bytes_to_words(padding: usize, bytes: &[u8]) -> Zeroizing<Vec<u16>> {
+    let mut words = Zeroizing::new(Vec::new());
+    let mut accumulator = 0u32;
+    let mut accumulator_bits = padding;
+
+    for byte in bytes {
+        accumulator = accumulator << 8 | *byte as u32;
+        accumulator_bits += 8;
+        if accumulator_bits >= RADIX_BITS {
+            accumulator_bits -= RADIX_BITS;
+            words.push((accumulator >> accumulator_bits) as u16 & 0x3FF);
+            accumulator &= (1 << accumulator_bits) - 1;
+        }
+    }
+
+    words
+}
+
+// Decodes words to bytes, skipping `padding` leading bits that must be zero.
+fn words_to_bytes(padding: usize, words: &[u16]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let mut bytes = Zeroizing::new(Vec::new());
+    let mut accumulator = 0u32;
+    let mut accumulator_bits = 0;
+    let mut padding = padding;
+
+    for word in words {
+        accumulator = accumulator << RADIX_BITS | *word as u32;
+        accumulator_bits += RADIX_BITS;
+        if padding > 0 {
+            accumulator_bits -= padding;
+            if accumulator >> accumulator_bits != 0 {
+                return Err(Error::InvalidPadding);
+            }
+            padding = 0;
+        }
+        while accumulator_bits >= 8 {
+            accumulator_bits -= 8;
+            bytes.push((accumulator >> accumulator_bits) as u8);
+            accumulator &= (1 << accumulator_bits) - 1;
+        }
+    }
+
+    Ok(bytes)
+}
+
+// Computes the RS1024 polymod of the words, customized by the extendable flag.
+fn checksum_polymod(extendable: bool, words: impl Iterator<Item = u16>) -> u32 {
+    let customization: &[u8] = if extendable { b"shamir_extendable" } else { b"shamir" };
+
+    customization
+        .iter()
+        .map(|byte| *byte as u32)
+        .chain(words.map(u32::from))
+        .fold(1, |checksum, value| {
+            let top = checksum >> 20;
+            CHECKSUM_GENERATOR.iter().enumerate().fold(
+                (checksum & 0xFFFFF) << RADIX_BITS ^ value,
+                |checksum, (i, generator)| {
+                    if (top >> i) & 1 == 1 {
+                        checksum ^ generator
+                    } else {
+                        checksum
+                    }
+                },
+            )
+        })
+}
+
+// The round function of the Feistel network.
+fn round_function(
+    round: u8,
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    salt_prefix: &[u8],
+    value: &[u8],
+) -> Zeroizing<Vec<u8>> {
+    let password = Zeroizing::new([&[round], passphrase].concat());
+    let salt = [salt_prefix, value].concat();
+    let mut output = Zeroizing::new(vec![0; value.len()]);
+    // PANIC: the iteration count is at least 2500.
+    let iterations = NonZeroU32::new((BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32).unwrap();
+
+    PBKDF2_HMAC_SHA256(&password, &salt, iterations, &mut output);
+
+    output
+}
+
+fn salt_prefix(identifier: u16, extendable: bool) -> Vec<u8> {
+    if extendable {
+        Vec::new()
+    } else {
+        [b"shamir".as_slice(), &identifier.to_be_bytes()].concat()
+    }
+}
+
+fn feistel(
+    value: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Zeroizing<Vec<u8>> {
+    let salt_prefix = salt_prefix(identifier, extendable);
+    let (left, right) = value.split_at(value.len() / 2);
+    let mut left = Zeroizing::new(left.to_vec());
+    let mut right = Zeroizing::new(right.to_vec());
+
+    for round in rounds {
+        let mut next = round_function(round, passphrase, iteration_exponent, &salt_prefix, &right);
+        next.iter_mut().zip(left.iter()).for_each(|(next, left)| *next ^= left);
+        left = std::mem::replace(&mut right, next);
+    }
+
+    right.extend_from_slice(&left);
+    right
+}
+
+fn encrypt(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Zeroizing<Vec<u8>> {
+    feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        0..ROUND_COUNT,
+    )
+}
+
+fn decrypt(
+    encrypted_master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Zeroizing<Vec<u8>> {
+    feistel(
+        encrypted_master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
@@ -0,0 +1,132 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shamir's secret sharing over GF(256), as specified by SLIP-39.
+
+use crypto::macs::hmac::HMAC_SHA256;
+use zeroize::Zeroizing;
+
+use super::Error;
+
+/// The length of the digest of the shared secret.
+const DIGEST_LENGTH_BYTES: usize = 4;
+/// The x coordinate of the share holding the digest.
+const DIGEST_INDEX: u8 = 254;
+/// The x coordinate of the share holding the secret.
+const SECRET_INDEX: u8 = 255;
+
+pub(super) type Share = (u8, Zeroizing<Vec<u8>>);
+
+// Exponentiation and logarithm tables of GF(256) with the Rijndael polynomial and generator 3.
+fn tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0; 255];
+    let mut log = [0; 256];
+    let mut poly = 1u16;
+
+    for (i, exp) in exp.iter_mut().enumerate() {
+        *exp = poly as u8;
+        log[poly as usize] = i as u8;
+        // Multiply by the generator x + 1.
+        poly = (poly << 1) ^ poly;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11B;
+        }
+    }
+
+    (exp, log)
+}
+
+fn random_bytes(length: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let mut bytes = Zeroizing::new(vec![0; length]);
+    crypto::utils::rand::fill(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn digest(random_part: &[u8], shared_secret: &[u8]) -> [u8; DIGEST_LENGTH_BYTES] {
+    let mut mac = [0; 32];
+    HMAC_SHA256(shared_secret, random_part, &mut mac);
+    // PANIC: the mac is longer than the digest.
+    mac[..DIGEST_LENGTH_BYTES].try_into().unwrap()
+}
+
+// Evaluates at `x` the polynomial going through the shares, which have distinct x coordinates and values of the same
+// length.
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> Zeroizing<Vec<u8>> {
+    if let Some((_, value)) = shares.iter().find(|(share_x, _)| *share_x == x) {
+        return Zeroizing::new(value.to_vec());
+    }
+
+    let (exp, log) = tables();
+    let log_product = shares
+        .iter()
+        .map(|(share_x, _)| log[(share_x ^ x) as usize] as i32)
+        .sum::<i32>();
+    let mut result = Zeroizing::new(vec![0; shares[0].1.len()]);
+
+    for (share_x, value) in shares {
+        let log_basis = (log_product
+            - log[(share_x ^ x) as usize] as i32
+            - shares
+                .iter()
+                .filter(|(other_x, _)| other_x != share_x)
+                .map(|(other_x, _)| log[(share_x ^ other_x) as usize] as i32)
+                .sum::<i32>())
+        .rem_euclid(255);
+
+        for (result, byte) in result.iter_mut().zip(value.iter()) {
+            if *byte != 0 {
+                *result ^= exp[((log[*byte as usize] as i32 + log_basis) % 255) as usize];
+            }
+        }
+    }
+
+    result
+}
+
+/// Splits a secret into `share_count` shares, `threshold` of which recover it.
+pub(super) fn split_secret(threshold: u8, share_count: u8, shared_secret: &[u8]) -> Result<Vec<Share>, Error> {
+    if threshold == 1 {
+        return Ok((0..share_count)
+            .map(|index| (index, Zeroizing::new(shared_secret.to_vec())))
+            .collect());
+    }
+
+    let random_share_count = threshold - 2;
+    let mut shares = (0..random_share_count)
+        .map(|index| Ok((index, random_bytes(shared_secret.len())?)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let random_part = random_bytes(shared_secret.len() - DIGEST_LENGTH_BYTES)?;
+    let mut digest_share = Zeroizing::new(digest(&random_part, shared_secret).to_vec());
+    digest_share.extend_from_slice(&random_part);
+
+    let mut base_shares = shares
+        .iter()
+        .map(|(index, value)| (*index, value.as_slice()))
+        .collect::<Vec<_>>();
+    base_shares.push((DIGEST_INDEX, &digest_share));
+    base_shares.push((SECRET_INDEX, shared_secret));
+
+    let interpolated_shares = (random_share_count..share_count)
+        .map(|index| (index, interpolate(&base_shares, index)))
+        .collect::<Vec<_>>();
+    shares.extend(interpolated_shares);
+
+    Ok(shares)
+}
+
+/// Recovers a secret from `threshold` shares and checks its digest.
+pub(super) fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    if threshold == 1 {
+        return Ok(Zeroizing::new(shares[0].1.to_vec()));
+    }
+
+    let shared_secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    let (expected_digest, random_part) = digest_share.split_at(DIGEST_LENGTH_BYTES);
+
+    if digest(random_part, &shared_secret) != expected_digest {
+        return Err(Error::InvalidDigest);
+    }
+
+    Ok(shared_secret)
+}
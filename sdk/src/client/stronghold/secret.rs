@@ -455,6 +455,17 @@ impl StrongholdAdapter {
 
     /// Store a mnemonic into the Stronghold vault.
     pub async fn store_mnemonic(&self, mnemonic: impl Borrow<MnemonicRef> + Send) -> Result<(), Error> {
+        self.store_mnemonic_with_passphrase(mnemonic, Passphrase::default())
+            .await
+    }
+
+    /// Store a mnemonic into the Stronghold vault, deriving the seed with a BIP-39 passphrase. Only the seed is stored,
+    /// so the passphrase isn't needed afterwards.
+    pub async fn store_mnemonic_with_passphrase(
+        &self,
+        mnemonic: impl Borrow<MnemonicRef> + Send,
+        passphrase: impl Into<Passphrase> + Send,
+    ) -> Result<(), Error> {
         // The key needs to be supplied first.
         if self.key_provider.lock().await.is_none() {
             return Err(Error::KeyCleared);
@@ -482,8 +493,7 @@ impl StrongholdAdapter {
         }
 
         // Execute the BIP-39 recovery procedure to put it into the vault (in memory).
-        self.bip39_recover(trimmed_mnemonic, Passphrase::default(), output)
-            .await?;
+        self.bip39_recover(trimmed_mnemonic, passphrase.into(), output).await?;
 
         // Persist Stronghold to the disk
         self.write_stronghold_snapshot(None).await?;
@@ -534,6 +544,50 @@ mod tests {
         std::fs::remove_file(stronghold_path).ok();
     }
 
+    #[tokio::test]
+    async fn test_ed25519_address_generation_with_passphrase() {
+        let stronghold_path = "test_ed25519_address_generation_with_passphrase.stronghold";
+        // Remove potential old stronghold file
+        std::fs::remove_file(stronghold_path).ok();
+        let mnemonic = "giant dynamic museum toddler six deny defense ostrich bomb access mercy blood explain muscle shoot shallow glad autumn author calm heavy hawk abuse rally";
+        let stronghold_adapter = StrongholdAdapter::builder()
+            .password("drowssap".to_owned())
+            .build(stronghold_path)
+            .unwrap();
+
+        stronghold_adapter
+            .store_mnemonic_with_passphrase(Mnemonic::from(mnemonic.to_owned()), "passphrase")
+            .await
+            .unwrap();
+
+        let addresses = stronghold_adapter
+            .generate_ed25519_addresses(IOTA_COIN_TYPE, 0, 0..1, None)
+            .await
+            .unwrap();
+
+        // The same seed is derived as by the mnemonic secret manager.
+        let mnemonic_secret_manager =
+            crate::client::secret::mnemonic::MnemonicSecretManager::try_from_mnemonic_with_passphrase(
+                mnemonic.to_owned(),
+                "passphrase",
+            )
+            .unwrap();
+        assert_eq!(
+            addresses,
+            mnemonic_secret_manager
+                .generate_ed25519_addresses(IOTA_COIN_TYPE, 0, 0..1, None)
+                .await
+                .unwrap()
+        );
+        assert_ne!(
+            addresses[0].to_bech32_unchecked("atoi"),
+            "atoi1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluehe53e"
+        );
+
+        // Remove garbage after test, but don't care about the result
+        std::fs::remove_file(stronghold_path).ok();
+    }
+
     #[tokio::test]
     async fn test_evm_address_generation() {
         let stronghold_path = "test_evm_address_generation.stronghold";
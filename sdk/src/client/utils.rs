@@ -8,7 +8,7 @@ use std::collections::HashMap;
 
 use crypto::{
     hashes::{blake2b::Blake2b256, Digest},
-    keys::bip39::{wordlist, Mnemonic, MnemonicRef, Passphrase, Seed},
+    keys::bip39::{wordlist, Mnemonic, MnemonicRef, Passphrase, PassphraseRef, Seed},
     utils,
 };
 use serde::{Deserialize, Serialize};
@@ -16,7 +16,10 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use super::{Client, ClientInner};
 use crate::{
-    client::{Error, Result},
+    client::{
+        slip39::{self, ShareGroup},
+        Error, Result,
+    },
     types::block::{
         address::{Address, Bech32Address, Ed25519Address, Hrp, ToBech32Ext},
         output::{AliasId, NftId},
@@ -68,11 +71,19 @@ pub fn mnemonic_to_hex_seed(mnemonic: impl Borrow<MnemonicRef>) -> Result<String
 
 /// Returns a seed for a mnemonic.
 pub fn mnemonic_to_seed(mnemonic: impl Borrow<MnemonicRef>) -> Result<Seed> {
+    mnemonic_to_seed_with_passphrase(mnemonic, Passphrase::default())
+}
+
+/// Returns a seed for a mnemonic and a BIP-39 passphrase.
+pub fn mnemonic_to_seed_with_passphrase(
+    mnemonic: impl Borrow<MnemonicRef>,
+    passphrase: impl Borrow<PassphraseRef>,
+) -> Result<Seed> {
     // first we check if the mnemonic is valid to give meaningful errors
     verify_mnemonic(mnemonic.borrow())?;
     Ok(crypto::keys::bip39::mnemonic_to_seed(
         mnemonic.borrow(),
-        &Passphrase::default(),
+        passphrase.borrow(),
     ))
 }
 
@@ -83,6 +94,40 @@ pub fn verify_mnemonic(mnemonic: impl Borrow<MnemonicRef>) -> Result<()> {
     Ok(())
 }
 
+/// Splits a mnemonic into SLIP-39 mnemonic shares, by group.
+///
+/// The entropy of the mnemonic is shared without SLIP-39 passphrase, `group_threshold` groups are required to recover
+/// it with [`combine_mnemonic_shares()`].
+pub fn split_mnemonic(
+    mnemonic: impl Borrow<MnemonicRef>,
+    group_threshold: u8,
+    groups: &[ShareGroup],
+) -> Result<Vec<Vec<Mnemonic>>> {
+    verify_mnemonic(mnemonic.borrow())?;
+    let entropy = wordlist::decode(mnemonic.borrow(), &crypto::keys::bip39::wordlist::ENGLISH)
+        .map_err(|e| crate::client::Error::InvalidMnemonic(format!("{e:?}")))?;
+
+    Ok(slip39::split_master_secret(
+        &entropy,
+        &[],
+        group_threshold,
+        groups,
+        slip39::DEFAULT_ITERATION_EXPONENT,
+    )?
+    .into_iter()
+    .map(|group| group.into_iter().map(Mnemonic::from).collect())
+    .collect())
+}
+
+/// Recovers a mnemonic from SLIP-39 mnemonic shares created with [`split_mnemonic()`].
+pub fn combine_mnemonic_shares(shares: &[impl Borrow<MnemonicRef>]) -> Result<Mnemonic> {
+    let shares = shares.iter().map(|share| &**share.borrow()).collect::<Vec<_>>();
+    let entropy = slip39::recover_master_secret(&shares, &[])?;
+
+    wordlist::encode(&entropy, &crypto::keys::bip39::wordlist::ENGLISH)
+        .map_err(|e| crate::client::Error::InvalidMnemonic(format!("{e:?}")))
+}
+
 /// Requests funds from a faucet
 pub async fn request_funds_from_faucet(url: &str, bech32_address: &Bech32Address) -> Result<String> {
     let mut map = HashMap::new();
@@ -167,11 +212,33 @@ impl Client {
         mnemonic_to_seed(mnemonic)
     }
 
+    /// Returns a seed for a mnemonic and a BIP-39 passphrase.
+    pub fn mnemonic_to_seed_with_passphrase(
+        mnemonic: impl Borrow<MnemonicRef>,
+        passphrase: impl Borrow<PassphraseRef>,
+    ) -> Result<Seed> {
+        mnemonic_to_seed_with_passphrase(mnemonic, passphrase)
+    }
+
     /// Returns a hex encoded seed for a mnemonic.
     pub fn mnemonic_to_hex_seed(mnemonic: impl Borrow<MnemonicRef>) -> Result<String> {
         mnemonic_to_hex_seed(mnemonic)
     }
 
+    /// Splits a mnemonic into SLIP-39 mnemonic shares, by group.
+    pub fn split_mnemonic(
+        mnemonic: impl Borrow<MnemonicRef>,
+        group_threshold: u8,
+        groups: &[ShareGroup],
+    ) -> Result<Vec<Vec<Mnemonic>>> {
+        split_mnemonic(mnemonic, group_threshold, groups)
+    }
+
+    /// Recovers a mnemonic from SLIP-39 mnemonic shares.
+    pub fn combine_mnemonic_shares(shares: &[impl Borrow<MnemonicRef>]) -> Result<Mnemonic> {
+        combine_mnemonic_shares(shares)
+    }
+
     /// UTF-8 encodes the `tag` of a given TaggedDataPayload.
     pub fn tag_to_utf8(payload: &TaggedDataPayload) -> Result<String> {
         String::from_utf8(payload.tag().to_vec()).map_err(|_| Error::TaggedData("found invalid UTF-8".to_string()))
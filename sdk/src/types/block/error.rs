@@ -16,10 +16,12 @@ use crate::types::block::{
     },
     parent::ParentCount,
     payload::{
-        milestone::BinaryParametersLength, InputCount, MilestoneMetadataLength, MilestoneOptionCount, OutputCount,
-        ReceiptFundsCount, SignatureCount, TagLength, TaggedDataLength,
+        milestone::{BinaryParametersLength, MerkleRoot},
+        InputCount, MilestoneMetadataLength, MilestoneOptionCount, OutputCount, ReceiptFundsCount, SignatureCount,
+        TagLength, TaggedDataLength,
     },
     unlock::{UnlockCount, UnlockIndex},
+    BlockId,
 };
 
 /// Error occurring when creating/parsing/validating blocks.
@@ -60,6 +62,7 @@ pub enum Error {
     InvalidMilestoneMetadataLength(<MilestoneMetadataLength as TryFrom<usize>>::Error),
     InvalidMilestoneOptionCount(<MilestoneOptionCount as TryFrom<usize>>::Error),
     InvalidMilestoneOptionKind(u8),
+    InvalidMerkleProofIndex(usize),
     InvalidMigratedFundsEntryAmount(u64),
     InvalidNativeTokenCount(<NativeTokenCount as TryFrom<usize>>::Error),
     InvalidNetworkName(FromUtf8Error),
@@ -92,12 +95,14 @@ pub enum Error {
     InvalidUnlockConditionCount(<UnlockConditionCount as TryFrom<usize>>::Error),
     InvalidUnlockConditionKind(u8),
     InvalidFoundryZeroSerialNumber,
+    MilestoneInclusionMerkleRootMismatch { expected: MerkleRoot, found: MerkleRoot },
     MilestoneInvalidSignatureCount(<SignatureCount as TryFrom<usize>>::Error),
     MilestonePublicKeysSignaturesCountMismatch { key_count: usize, sig_count: usize },
     MilestoneOptionsNotUniqueSorted,
     MilestoneSignaturesNotUniqueSorted,
     MissingAddressUnlockCondition,
     MissingGovernorUnlockCondition,
+    MissingIncludedBlock(BlockId),
     MissingStateControllerUnlockCondition,
     NativeTokensNotUniqueSorted,
     NativeTokensNullAmount,
@@ -201,6 +206,7 @@ impl fmt::Display for Error {
             }
             Self::InvalidMilestoneOptionCount(count) => write!(f, "invalid milestone option count: {count}"),
             Self::InvalidMilestoneOptionKind(k) => write!(f, "invalid milestone option kind: {k}"),
+            Self::InvalidMerkleProofIndex(index) => write!(f, "invalid merkle proof index: {index}"),
             Self::InvalidMigratedFundsEntryAmount(amount) => {
                 write!(f, "invalid migrated funds entry amount: {amount}")
             }
@@ -253,6 +259,12 @@ impl fmt::Display for Error {
             Self::InvalidUnlockConditionCount(count) => write!(f, "invalid unlock condition count: {count}"),
             Self::InvalidUnlockConditionKind(k) => write!(f, "invalid unlock condition kind: {k}"),
             Self::InvalidFoundryZeroSerialNumber => write!(f, "invalid foundry zero serial number"),
+            Self::MilestoneInclusionMerkleRootMismatch { expected, found } => {
+                write!(
+                    f,
+                    "milestone inclusion merkle root mismatch: expected {expected}, found {found}"
+                )
+            }
             Self::MilestoneInvalidSignatureCount(count) => {
                 write!(f, "invalid milestone signature count: {count}")
             }
@@ -270,6 +282,7 @@ impl fmt::Display for Error {
             }
             Self::MissingAddressUnlockCondition => write!(f, "missing address unlock condition"),
             Self::MissingGovernorUnlockCondition => write!(f, "missing governor unlock condition"),
+            Self::MissingIncludedBlock(block_id) => write!(f, "block {block_id} isn't included in the milestone"),
             Self::MissingStateControllerUnlockCondition => write!(f, "missing state controller unlock condition"),
             Self::NativeTokensNotUniqueSorted => write!(f, "native tokens are not unique and/or sorted"),
             Self::NativeTokensNullAmount => write!(f, "native tokens null amount"),
@@ -112,6 +112,12 @@ impl OutputMetadata {
     pub fn ledger_index(&self) -> u32 {
         self.ledger_index
     }
+
+    /// Sets the ledger index of the [`OutputMetadata`].
+    #[cfg(feature = "client")]
+    pub(crate) fn set_ledger_index(&mut self, ledger_index: u32) {
+        self.ledger_index = ledger_index;
+    }
 }
 
 #[cfg(feature = "serde")]
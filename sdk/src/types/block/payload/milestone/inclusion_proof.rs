@@ -0,0 +1,105 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::block::{
+    payload::milestone::{MerkleHasher, MerkleProof, MilestonePayload},
+    Block, BlockId, Error,
+};
+
+/// A self-contained proof that a block was included in a milestone, which can be verified without a node.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InclusionProof {
+    milestone: MilestonePayload,
+    block: Block,
+    proof: MerkleProof,
+}
+
+impl InclusionProof {
+    /// Creates a new [`InclusionProof`] of a block from the ids of the blocks included in the milestone, in white flag
+    /// order.
+    pub fn new(milestone: MilestonePayload, block: Block, included_block_ids: &[BlockId]) -> Result<Self, Error> {
+        let expected = *milestone.essence().inclusion_merkle_root();
+        let found = MerkleHasher::digest(included_block_ids);
+
+        if expected != found {
+            return Err(Error::MilestoneInclusionMerkleRootMismatch { expected, found });
+        }
+
+        let block_id = block.id();
+        let index = included_block_ids
+            .iter()
+            .position(|included_block_id| *included_block_id == block_id)
+            .ok_or(Error::MissingIncludedBlock(block_id))?;
+        let proof = MerkleProof::new(included_block_ids, index)?;
+
+        Ok(Self {
+            milestone,
+            block,
+            proof,
+        })
+    }
+
+    /// Returns the milestone the block was included in.
+    pub fn milestone(&self) -> &MilestonePayload {
+        &self.milestone
+    }
+
+    /// Returns the included block.
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// Returns the audit path of the block in the inclusion Merkle tree of the milestone.
+    pub fn proof(&self) -> &MerkleProof {
+        &self.proof
+    }
+
+    /// Verifies that the audit path proves the block and leads to the inclusion Merkle root of the milestone.
+    /// The milestone itself isn't verified, see [`MilestonePayload::validate()`].
+    pub fn verify(&self) -> bool {
+        self.proof.contains(&self.block.id()) && self.proof.root() == *self.milestone.essence().inclusion_merkle_root()
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) mod dto {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::types::{
+        block::{payload::milestone::dto::MilestonePayloadDto, BlockDto},
+        TryFromDto, ValidationParams,
+    };
+
+    /// A self-contained proof that a block was included in a milestone.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct InclusionProofDto {
+        pub milestone: MilestonePayloadDto,
+        pub block: BlockDto,
+        pub proof: MerkleProof,
+    }
+
+    impl From<&InclusionProof> for InclusionProofDto {
+        fn from(value: &InclusionProof) -> Self {
+            Self {
+                milestone: value.milestone().into(),
+                block: value.block().into(),
+                proof: value.proof().clone(),
+            }
+        }
+    }
+
+    impl TryFromDto for InclusionProof {
+        type Dto = InclusionProofDto;
+        type Error = Error;
+
+        fn try_from_dto_with_params_inner(dto: Self::Dto, params: ValidationParams<'_>) -> Result<Self, Self::Error> {
+            Ok(Self {
+                milestone: MilestonePayload::try_from_dto_with_params_inner(dto.milestone, params.clone())?,
+                block: Block::try_from_dto_with_params_inner(dto.block, params)?,
+                proof: dto.proof,
+            })
+        }
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::types::block::Error;
+use alloc::boxed::Box;
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+
+use crate::types::block::{BlockId, Error};
 
 /// A Merkle root of a list of hashes.
 #[derive(Clone, Copy, Eq, PartialEq, packable::Packable, derive_more::From, derive_more::AsRef)]
@@ -50,3 +54,136 @@ impl core::str::FromStr for MerkleRoot {
         Ok(Self::new(prefix_hex::decode(s).map_err(Error::Hex)?))
     }
 }
+
+/// Computes Merkle roots of lists of block ids as defined in
+/// <https://github.com/iotaledger/tips/blob/main/tips/TIP-0004/tip-0004.md>.
+pub struct MerkleHasher;
+
+impl MerkleHasher {
+    /// Prefix of the hashes of leaves.
+    const LEAF_HASH_PREFIX: u8 = 0;
+    /// Prefix of the hashes of inner nodes.
+    const NODE_HASH_PREFIX: u8 = 1;
+
+    /// Computes the Merkle root of a list of block ids.
+    pub fn digest(block_ids: &[BlockId]) -> MerkleRoot {
+        match block_ids {
+            [] => MerkleRoot::from(<[u8; MerkleRoot::LENGTH]>::from(Blake2b256::digest([]))),
+            [block_id] => Self::leaf(block_id),
+            _ => {
+                let (left, right) = block_ids.split_at(Self::split_index(block_ids.len()));
+                Self::node(&Self::digest(left), &Self::digest(right))
+            }
+        }
+    }
+
+    fn leaf(block_id: &BlockId) -> MerkleRoot {
+        MerkleRoot::from(<[u8; MerkleRoot::LENGTH]>::from(
+            Blake2b256::new()
+                .chain_update([Self::LEAF_HASH_PREFIX])
+                .chain_update(block_id)
+                .finalize(),
+        ))
+    }
+
+    fn node(left: &MerkleRoot, right: &MerkleRoot) -> MerkleRoot {
+        MerkleRoot::from(<[u8; MerkleRoot::LENGTH]>::from(
+            Blake2b256::new()
+                .chain_update([Self::NODE_HASH_PREFIX])
+                .chain_update(**left)
+                .chain_update(**right)
+                .finalize(),
+        ))
+    }
+
+    /// The largest power of two smaller than `len`, where the leaves of a tree are split into its two subtrees.
+    fn split_index(len: usize) -> usize {
+        len.next_power_of_two() / 2
+    }
+}
+
+/// An audit path proving that a block id is a leaf of a Merkle tree, made of the hashes of the subtrees next to the
+/// path from the root to the leaf.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(untagged))]
+pub enum MerkleProof {
+    /// An inner node on the path to the proven leaf.
+    Node {
+        /// The left subtree.
+        #[cfg_attr(feature = "serde", serde(rename = "l"))]
+        left: Box<Self>,
+        /// The right subtree.
+        #[cfg_attr(feature = "serde", serde(rename = "r"))]
+        right: Box<Self>,
+    },
+    /// The hash of a subtree that isn't on the path.
+    Hash {
+        /// The hash of the subtree.
+        #[cfg_attr(
+            feature = "serde",
+            serde(rename = "h", with = "crate::utils::serde::prefix_hex_bytes")
+        )]
+        hash: [u8; MerkleRoot::LENGTH],
+    },
+    /// The proven leaf.
+    Value {
+        /// The proven block id.
+        value: BlockId,
+    },
+}
+
+impl MerkleProof {
+    /// Creates the audit path of the block id at the given index of a list of block ids.
+    pub fn new(block_ids: &[BlockId], index: usize) -> Result<Self, Error> {
+        if index >= block_ids.len() {
+            return Err(Error::InvalidMerkleProofIndex(index));
+        }
+
+        Ok(Self::audit_path(block_ids, index))
+    }
+
+    fn audit_path(block_ids: &[BlockId], index: usize) -> Self {
+        if let [block_id] = block_ids {
+            return Self::Value { value: *block_id };
+        }
+
+        let split_index = MerkleHasher::split_index(block_ids.len());
+        let (left, right) = block_ids.split_at(split_index);
+
+        if index < split_index {
+            Self::Node {
+                left: Box::new(Self::audit_path(left, index)),
+                right: Box::new(Self::hash(right)),
+            }
+        } else {
+            Self::Node {
+                left: Box::new(Self::hash(left)),
+                right: Box::new(Self::audit_path(right, index - split_index)),
+            }
+        }
+    }
+
+    fn hash(block_ids: &[BlockId]) -> Self {
+        Self::Hash {
+            hash: *MerkleHasher::digest(block_ids),
+        }
+    }
+
+    /// Computes the Merkle root of the tree the audit path belongs to.
+    pub fn root(&self) -> MerkleRoot {
+        match self {
+            Self::Node { left, right } => MerkleHasher::node(&left.root(), &right.root()),
+            Self::Hash { hash } => MerkleRoot::from(*hash),
+            Self::Value { value } => MerkleHasher::leaf(value),
+        }
+    }
+
+    /// Returns whether the audit path proves the given block id.
+    pub fn contains(&self, block_id: &BlockId) -> bool {
+        match self {
+            Self::Node { left, right } => left.contains(block_id) || right.contains(block_id),
+            Self::Hash { .. } => false,
+            Self::Value { value } => value == block_id,
+        }
+    }
+}
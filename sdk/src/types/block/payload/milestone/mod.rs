@@ -4,6 +4,7 @@
 //! Module describing the milestone payload.
 
 mod essence;
+mod inclusion_proof;
 mod index;
 mod merkle;
 mod milestone_id;
@@ -21,8 +22,9 @@ use packable::{bounded::BoundedU8, prefix::VecPrefix, Packable};
 
 pub use self::{
     essence::MilestoneEssence,
+    inclusion_proof::InclusionProof,
     index::MilestoneIndex,
-    merkle::MerkleRoot,
+    merkle::{MerkleHasher, MerkleProof, MerkleRoot},
     milestone_id::MilestoneId,
     option::{MilestoneOption, MilestoneOptions, ParametersMilestoneOption, ReceiptMilestoneOption},
 };
@@ -166,6 +168,7 @@ pub mod dto {
     use serde::{Deserialize, Serialize};
 
     use self::option::dto::MilestoneOptionDto;
+    pub use super::inclusion_proof::dto::InclusionProofDto;
     use super::*;
     use crate::{
         types::{
@@ -0,0 +1,182 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use super::{semantic_validation, ConflictReason, ValidationContext};
+use crate::types::block::{
+    input::Input,
+    output::{Output, OutputId},
+    payload::transaction::{TransactionEssence, TransactionId, TransactionPayload},
+    Error,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LedgerStateOutput {
+    output: Output,
+    // The milestone index at which the output was spent and the transaction that spent it.
+    spent: Option<(u32, TransactionId)>,
+}
+
+/// An in-memory UTXO set to which transactions can be applied offline, following the semantic validation rules of
+/// the protocol.
+///
+/// Transactions are confirmed at the current milestone of the ledger state, which allows simulating chains of
+/// dependent transactions before any of them is broadcast.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LedgerState {
+    milestone_index: u32,
+    milestone_timestamp: u32,
+    outputs: BTreeMap<OutputId, LedgerStateOutput>,
+}
+
+impl LedgerState {
+    /// Creates a new, empty [`LedgerState`] at the given milestone.
+    pub fn new(milestone_index: u32, milestone_timestamp: u32) -> Self {
+        Self {
+            milestone_index,
+            milestone_timestamp,
+            outputs: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the index of the milestone confirming applied transactions.
+    pub fn milestone_index(&self) -> u32 {
+        self.milestone_index
+    }
+
+    /// Returns the timestamp of the milestone confirming applied transactions.
+    pub fn milestone_timestamp(&self) -> u32 {
+        self.milestone_timestamp
+    }
+
+    /// Moves the ledger state to a new milestone, against whose timestamp the next transactions are validated.
+    pub fn apply_milestone(&mut self, milestone_index: u32, milestone_timestamp: u32) {
+        self.milestone_index = milestone_index;
+        self.milestone_timestamp = milestone_timestamp;
+    }
+
+    /// Adds an unspent output to the ledger state, e.g. one fetched from a node.
+    pub fn add_output(&mut self, output_id: OutputId, output: Output) {
+        self.outputs
+            .insert(output_id, LedgerStateOutput { output, spent: None });
+    }
+
+    /// Returns the output with the given id if it is unspent.
+    pub fn unspent_output(&self, output_id: &OutputId) -> Option<&Output> {
+        self.outputs
+            .get(output_id)
+            .filter(|output| output.spent.is_none())
+            .map(|output| &output.output)
+    }
+
+    /// Returns the unspent outputs of the ledger state.
+    pub fn unspent_outputs(&self) -> impl Iterator<Item = (&OutputId, &Output)> {
+        self.outputs
+            .iter()
+            .filter(|(_, output)| output.spent.is_none())
+            .map(|(output_id, output)| (output_id, &output.output))
+    }
+
+    /// Returns the id of the transaction that spent the output with the given id, if any.
+    pub fn spending_transaction(&self, output_id: &OutputId) -> Option<TransactionId> {
+        self.outputs
+            .get(output_id)
+            .and_then(|output| output.spent)
+            .map(|(_, transaction_id)| transaction_id)
+    }
+
+    /// Validates a transaction against the ledger state without applying it. Errors are returned for malformed
+    /// transactions.
+    pub fn validate_transaction(&self, transaction: &TransactionPayload) -> Result<ConflictReason, Error> {
+        validate_ledger_transaction(
+            transaction,
+            self.milestone_index,
+            self.milestone_timestamp,
+            |output_id| {
+                self.outputs
+                    .get(output_id)
+                    .map(|output| (&output.output, output.spent.map(|(spent_index, _)| spent_index)))
+            },
+        )
+    }
+
+    /// Validates a transaction against the ledger state and, if it doesn't conflict, spends its inputs and adds its
+    /// outputs. The ledger state is left untouched by conflicting or malformed transactions.
+    pub fn apply_transaction(&mut self, transaction: &TransactionPayload) -> Result<ConflictReason, Error> {
+        let conflict_reason = self.validate_transaction(transaction)?;
+
+        if conflict_reason != ConflictReason::None {
+            return Ok(conflict_reason);
+        }
+
+        let transaction_id = transaction.id();
+        let TransactionEssence::Regular(essence) = transaction.essence();
+
+        for input in essence.inputs() {
+            let Input::Utxo(input) = input else { continue };
+            if let Some(output) = self.outputs.get_mut(input.output_id()) {
+                output.spent = Some((self.milestone_index, transaction_id));
+            }
+        }
+        for (index, output) in essence.outputs().iter().enumerate() {
+            // PANIC: the number of outputs is bounded by the transaction essence.
+            let output_id = OutputId::new(transaction_id, index as u16).unwrap();
+            self.add_output(output_id, output.clone());
+        }
+
+        Ok(ConflictReason::None)
+    }
+
+    /// Applies transactions in order, returning the [`ConflictReason`] of each of them, or the error if it is
+    /// malformed. Later transactions can spend outputs created by earlier ones.
+    pub fn apply_transactions<'a>(
+        &mut self,
+        transactions: impl IntoIterator<Item = &'a TransactionPayload>,
+    ) -> Vec<Result<ConflictReason, Error>> {
+        transactions
+            .into_iter()
+            .map(|transaction| self.apply_transaction(transaction))
+            .collect()
+    }
+}
+
+/// Validates a transaction against the outputs of a ledger, confirmed by the milestone with the given index and
+/// timestamp. `ledger_output` returns an output of the ledger with the index of the milestone that spent it, if any.
+pub(crate) fn validate_ledger_transaction<'a>(
+    transaction: &TransactionPayload,
+    milestone_index: u32,
+    milestone_timestamp: u32,
+    ledger_output: impl Fn(&OutputId) -> Option<(&'a Output, Option<u32>)>,
+) -> Result<ConflictReason, Error> {
+    let transaction_id = transaction.id();
+    let TransactionEssence::Regular(essence) = transaction.essence();
+
+    let mut inputs = Vec::with_capacity(essence.inputs().len());
+    for input in essence.inputs() {
+        let Input::Utxo(input) = input else {
+            return Ok(ConflictReason::SemanticValidationFailed);
+        };
+        match ledger_output(input.output_id()) {
+            None => return Ok(ConflictReason::InputUtxoNotFound),
+            Some((_, Some(spent_index))) => {
+                return Ok(if spent_index == milestone_index {
+                    ConflictReason::InputUtxoAlreadySpentInThisMilestone
+                } else {
+                    ConflictReason::InputUtxoAlreadySpent
+                });
+            }
+            Some((output, None)) => inputs.push((input.output_id(), output)),
+        }
+    }
+
+    let context = ValidationContext::new(
+        &transaction_id,
+        essence,
+        inputs.iter().map(|(id, input)| (*id, *input)),
+        transaction.unlocks(),
+        milestone_timestamp,
+    );
+
+    semantic_validation(context, inputs.as_slice(), transaction.unlocks())
+}
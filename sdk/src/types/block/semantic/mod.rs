@@ -1,12 +1,17 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod ledger_state;
+
 use alloc::collections::BTreeMap;
 use core::{convert::Infallible, fmt};
 
 use hashbrown::{HashMap, HashSet};
 use primitive_types::U256;
 
+pub use self::ledger_state::LedgerState;
+#[cfg(feature = "mock_node")]
+pub(crate) use self::ledger_state::validate_ledger_transaction;
 use crate::types::block::{
     address::Address,
     output::{ChainId, FoundryId, InputsCommitment, NativeTokens, Output, OutputId, TokenId},
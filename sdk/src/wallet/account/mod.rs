@@ -15,7 +15,7 @@ pub(crate) mod update;
 use std::{
     collections::{HashMap, HashSet},
     ops::Deref,
-    sync::Arc,
+    sync::{atomic::AtomicUsize, Arc},
 };
 
 use getset::{Getters, Setters};
@@ -24,19 +24,27 @@ use tokio::sync::{Mutex, RwLock};
 
 #[cfg(feature = "participation")]
 pub use self::operations::participation::{AccountParticipationOverview, ParticipationEventWithNodes};
-use self::types::{
-    address::{AccountAddress, AddressWithUnspentOutputs},
-    Balance, OutputData, Transaction, TransactionDto,
+use self::{
+    operations::{payment_queue::PaymentQueue, scheduled_payments::ScheduledPayments},
+    types::{
+        address::{AccountAddress, AddressWithUnspentOutputs},
+        Balance, OutputData, Transaction, TransactionDto,
+    },
 };
 pub use self::{
     operations::{
         output_claiming::OutputsToClaim,
         output_consolidation::ConsolidationParams,
+        payment_queue::{PaymentQueueOptions, QueuedPayment},
+        scheduled_payments::{MissedInstalments, PaymentSchedule, ScheduledPayment},
         syncing::{
             options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions},
             SyncOptions,
         },
         transaction::{
+            batch::{
+                BatchedTransaction, BatchedTransactionDto, TransactionBatch, TransactionBatchDto, TransactionBatchId,
+            },
             high_level::{
                 create_alias::CreateAliasParams,
                 minting::{
@@ -46,6 +54,7 @@ pub use self::{
                     },
                     mint_nfts::MintNftParams,
                 },
+                sweep::{Sweep, SweepOptions},
             },
             prepare_output::{Assets, Features, OutputParams, ReturnStrategy, StorageDeposit, Unlocks},
             RemainderValueStrategy, TransactionOptions, TransactionOptionsDto,
@@ -56,6 +65,7 @@ pub use self::{
 use super::core::WalletInner;
 use crate::{
     client::{
+        api::input_selection::SelectionTrace,
         secret::{SecretManage, SecretManager},
         Client,
     },
@@ -167,6 +177,14 @@ pub struct AccountInner {
     // again, because sending transactions can change that
     pub(crate) last_synced: Mutex<u128>,
     pub(crate) default_sync_options: Mutex<SyncOptions>,
+    // payments waiting to be sent together in a single transaction
+    pub(crate) payment_queue: Mutex<PaymentQueue>,
+    // 0 = not running, 1 = running, 2 = stopping
+    pub(crate) payment_queue_status: AtomicUsize,
+    // payments sent when their instalments are due
+    pub(crate) scheduled_payments: Mutex<ScheduledPayments>,
+    // the trace of the last input selection, if it failed
+    pub(crate) last_selection_trace: Mutex<Option<SelectionTrace>>,
 }
 
 // impl Deref so we can use `account.details()` instead of `account.details.read()`
@@ -195,12 +213,38 @@ where
         #[cfg(not(feature = "storage"))]
         let default_sync_options = Default::default();
 
+        #[cfg(feature = "storage")]
+        let payment_queue = wallet
+            .storage_manager
+            .read()
+            .await
+            .get_payment_queue(*details.index())
+            .await?
+            .unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let payment_queue = Default::default();
+
+        #[cfg(feature = "storage")]
+        let scheduled_payments = wallet
+            .storage_manager
+            .read()
+            .await
+            .get_scheduled_payments(*details.index())
+            .await?
+            .unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let scheduled_payments = Default::default();
+
         Ok(Self {
             wallet,
             inner: Arc::new(AccountInner {
                 details: RwLock::new(details),
                 last_synced: Default::default(),
                 default_sync_options: Mutex::new(default_sync_options),
+                payment_queue: Mutex::new(payment_queue),
+                payment_queue_status: AtomicUsize::new(0),
+                scheduled_payments: Mutex::new(scheduled_payments),
+                last_selection_trace: Default::default(),
             }),
         })
     }
@@ -18,8 +18,12 @@ pub(crate) mod output_finder;
 /// The module for participation
 #[cfg(feature = "participation")]
 pub(crate) mod participation;
+/// The module for the payment queue
+pub(crate) mod payment_queue;
 /// The module for retrying blocks or transactions
 pub(crate) mod retry;
+/// The module for scheduled payments
+pub(crate) mod scheduled_payments;
 /// The module for synchronization of an account
 pub(crate) mod syncing;
 /// The module for transactions
@@ -0,0 +1,395 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::atomic::Ordering, time::Duration};
+
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+#[cfg(feature = "events")]
+use crate::wallet::events::types::{PaymentStatus, PaymentStatusEvent, WalletEvent};
+use crate::{
+    client::{
+        api::{SignedTransactionData, SignedTransactionDataDto},
+        secret::SecretManage,
+        Error as ClientError,
+    },
+    types::{
+        block::{output::OUTPUT_COUNT_MAX, payload::transaction::TransactionId},
+        TryFromDto,
+    },
+    wallet::{
+        account::{
+            operations::transaction::high_level::send::SendParams, types::Transaction, Account, TransactionOptions,
+        },
+        Error,
+    },
+};
+
+/// The default interval at which queued payments are sent.
+pub(crate) const DEFAULT_PAYMENT_QUEUE_INTERVAL: Duration = Duration::from_secs(60);
+/// The maximum number of payments sent in a single transaction, leaving room for a remainder output.
+const PAYMENT_QUEUE_FLUSH_MAX: usize = OUTPUT_COUNT_MAX as usize - 1;
+
+/// Options for the periodic sending of the payment queue.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentQueueOptions {
+    /// The interval at which queued payments are sent.
+    pub interval: Duration,
+    /// The number of queued payments at which they are sent without waiting for the interval to elapse.
+    pub max_payments: usize,
+}
+
+impl Default for PaymentQueueOptions {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_PAYMENT_QUEUE_INTERVAL,
+            max_payments: PAYMENT_QUEUE_FLUSH_MAX,
+        }
+    }
+}
+
+/// A payment waiting in the payment queue of an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedPayment {
+    /// The id of the payment, unique within the account.
+    pub id: u64,
+    /// The parameters of the payment.
+    pub params: SendParams,
+    /// The unix timestamp in milliseconds at which the payment was queued.
+    pub timestamp: u128,
+}
+
+/// The payments queued by an account, stored with the `storage` feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PaymentQueue {
+    next_id: u64,
+    payments: Vec<QueuedPayment>,
+    // The flush whose transaction is being submitted, stored before submitting it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    in_flight: Option<InFlightPayments>,
+}
+
+/// Queued payments sent by a signed transaction that may not have been submitted yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InFlightPayments {
+    payment_ids: Vec<u64>,
+    signed_transaction_data: SignedTransactionDataDto,
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Adds a payment to the payment queue of the account, to be sent together with other payments by
+    /// [Account::flush_payment_queue()](crate::wallet::Account::flush_payment_queue). Returns the id of the payment.
+    /// If storage is enabled, will persist during restarts. Payments whose output can't be built are refused, except
+    /// for micro amounts which depend on the transaction options of the flush.
+    pub async fn queue_payment(&self, params: SendParams) -> crate::wallet::Result<u64> {
+        let options = TransactionOptions {
+            allow_micro_amount: true,
+            ..Default::default()
+        };
+        self.send_output_context()
+            .await?
+            .build_output(params.clone(), Some(&options))?;
+
+        let mut payment_queue = self.payment_queue.lock().await;
+        let id = payment_queue.next_id;
+
+        payment_queue.next_id += 1;
+        payment_queue.payments.push(QueuedPayment {
+            id,
+            params,
+            timestamp: crate::utils::unix_timestamp_now().as_millis(),
+        });
+        log::debug!("[PAYMENT QUEUE] queued payment {id}");
+
+        #[cfg(feature = "storage")]
+        self.save_payment_queue(&payment_queue).await?;
+        drop(payment_queue);
+
+        #[cfg(feature = "events")]
+        self.emit_payment_status(id, PaymentStatus::Queued).await;
+
+        Ok(id)
+    }
+
+    /// Returns the payments waiting in the payment queue of the account.
+    pub async fn queued_payments(&self) -> Vec<QueuedPayment> {
+        self.payment_queue.lock().await.payments.clone()
+    }
+
+    /// Removes a payment from the payment queue of the account, returns it if it was still queued.
+    pub async fn remove_queued_payment(&self, id: u64) -> crate::wallet::Result<Option<QueuedPayment>> {
+        let mut payment_queue = self.payment_queue.lock().await;
+        let Some(index) = payment_queue.payments.iter().position(|payment| payment.id == id) else {
+            return Ok(None);
+        };
+        let payment = payment_queue.payments.remove(index);
+
+        #[cfg(feature = "storage")]
+        self.save_payment_queue(&payment_queue).await?;
+
+        Ok(Some(payment))
+    }
+
+    /// Sends the oldest queued payments in a single transaction, up to the maximum number of outputs of a
+    /// transaction. Returns `None` if no payment is sent.
+    ///
+    /// Sent payments are removed from the queue. Payments that can't be sent with the options, e.g. micro amounts if
+    /// they aren't allowed, are removed from the queue and reported as failed without holding back the others. If
+    /// sending fails, the payments stay queued so they are sent again with the next flush, unless they are removed
+    /// with [Account::remove_queued_payment()](crate::wallet::Account::remove_queued_payment). A flush interrupted
+    /// after signing its transaction is completed by the next one, without sending its payments twice. Payments can't
+    /// be queued while the queue is flushed.
+    pub async fn flush_payment_queue(
+        &self,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Option<Transaction>> {
+        let options = options.into();
+        let mut payment_queue = self.payment_queue.lock().await;
+
+        if let Some(in_flight) = payment_queue.in_flight.clone() {
+            self.complete_in_flight_payments(&mut payment_queue, in_flight, options.clone())
+                .await?;
+        }
+
+        if payment_queue.payments.is_empty() {
+            return Ok(None);
+        }
+
+        let context = self.send_output_context().await?;
+        let mut outputs = Vec::new();
+        let mut payment_ids = Vec::new();
+        let mut index = 0;
+
+        while index < payment_queue.payments.len() && outputs.len() < PAYMENT_QUEUE_FLUSH_MAX {
+            let payment = &payment_queue.payments[index];
+
+            match context.build_output(payment.params.clone(), options.as_ref()) {
+                Ok(output) => {
+                    outputs.push(output);
+                    payment_ids.push(payment.id);
+                    index += 1;
+                }
+                Err(err) => {
+                    let payment = payment_queue.payments.remove(index);
+                    log::debug!(
+                        "[PAYMENT QUEUE] removed payment {} that can't be sent: {err}",
+                        payment.id
+                    );
+
+                    #[cfg(feature = "events")]
+                    self.emit_payment_status(payment.id, PaymentStatus::Failed { error: err.to_string() })
+                        .await;
+                }
+            }
+        }
+
+        #[cfg(feature = "storage")]
+        self.save_payment_queue(&payment_queue).await?;
+
+        if outputs.is_empty() {
+            return Ok(None);
+        }
+
+        log::debug!("[PAYMENT QUEUE] flushing {} payments", outputs.len());
+
+        let result = async {
+            let prepared_transaction_data = self.prepare_transaction(outputs, options.clone()).await?;
+            let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
+                Ok(res) => res,
+                Err(err) => {
+                    // unlock outputs so they are available for a new transaction
+                    self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
+                    return Err(err);
+                }
+            };
+
+            // Store the signed transaction before submitting it, so the payments aren't sent twice after a crash
+            payment_queue.in_flight = Some(InFlightPayments {
+                payment_ids: payment_ids.clone(),
+                signed_transaction_data: SignedTransactionDataDto::from(&signed_transaction_data),
+            });
+            #[cfg(feature = "storage")]
+            self.save_payment_queue(&payment_queue).await?;
+
+            self.submit_and_store_transaction(signed_transaction_data, options.clone())
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(transaction) => {
+                self.remove_sent_payments(&mut payment_queue, &payment_ids, transaction.transaction_id)
+                    .await?;
+
+                Ok(Some(transaction))
+            }
+            Err(err) => {
+                log::debug!("[PAYMENT QUEUE] flushing failed: {err}");
+
+                // An invalid transaction isn't submitted, its payments are sent with the next flush.
+                if matches!(&err, Error::Client(error) if matches!(error.as_ref(), ClientError::TransactionSemantic(_)))
+                {
+                    payment_queue.in_flight = None;
+                    #[cfg(feature = "storage")]
+                    self.save_payment_queue(&payment_queue).await?;
+                }
+
+                #[cfg(feature = "events")]
+                for payment_id in payment_ids {
+                    self.emit_payment_status(payment_id, PaymentStatus::Failed { error: err.to_string() })
+                        .await;
+                }
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Starts sending the payment queue of the account periodically, when the interval elapsed or when enough payments
+    /// are queued.
+    pub async fn start_payment_queue(
+        &self,
+        options: Option<PaymentQueueOptions>,
+        transaction_options: Option<TransactionOptions>,
+    ) -> crate::wallet::Result<()> {
+        log::debug!("[start_payment_queue]");
+        // stop existing process if running
+        self.stop_payment_queue().await?;
+
+        self.payment_queue_status.store(1, Ordering::Relaxed);
+        let account = self.clone();
+        let options = options.unwrap_or_default();
+        let _payment_queue = std::thread::spawn(move || {
+            #[cfg(not(target_family = "wasm"))]
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            #[cfg(target_family = "wasm")]
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let mut last_flush = Instant::now();
+                // check every second so stopping the process doesn't have to wait long
+                while account.payment_queue_status.load(Ordering::Relaxed) != 2 {
+                    let queued = account.payment_queue.lock().await.payments.len();
+
+                    if queued >= options.max_payments || (queued > 0 && last_flush.elapsed() >= options.interval) {
+                        if let Err(err) = account.flush_payment_queue(transaction_options.clone()).await {
+                            log::debug!("[payment_queue] error: {}", err);
+                        }
+                        last_flush = Instant::now();
+                    }
+                    sleep(Duration::from_secs(1)).await;
+                }
+                account.payment_queue_status.store(0, Ordering::Relaxed);
+                log::debug!("[payment_queue]: stopped");
+            });
+        });
+        Ok(())
+    }
+
+    /// Stops sending the payment queue of the account periodically, queued payments stay queued.
+    pub async fn stop_payment_queue(&self) -> crate::wallet::Result<()> {
+        log::debug!("[stop_payment_queue]");
+        // immediately return if not running
+        if self.payment_queue_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.payment_queue_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.payment_queue_status.load(Ordering::Relaxed) != 0 {
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(10).await;
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+
+    // Completes a flush interrupted after signing its transaction, removing its payments from the queue once sent.
+    async fn complete_in_flight_payments(
+        &self,
+        payment_queue: &mut PaymentQueue,
+        in_flight: InFlightPayments,
+        options: Option<TransactionOptions>,
+    ) -> crate::wallet::Result<()> {
+        let signed_transaction_data = SignedTransactionData::try_from_dto(in_flight.signed_transaction_data)?;
+        let transaction_id = signed_transaction_data.transaction_payload.id();
+        log::debug!("[PAYMENT QUEUE] completing flush of transaction {transaction_id}");
+
+        if self
+            .ensure_transaction_submitted(signed_transaction_data, options)
+            .await?
+        {
+            self.remove_sent_payments(payment_queue, &in_flight.payment_ids, transaction_id)
+                .await
+        } else {
+            // The inputs were spent by another transaction, the payments are sent again.
+            payment_queue.in_flight = None;
+
+            #[cfg(feature = "storage")]
+            self.save_payment_queue(payment_queue).await?;
+
+            Ok(())
+        }
+    }
+
+    async fn remove_sent_payments(
+        &self,
+        payment_queue: &mut PaymentQueue,
+        payment_ids: &[u64],
+        transaction_id: TransactionId,
+    ) -> crate::wallet::Result<()> {
+        payment_queue
+            .payments
+            .retain(|payment| !payment_ids.contains(&payment.id));
+        payment_queue.in_flight = None;
+
+        #[cfg(feature = "storage")]
+        self.save_payment_queue(payment_queue).await?;
+
+        for payment_id in payment_ids {
+            log::debug!("[PAYMENT QUEUE] sent payment {payment_id} in transaction {transaction_id}");
+
+            #[cfg(feature = "events")]
+            self.emit_payment_status(*payment_id, PaymentStatus::Sent { transaction_id })
+                .await;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "storage")]
+    async fn save_payment_queue(&self, payment_queue: &PaymentQueue) -> crate::wallet::Result<()> {
+        let account_index = *self.details().await.index();
+
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .set_payment_queue(account_index, payment_queue)
+            .await
+    }
+
+    #[cfg(feature = "events")]
+    async fn emit_payment_status(&self, payment_id: u64, status: PaymentStatus) {
+        self.emit(
+            *self.details().await.index(),
+            WalletEvent::PaymentStatus(PaymentStatusEvent { payment_id, status }),
+        )
+        .await;
+    }
+}
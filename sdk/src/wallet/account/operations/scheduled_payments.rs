@@ -0,0 +1,492 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "events")]
+use crate::wallet::events::types::{ScheduledPaymentExecutedEvent, WalletEvent};
+use crate::{
+    client::{
+        api::{SignedTransactionData, SignedTransactionDataDto},
+        secret::SecretManage,
+        Error as ClientError,
+    },
+    types::{
+        block::{output::OUTPUT_COUNT_MAX, payload::transaction::TransactionId},
+        TryFromDto,
+    },
+    wallet::{
+        account::{
+            operations::transaction::prepare_output::{OutputParams, Unlocks},
+            types::Transaction,
+            Account, TransactionOptions,
+        },
+        Error,
+    },
+};
+
+/// The maximum number of instalments sent in a single transaction, leaving room for a remainder output.
+const SCHEDULED_PAYMENTS_EXECUTION_MAX: usize = OUTPUT_COUNT_MAX as usize - 1;
+
+/// When the instalments of a scheduled payment are due.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentSchedule {
+    /// The unix timestamp in seconds at which the first instalment is due.
+    pub start: u32,
+    /// The number of seconds between two instalments, required for more than one instalment.
+    #[serde(default)]
+    pub interval: Option<u32>,
+    /// The number of instalments, unlimited if not set.
+    #[serde(default)]
+    pub instalments: Option<u32>,
+    /// How instalments that were missed, because the account wasn't executing scheduled payments when they were due,
+    /// are sent.
+    #[serde(default)]
+    pub missed_instalments: MissedInstalments,
+}
+
+/// How the instalments of a [`PaymentSchedule`] that were missed are sent.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MissedInstalments {
+    /// A single instalment is sent for all the due ones, the others are skipped.
+    #[default]
+    PayOnce,
+    /// Every due instalment is sent.
+    PayAll,
+}
+
+impl PaymentSchedule {
+    /// Creates a schedule for a single payment due at the given unix timestamp in seconds.
+    pub fn at(start: u32) -> Self {
+        Self {
+            start,
+            interval: None,
+            instalments: Some(1),
+            missed_instalments: MissedInstalments::default(),
+        }
+    }
+
+    /// Creates a schedule for a payment recurring every `interval` seconds, starting at the given unix timestamp in
+    /// seconds.
+    pub fn recurring(start: u32, interval: u32) -> Self {
+        Self {
+            start,
+            interval: Some(interval),
+            instalments: None,
+            missed_instalments: MissedInstalments::default(),
+        }
+    }
+
+    /// Sets the number of instalments.
+    pub fn with_instalments(mut self, instalments: impl Into<Option<u32>>) -> Self {
+        self.instalments = instalments.into();
+        self
+    }
+
+    /// Sets how missed instalments are sent.
+    pub fn with_missed_instalments(mut self, missed_instalments: MissedInstalments) -> Self {
+        self.missed_instalments = missed_instalments;
+        self
+    }
+
+    /// Returns the unix timestamp in seconds at which an instalment is due, `None` if the schedule doesn't have it.
+    pub fn due_time(&self, instalment: u32) -> Option<u32> {
+        if self.instalments.is_some_and(|instalments| instalment >= instalments) {
+            return None;
+        }
+
+        match self.interval {
+            Some(interval) => interval.checked_mul(instalment)?.checked_add(self.start),
+            None => (instalment == 0).then_some(self.start),
+        }
+    }
+
+    // Returns the last instalment that is due at the given unix timestamp in seconds, `None` if none is due yet.
+    fn last_due_instalment(&self, time: u32) -> Option<u32> {
+        let elapsed = time.checked_sub(self.start)?;
+        let last = self.interval.map_or(0, |interval| elapsed / interval);
+
+        Some(match self.instalments {
+            Some(instalments) => last.min(instalments.checked_sub(1)?),
+            None => last,
+        })
+    }
+
+    fn validate(&self) -> crate::wallet::Result<()> {
+        match (self.interval, self.instalments) {
+            (_, Some(0)) => Err(Error::InvalidPaymentSchedule("at least one instalment is required")),
+            (Some(0), _) => Err(Error::InvalidPaymentSchedule("the interval can't be zero")),
+            (None, None) | (None, Some(2..)) => Err(Error::InvalidPaymentSchedule(
+                "an interval is required for more than one instalment",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A payment sent by an account when its instalments are due.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledPayment {
+    /// The id of the payment, unique within the account.
+    pub id: u64,
+    /// The parameters of the output sent with each instalment.
+    pub params: OutputParams,
+    /// When the instalments are due.
+    pub schedule: PaymentSchedule,
+    /// The number of instalments that were sent or skipped.
+    pub executed_instalments: u32,
+    /// The transaction that sent the last instalment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_transaction_id: Option<TransactionId>,
+}
+
+impl ScheduledPayment {
+    /// Returns the unix timestamp in seconds at which the next instalment is due, `None` if all were sent.
+    pub fn next_due_time(&self) -> Option<u32> {
+        self.schedule.due_time(self.executed_instalments)
+    }
+}
+
+/// The payments scheduled by an account, stored with the `storage` feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScheduledPayments {
+    next_id: u64,
+    payments: Vec<ScheduledPayment>,
+    // The execution whose transaction is being submitted, stored before submitting it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    in_flight: Option<InFlightInstalments>,
+}
+
+/// Instalments sent by a signed transaction that may not have been submitted yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InFlightInstalments {
+    // The id of the payment and the instalment of each output.
+    instalments: Vec<(u64, u32)>,
+    signed_transaction_data: SignedTransactionDataDto,
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Schedules a payment that sends an output with the provided parameters whenever one of its instalments is due.
+    /// Due instalments are sent by the background syncing, or by calling
+    /// [Account::execute_scheduled_payments()](crate::wallet::Account::execute_scheduled_payments). If storage is
+    /// enabled, will persist during restarts.
+    pub async fn schedule_payment(
+        &self,
+        params: OutputParams,
+        schedule: PaymentSchedule,
+    ) -> crate::wallet::Result<ScheduledPayment> {
+        schedule.validate()?;
+        self.client().bech32_hrp_matches(params.recipient_address.hrp()).await?;
+
+        let mut scheduled_payments = self.scheduled_payments.lock().await;
+        let payment = ScheduledPayment {
+            id: scheduled_payments.next_id,
+            params,
+            schedule,
+            executed_instalments: 0,
+            last_transaction_id: None,
+        };
+
+        scheduled_payments.next_id += 1;
+        scheduled_payments.payments.push(payment.clone());
+        log::debug!("[SCHEDULED PAYMENTS] scheduled payment {}", payment.id);
+
+        #[cfg(feature = "storage")]
+        self.save_scheduled_payments(&scheduled_payments).await?;
+
+        Ok(payment)
+    }
+
+    /// Sends all instalments of a payment right away, in outputs with a timelock until the instalment is due. Unlike
+    /// [Account::schedule_payment()](crate::wallet::Account::schedule_payment), the account doesn't need to be online
+    /// when the instalments are due, but they can't be cancelled anymore and the number of instalments is limited.
+    pub async fn send_timelocked_payment(
+        &self,
+        params: OutputParams,
+        schedule: PaymentSchedule,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        schedule.validate()?;
+        let options = options.into();
+        let instalments = schedule.instalments.ok_or(Error::InvalidPaymentSchedule(
+            "timelocked payments require a number of instalments",
+        ))?;
+
+        let mut outputs = Vec::new();
+        for instalment in 0..instalments {
+            let mut params = params.clone();
+            params.unlocks.get_or_insert_with(Unlocks::default).timelock_unix_time = schedule.due_time(instalment);
+            outputs.push(self.prepare_output(params, options.clone()).await?);
+        }
+
+        self.send_outputs(outputs, options).await
+    }
+
+    /// Returns the payments scheduled by the account that still have instalments to send.
+    pub async fn scheduled_payments(&self) -> Vec<ScheduledPayment> {
+        self.scheduled_payments.lock().await.payments.clone()
+    }
+
+    /// Cancels a scheduled payment, its remaining instalments won't be sent. Returns it if it was still scheduled.
+    pub async fn cancel_scheduled_payment(&self, id: u64) -> crate::wallet::Result<Option<ScheduledPayment>> {
+        let mut scheduled_payments = self.scheduled_payments.lock().await;
+        let Some(index) = scheduled_payments.payments.iter().position(|payment| payment.id == id) else {
+            return Ok(None);
+        };
+        let payment = scheduled_payments.payments.remove(index);
+        log::debug!("[SCHEDULED PAYMENTS] cancelled payment {id}");
+
+        #[cfg(feature = "storage")]
+        self.save_scheduled_payments(&scheduled_payments).await?;
+
+        Ok(Some(payment))
+    }
+
+    /// Sends the instalments of scheduled payments that are due in a single transaction, up to the maximum number of
+    /// outputs of a transaction. Returns `None` if no instalment is sent.
+    ///
+    /// Instalments that were missed are sent according to the [`MissedInstalments`] of their schedule, only once by
+    /// default. If sending fails, they stay due and are sent with the next execution, as do the instalments of payments
+    /// whose output can't be built. An execution interrupted after signing its transaction is completed by the next
+    /// one, without sending its instalments twice. Payments are removed once all of their instalments were sent.
+    pub async fn execute_scheduled_payments(
+        &self,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Option<Transaction>> {
+        let options = options.into();
+        let mut scheduled_payments = self.scheduled_payments.lock().await;
+
+        if let Some(in_flight) = scheduled_payments.in_flight.clone() {
+            self.complete_in_flight_instalments(&mut scheduled_payments, in_flight, options.clone())
+                .await?;
+        }
+
+        if scheduled_payments.payments.is_empty() {
+            return Ok(None);
+        }
+
+        let current_time = self.client().get_time_checked().await?;
+        // The payment id and instalment of each output.
+        let mut instalments = Vec::new();
+        let mut outputs = Vec::new();
+
+        'payments: for payment in &scheduled_payments.payments {
+            let Some(last_due_instalment) = payment
+                .schedule
+                .last_due_instalment(current_time)
+                .filter(|last_due_instalment| *last_due_instalment >= payment.executed_instalments)
+            else {
+                continue;
+            };
+            let first_instalment = match payment.schedule.missed_instalments {
+                MissedInstalments::PayOnce => last_due_instalment,
+                MissedInstalments::PayAll => payment.executed_instalments,
+            };
+
+            // A payment whose output can't be built doesn't hold back the others, it stays due.
+            let output = match self.prepare_output(payment.params.clone(), options.clone()).await {
+                Ok(output) => output,
+                Err(err) => {
+                    log::debug!(
+                        "[SCHEDULED PAYMENTS] skipped payment {} whose output can't be built: {err}",
+                        payment.id
+                    );
+                    continue;
+                }
+            };
+
+            for instalment in first_instalment..=last_due_instalment {
+                if outputs.len() == SCHEDULED_PAYMENTS_EXECUTION_MAX {
+                    break 'payments;
+                }
+
+                outputs.push(output.clone());
+                instalments.push((payment.id, instalment));
+            }
+        }
+
+        if outputs.is_empty() {
+            return Ok(None);
+        }
+
+        log::debug!("[SCHEDULED PAYMENTS] sending {} instalments", outputs.len());
+
+        let prepared_transaction_data = self.prepare_transaction(outputs, options.clone()).await?;
+        let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
+            Ok(res) => res,
+            Err(err) => {
+                // unlock outputs so they are available for a new transaction
+                self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
+                return Err(err);
+            }
+        };
+
+        // Store the signed transaction before submitting it, so the instalments aren't sent twice after a crash
+        scheduled_payments.in_flight = Some(InFlightInstalments {
+            instalments: instalments.clone(),
+            signed_transaction_data: SignedTransactionDataDto::from(&signed_transaction_data),
+        });
+        #[cfg(feature = "storage")]
+        self.save_scheduled_payments(&scheduled_payments).await?;
+
+        let transaction = match self
+            .submit_and_store_transaction(signed_transaction_data, options)
+            .await
+        {
+            Ok(transaction) => transaction,
+            Err(err) => {
+                // An invalid transaction isn't submitted, its instalments are sent with the next execution.
+                if matches!(&err, Error::Client(error) if matches!(error.as_ref(), ClientError::TransactionSemantic(_)))
+                {
+                    scheduled_payments.in_flight = None;
+                    #[cfg(feature = "storage")]
+                    self.save_scheduled_payments(&scheduled_payments).await?;
+                }
+
+                return Err(err);
+            }
+        };
+
+        self.mark_instalments_sent(&mut scheduled_payments, &instalments, transaction.transaction_id)
+            .await?;
+
+        Ok(Some(transaction))
+    }
+
+    // Completes an execution interrupted after signing its transaction, marking its instalments as sent once sent.
+    async fn complete_in_flight_instalments(
+        &self,
+        scheduled_payments: &mut ScheduledPayments,
+        in_flight: InFlightInstalments,
+        options: Option<TransactionOptions>,
+    ) -> crate::wallet::Result<()> {
+        let signed_transaction_data = SignedTransactionData::try_from_dto(in_flight.signed_transaction_data)?;
+        let transaction_id = signed_transaction_data.transaction_payload.id();
+        log::debug!("[SCHEDULED PAYMENTS] completing execution of transaction {transaction_id}");
+
+        if self
+            .ensure_transaction_submitted(signed_transaction_data, options)
+            .await?
+        {
+            self.mark_instalments_sent(scheduled_payments, &in_flight.instalments, transaction_id)
+                .await
+        } else {
+            // The inputs were spent by another transaction, the instalments are sent again.
+            scheduled_payments.in_flight = None;
+
+            #[cfg(feature = "storage")]
+            self.save_scheduled_payments(scheduled_payments).await?;
+
+            Ok(())
+        }
+    }
+
+    async fn mark_instalments_sent(
+        &self,
+        scheduled_payments: &mut ScheduledPayments,
+        instalments: &[(u64, u32)],
+        transaction_id: TransactionId,
+    ) -> crate::wallet::Result<()> {
+        for (payment_id, instalment) in instalments {
+            // The payment may have been cancelled in the meantime.
+            let Some(payment) = scheduled_payments
+                .payments
+                .iter_mut()
+                .find(|payment| payment.id == *payment_id)
+            else {
+                continue;
+            };
+            payment.executed_instalments = payment.executed_instalments.max(instalment + 1);
+            payment.last_transaction_id = Some(transaction_id);
+            log::debug!(
+                "[SCHEDULED PAYMENTS] sent instalment {instalment} of payment {payment_id} in transaction {transaction_id}"
+            );
+
+            #[cfg(feature = "events")]
+            self.emit(
+                *self.details().await.index(),
+                WalletEvent::ScheduledPaymentExecuted(ScheduledPaymentExecutedEvent {
+                    payment_id: *payment_id,
+                    instalment: *instalment,
+                    transaction_id,
+                }),
+            )
+            .await;
+        }
+
+        scheduled_payments
+            .payments
+            .retain(|payment| payment.next_due_time().is_some());
+        scheduled_payments.in_flight = None;
+
+        #[cfg(feature = "storage")]
+        self.save_scheduled_payments(scheduled_payments).await?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "storage")]
+    async fn save_scheduled_payments(&self, scheduled_payments: &ScheduledPayments) -> crate::wallet::Result<()> {
+        let account_index = *self.details().await.index();
+
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .set_scheduled_payments(account_index, scheduled_payments)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payment_schedule_due_time() {
+        let single = PaymentSchedule::at(100);
+        assert_eq!(single.due_time(0), Some(100));
+        assert_eq!(single.due_time(1), None);
+
+        let limited = PaymentSchedule::recurring(100, 10).with_instalments(3);
+        assert_eq!(limited.due_time(2), Some(120));
+        assert_eq!(limited.due_time(3), None);
+
+        let unlimited = PaymentSchedule::recurring(100, 10);
+        assert_eq!(unlimited.due_time(1_000), Some(10_100));
+        assert_eq!(unlimited.due_time(u32::MAX), None);
+    }
+
+    #[test]
+    fn payment_schedule_last_due_instalment() {
+        let single = PaymentSchedule::at(100);
+        assert_eq!(single.last_due_instalment(99), None);
+        assert_eq!(single.last_due_instalment(1_000), Some(0));
+
+        let limited = PaymentSchedule::recurring(100, 10).with_instalments(3);
+        assert_eq!(limited.last_due_instalment(100), Some(0));
+        assert_eq!(limited.last_due_instalment(119), Some(1));
+        assert_eq!(limited.last_due_instalment(1_000), Some(2));
+
+        let every_second = PaymentSchedule::recurring(0, 1);
+        assert_eq!(every_second.last_due_instalment(1_700_000_000), Some(1_700_000_000));
+    }
+
+    #[test]
+    fn payment_schedule_validation() {
+        assert!(PaymentSchedule::at(100).validate().is_ok());
+        assert!(PaymentSchedule::recurring(100, 10).validate().is_ok());
+        assert!(PaymentSchedule::at(100).with_instalments(0).validate().is_err());
+        assert!(PaymentSchedule::at(100).with_instalments(2).validate().is_err());
+        assert!(PaymentSchedule::at(100).with_instalments(None).validate().is_err());
+        assert!(PaymentSchedule::recurring(100, 0).validate().is_err());
+    }
+}
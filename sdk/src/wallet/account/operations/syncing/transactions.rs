@@ -9,6 +9,7 @@ use crate::{
     },
     utils::unix_timestamp_now,
     wallet::account::{
+        operations::transaction::replace::transaction_inputs,
         types::{InclusionState, Transaction},
         Account, AccountDetails,
     },
@@ -199,6 +200,21 @@ where
                 }
             }
         }
+
+        reconcile_replaced_transactions(
+            &account_details,
+            &mut updated_transactions,
+            &mut spent_output_ids,
+            &mut output_ids_to_unlock,
+        );
+        // Only the latest replacement of a transaction gets reattached, so the replaced ones don't get confirmed
+        // instead.
+        transactions_to_reattach.retain(|transaction| {
+            !is_replaced(&account_details, transaction)
+                && !updated_transactions
+                    .iter()
+                    .any(|updated| updated.transaction_id == transaction.transaction_id)
+        });
         drop(account_details);
 
         for mut transaction in transactions_to_reattach {
@@ -269,3 +285,76 @@ fn process_transaction_with_unknown_state(
     updated_transactions.push(transaction);
     Ok(())
 }
+
+// Returns whether a newer pending transaction spends some of the same inputs, created by
+// `Account::replace_transaction()` or `Account::cancel_transaction()`.
+fn is_replaced(account: &AccountDetails, transaction: &Transaction) -> bool {
+    let inputs = transaction_inputs(transaction);
+
+    account.pending_transactions.iter().any(|transaction_id| {
+        account.transactions.get(transaction_id).is_some_and(|pending| {
+            pending.timestamp > transaction.timestamp && !transaction_inputs(pending).is_disjoint(&inputs)
+        })
+    })
+}
+
+// Once a transaction got confirmed, the pending transactions it replaced or got replaced by can't be confirmed anymore.
+// Their inputs that weren't spent by the confirmed transaction are made available again and the confirmed transaction
+// keeps their note if it has none.
+fn reconcile_replaced_transactions(
+    account: &AccountDetails,
+    updated_transactions: &mut Vec<Transaction>,
+    spent_output_ids: &mut Vec<OutputId>,
+    output_ids_to_unlock: &mut Vec<OutputId>,
+) {
+    let confirmed_transactions = updated_transactions
+        .iter()
+        .filter(|transaction| transaction.inclusion_state == InclusionState::Confirmed)
+        .map(|transaction| (transaction.transaction_id, transaction_inputs(transaction)))
+        .collect::<Vec<_>>();
+
+    for (confirmed_transaction_id, confirmed_inputs) in confirmed_transactions {
+        for transaction_id in &account.pending_transactions {
+            if *transaction_id == confirmed_transaction_id {
+                continue;
+            }
+
+            // Use the state from this sync if the transaction was already updated.
+            let Some(mut transaction) = updated_transactions
+                .iter()
+                .find(|transaction| transaction.transaction_id == *transaction_id)
+                .or_else(|| account.transactions.get(transaction_id))
+                .cloned()
+            else {
+                continue;
+            };
+            let inputs = transaction_inputs(&transaction);
+
+            if inputs.is_disjoint(&confirmed_inputs) {
+                continue;
+            }
+
+            log::debug!(
+                "[SYNC] transaction {transaction_id} conflicts with confirmed transaction {confirmed_transaction_id}"
+            );
+            transaction.inclusion_state = InclusionState::Conflicting;
+
+            for output_id in inputs.difference(&confirmed_inputs) {
+                spent_output_ids.retain(|spent_output_id| spent_output_id != output_id);
+                output_ids_to_unlock.push(*output_id);
+            }
+
+            if let Some(confirmed_transaction) = updated_transactions
+                .iter_mut()
+                .find(|transaction| transaction.transaction_id == confirmed_transaction_id)
+            {
+                if confirmed_transaction.note.is_none() {
+                    confirmed_transaction.note = transaction.note.clone();
+                }
+            }
+
+            updated_transactions.retain(|updated| updated.transaction_id != *transaction_id);
+            updated_transactions.push(transaction);
+        }
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{
+        api::{input_selection::Error as InputSelectionError, SignedTransactionData, SignedTransactionDataDto},
+        secret::SecretManage,
+        Error as ClientError,
+    },
+    types::{
+        block::{
+            impl_id,
+            output::{dto::OutputDto, Output, OUTPUT_COUNT_MAX},
+            payload::transaction::TransactionId,
+            string_serde_impl,
+        },
+        TryFromDto, ValidationParams,
+    },
+    wallet::{
+        account::{operations::transaction::TransactionOptions, types::InclusionState, Account},
+        Error,
+    },
+};
+
+/// The maximum number of outputs sent by a transaction of a batch, leaving room for a remainder output.
+const BATCH_OUTPUT_COUNT_MAX: usize = OUTPUT_COUNT_MAX as usize - 1;
+
+impl_id!(pub TransactionBatchId, 32, "Unique identifier of a transaction batch.");
+string_serde_impl!(TransactionBatchId);
+
+/// A sequence of transactions sending more outputs than fit into a single transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionBatch {
+    /// The identifier of the batch.
+    pub id: TransactionBatchId,
+    /// The transactions of the batch, in the order they are sent.
+    pub transactions: Vec<BatchedTransaction>,
+}
+
+impl TransactionBatch {
+    /// Plans a batch sending the given outputs with as few transactions as possible, with a random id.
+    pub fn new(outputs: impl Into<Vec<Output>>) -> crate::wallet::Result<Self> {
+        let mut id = [0u8; TransactionBatchId::LENGTH];
+        crypto::utils::rand::fill(&mut id)?;
+
+        Ok(Self {
+            id: TransactionBatchId::new(id),
+            transactions: outputs
+                .into()
+                .chunks(BATCH_OUTPUT_COUNT_MAX)
+                .map(|outputs| BatchedTransaction::new(outputs.to_vec()))
+                .collect(),
+        })
+    }
+
+    /// Returns whether all the transactions of the batch are confirmed.
+    pub fn is_finished(&self) -> bool {
+        self.transactions.iter().all(|transaction| transaction.confirmed)
+    }
+
+    /// Returns the number of confirmed transactions of the batch.
+    pub fn confirmed_count(&self) -> usize {
+        self.transactions
+            .iter()
+            .filter(|transaction| transaction.confirmed)
+            .count()
+    }
+
+    /// Splits the transaction at the given index in two halves, e.g. because its outputs require too many inputs.
+    pub(crate) fn split(&mut self, index: usize) {
+        let outputs = &mut self.transactions[index].outputs;
+        let second_half = outputs.split_off(outputs.len() / 2);
+
+        self.transactions
+            .insert(index + 1, BatchedTransaction::new(second_half));
+    }
+}
+
+/// A transaction of a [`TransactionBatch`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchedTransaction {
+    /// The outputs sent by the transaction.
+    pub outputs: Vec<Output>,
+    /// The signed transaction, kept until it's confirmed so it can be submitted again.
+    pub signed_transaction_data: Option<SignedTransactionData>,
+    /// Whether the transaction is confirmed.
+    pub confirmed: bool,
+}
+
+impl BatchedTransaction {
+    fn new(outputs: Vec<Output>) -> Self {
+        Self {
+            outputs,
+            signed_transaction_data: None,
+            confirmed: false,
+        }
+    }
+
+    /// Returns the id of the transaction, once it has been signed.
+    pub fn transaction_id(&self) -> Option<TransactionId> {
+        self.signed_transaction_data
+            .as_ref()
+            .map(|signed_transaction_data| signed_transaction_data.transaction_payload.id())
+    }
+}
+
+/// Dto for [`TransactionBatch`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBatchDto {
+    pub id: TransactionBatchId,
+    pub transactions: Vec<BatchedTransactionDto>,
+}
+
+impl From<&TransactionBatch> for TransactionBatchDto {
+    fn from(value: &TransactionBatch) -> Self {
+        Self {
+            id: value.id,
+            transactions: value.transactions.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFromDto for TransactionBatch {
+    type Dto = TransactionBatchDto;
+    type Error = Error;
+
+    fn try_from_dto_with_params_inner(dto: Self::Dto, params: ValidationParams<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: dto.id,
+            transactions: dto
+                .transactions
+                .into_iter()
+                .map(|transaction| BatchedTransaction::try_from_dto_with_params(transaction, &params))
+                .collect::<crate::wallet::Result<_>>()?,
+        })
+    }
+}
+
+/// Dto for [`BatchedTransaction`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchedTransactionDto {
+    pub outputs: Vec<OutputDto>,
+    pub signed_transaction_data: Option<SignedTransactionDataDto>,
+    pub confirmed: bool,
+}
+
+impl From<&BatchedTransaction> for BatchedTransactionDto {
+    fn from(value: &BatchedTransaction) -> Self {
+        Self {
+            outputs: value.outputs.iter().map(Into::into).collect(),
+            signed_transaction_data: value.signed_transaction_data.as_ref().map(Into::into),
+            confirmed: value.confirmed,
+        }
+    }
+}
+
+impl TryFromDto for BatchedTransaction {
+    type Dto = BatchedTransactionDto;
+    type Error = Error;
+
+    fn try_from_dto_with_params_inner(dto: Self::Dto, params: ValidationParams<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            outputs: dto
+                .outputs
+                .into_iter()
+                .map(|output| Output::try_from_dto_with_params(output, &params))
+                .collect::<Result<_, _>>()?,
+            signed_transaction_data: dto
+                .signed_transaction_data
+                .map(|signed_transaction_data| {
+                    SignedTransactionData::try_from_dto_with_params(signed_transaction_data, &params)
+                })
+                .transpose()?,
+            confirmed: dto.confirmed,
+        })
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Sends outputs with as many transactions as required to not exceed the maximum number of inputs and outputs of a
+    /// transaction.
+    ///
+    /// Calls [Account::plan_transaction_batch()](crate::wallet::Account::plan_transaction_batch) and
+    /// [Account::process_transaction_batch()](crate::wallet::Account::process_transaction_batch) internally.
+    pub async fn send_outputs_batched(
+        &self,
+        outputs: impl Into<Vec<Output>> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<TransactionBatch> {
+        let batch = self.plan_transaction_batch(outputs).await?;
+
+        self.process_transaction_batch(batch, options).await
+    }
+
+    /// Plans a [`TransactionBatch`] sending the given outputs, without sending anything yet.
+    ///
+    /// With the `storage` feature, the batch is stored until all its transactions are confirmed, so it can be resumed
+    /// with [Account::process_transaction_batch()](crate::wallet::Account::process_transaction_batch) after a
+    /// crash.
+    pub async fn plan_transaction_batch(
+        &self,
+        outputs: impl Into<Vec<Output>> + Send,
+    ) -> crate::wallet::Result<TransactionBatch> {
+        log::debug!("[TRANSACTION] plan_transaction_batch");
+        let outputs = outputs.into();
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+
+        // Check if the outputs have enough amount to cover the storage deposit
+        for output in &outputs {
+            output.verify_storage_deposit(
+                *protocol_parameters.rent_structure(),
+                protocol_parameters.token_supply(),
+            )?;
+        }
+
+        let batch = TransactionBatch::new(outputs)?;
+
+        log::debug!(
+            "[TRANSACTION] planned batch {} of {} transactions",
+            batch.id,
+            batch.transactions.len()
+        );
+
+        #[cfg(feature = "storage")]
+        self.save_transaction_batch(&batch).await?;
+
+        Ok(batch)
+    }
+
+    /// Sends the transactions of a [`TransactionBatch`] that are not confirmed yet, one after the other.
+    ///
+    /// Every transaction is awaited to be confirmed before sending the next one, so that the next one can use its
+    /// remainder. A transaction whose outputs require too many inputs is split in two. If an error occurs, the
+    /// returned progress is lost but the batch can be processed again: signed transactions that the node doesn't know
+    /// are submitted again as they are, and only prepared again once conflicting or when their inputs were spent by
+    /// another transaction.
+    pub async fn process_transaction_batch(
+        &self,
+        mut batch: TransactionBatch,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<TransactionBatch> {
+        log::debug!("[TRANSACTION] process_transaction_batch {}", batch.id);
+        let options = options.into();
+        let mut index = 0;
+
+        while index < batch.transactions.len() {
+            if batch.transactions[index].confirmed {
+                index += 1;
+                continue;
+            }
+
+            if let Some(signed_transaction_data) = &batch.transactions[index].signed_transaction_data {
+                if self
+                    .await_batched_transaction(signed_transaction_data, options.clone())
+                    .await?
+                {
+                    batch.transactions[index].confirmed = true;
+                } else {
+                    log::debug!(
+                        "[TRANSACTION] batched transaction {} has to be prepared again",
+                        signed_transaction_data.transaction_payload.id()
+                    );
+                    batch.transactions[index].signed_transaction_data = None;
+                }
+
+                #[cfg(feature = "storage")]
+                self.save_transaction_batch(&batch).await?;
+                continue;
+            }
+
+            let prepared_transaction_data = match self
+                .prepare_transaction(batch.transactions[index].outputs.clone(), options.clone())
+                .await
+            {
+                Ok(res) => res,
+                Err(err) if is_input_count_exceeded(&err) && batch.transactions[index].outputs.len() > 1 => {
+                    log::debug!("[TRANSACTION] splitting batched transaction {index} requiring too many inputs");
+                    batch.split(index);
+
+                    #[cfg(feature = "storage")]
+                    self.save_transaction_batch(&batch).await?;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
+                Ok(res) => res,
+                Err(err) => {
+                    // unlock outputs so they are available for a new transaction
+                    self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
+                    return Err(err);
+                }
+            };
+
+            // Store the signed transaction before submitting it, so it's not sent twice after a crash
+            batch.transactions[index].signed_transaction_data = Some(signed_transaction_data.clone());
+
+            #[cfg(feature = "storage")]
+            self.save_transaction_batch(&batch).await?;
+
+            self.submit_and_store_transaction(signed_transaction_data, options.clone())
+                .await?;
+        }
+
+        #[cfg(feature = "storage")]
+        {
+            let account_index = self.details().await.index;
+            self.wallet
+                .storage_manager
+                .read()
+                .await
+                .remove_transaction_batch(account_index, &batch.id)
+                .await?;
+        }
+
+        Ok(batch)
+    }
+
+    /// Returns the stored transaction batches that are not finished yet.
+    #[cfg(feature = "storage")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+    pub async fn transaction_batches(&self) -> crate::wallet::Result<Vec<TransactionBatch>> {
+        let account_index = self.details().await.index;
+
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .get_transaction_batches(account_index)
+            .await
+    }
+
+    #[cfg(feature = "storage")]
+    async fn save_transaction_batch(&self, batch: &TransactionBatch) -> crate::wallet::Result<()> {
+        let account_index = self.details().await.index;
+
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .save_transaction_batch(account_index, batch)
+            .await
+    }
+
+    // Waits for a signed transaction of a batch to be confirmed, returns false if it has to be prepared again.
+    async fn await_batched_transaction(
+        &self,
+        signed_transaction_data: &SignedTransactionData,
+        options: Option<TransactionOptions>,
+    ) -> crate::wallet::Result<bool> {
+        let transaction_id = signed_transaction_data.transaction_payload.id();
+
+        match self.batched_transaction_inclusion_state(&transaction_id).await {
+            Some(InclusionState::Confirmed) => return Ok(true),
+            Some(InclusionState::Conflicting) => return Ok(false),
+            Some(InclusionState::Pending) => {}
+            // The transaction was signed but not stored before a crash, or the node doesn't know it anymore: it's sent
+            // again as long as its inputs are unspent.
+            Some(InclusionState::UnknownPruned) | None => {
+                match self.spending_transaction(signed_transaction_data).await? {
+                    Some(spending_transaction_id) => {
+                        self.sync(None).await?;
+                        return Ok(spending_transaction_id == transaction_id);
+                    }
+                    None => {
+                        log::debug!("[TRANSACTION] submitting batched transaction {transaction_id} again");
+                        self.submit_and_store_transaction(signed_transaction_data.clone(), options)
+                            .await?;
+                    }
+                }
+            }
+        }
+
+        self.retry_transaction_until_included(&transaction_id, None, None)
+            .await?;
+        // Sync so that the remainder of the transaction can be used by the next one
+        self.sync(None).await?;
+
+        match self.batched_transaction_inclusion_state(&transaction_id).await {
+            Some(InclusionState::Confirmed) => Ok(true),
+            Some(InclusionState::Conflicting) => Ok(false),
+            inclusion_state => Err(ClientError::TangleInclusion(format!(
+                "transaction id: {transaction_id} inclusion state: {inclusion_state:?}"
+            ))
+            .into()),
+        }
+    }
+
+    async fn batched_transaction_inclusion_state(&self, transaction_id: &TransactionId) -> Option<InclusionState> {
+        self.details()
+            .await
+            .transactions
+            .get(transaction_id)
+            .map(|transaction| transaction.inclusion_state)
+    }
+}
+
+// Whether the input selection failed because the outputs require more inputs than allowed in a transaction.
+fn is_input_count_exceeded(error: &Error) -> bool {
+    match error {
+        Error::Client(error) => matches!(
+            error.as_ref(),
+            ClientError::InputSelection(InputSelectionError::InvalidInputCount(_))
+        ),
+        _ => false,
+    }
+}
@@ -11,12 +11,12 @@ use crate::{
         output::{
             feature::{IssuerFeature, MetadataFeature, SenderFeature, TagFeature},
             unlock_condition::AddressUnlockCondition,
-            NftId, NftOutputBuilder,
+            NftId, NftOutputBuilder, Output,
         },
         ConvertTo,
     },
     wallet::{
-        account::{operations::transaction::Transaction, Account, TransactionOptions},
+        account::{operations::transaction::Transaction, Account, TransactionBatch, TransactionOptions},
         Error as WalletError,
     },
 };
@@ -160,6 +160,34 @@ where
         I::IntoIter: Send,
     {
         log::debug!("[TRANSACTION] prepare_mint_nfts");
+        let outputs = self.build_mint_nfts_outputs(params).await?;
+
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Mints any number of NFTs, with as many transactions as required.
+    ///
+    /// Calls [Account::send_outputs_batched()](crate::wallet::Account::send_outputs_batched) internally.
+    pub async fn mint_nfts_batched<I: IntoIterator<Item = MintNftParams> + Send>(
+        &self,
+        params: I,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<TransactionBatch>
+    where
+        I::IntoIter: Send,
+    {
+        let outputs = self.build_mint_nfts_outputs(params).await?;
+
+        self.send_outputs_batched(outputs, options).await
+    }
+
+    async fn build_mint_nfts_outputs<I: IntoIterator<Item = MintNftParams> + Send>(
+        &self,
+        params: I,
+    ) -> crate::wallet::Result<Vec<Output>>
+    where
+        I::IntoIter: Send,
+    {
         let rent_structure = self.client().get_rent_structure().await?;
         let token_supply = self.client().get_token_supply().await?;
         let account_addresses = self.addresses().await?;
@@ -216,6 +244,6 @@ where
             outputs.push(nft_builder.finish_output(token_supply)?);
         }
 
-        self.prepare_transaction(outputs, options).await
+        Ok(outputs)
     }
 }
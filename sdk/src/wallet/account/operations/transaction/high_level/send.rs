@@ -7,18 +7,19 @@ use serde::{Deserialize, Serialize};
 use crate::{
     client::{api::PreparedTransactionData, secret::SecretManage},
     types::block::{
-        address::Bech32Address,
+        address::{Bech32Address, Hrp},
         output::{
             unlock_condition::{
                 AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition,
             },
-            BasicOutputBuilder, MinimumStorageDepositBasicOutput,
+            BasicOutputBuilder, MinimumStorageDepositBasicOutput, Output, RentStructure,
         },
         ConvertTo,
     },
     wallet::{
         account::{
-            constants::DEFAULT_EXPIRATION_TIME, operations::transaction::Transaction, Account, TransactionOptions,
+            constants::DEFAULT_EXPIRATION_TIME, operations::transaction::Transaction, Account, TransactionBatch,
+            TransactionOptions,
         },
         Error,
     },
@@ -137,85 +138,143 @@ where
     {
         log::debug!("[TRANSACTION] prepare_send");
         let options = options.into();
-        let rent_structure = self.client().get_rent_structure().await?;
-        let token_supply = self.client().get_token_supply().await?;
+        let outputs = self.build_send_outputs(params, options.as_ref()).await?;
 
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Sends base coins to any number of addresses, with as many transactions as required.
+    ///
+    /// Calls [Account::send_outputs_batched()](crate::wallet::Account::send_outputs_batched) internally.
+    pub async fn send_with_params_batched<I: IntoIterator<Item = SendParams> + Send>(
+        &self,
+        params: I,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<TransactionBatch>
+    where
+        I::IntoIter: Send,
+    {
+        let options = options.into();
+        let outputs = self.build_send_outputs(params, options.as_ref()).await?;
+
+        self.send_outputs_batched(outputs, options).await
+    }
+
+    async fn build_send_outputs<I: IntoIterator<Item = SendParams> + Send>(
+        &self,
+        params: I,
+        options: Option<&TransactionOptions>,
+    ) -> crate::wallet::Result<Vec<Output>>
+    where
+        I::IntoIter: Send,
+    {
+        let context = self.send_output_context().await?;
+
+        params
+            .into_iter()
+            .map(|params| context.build_output(params, options))
+            .collect()
+    }
+
+    /// Fetches what the outputs of [`SendParams`] are built with.
+    pub(crate) async fn send_output_context(&self) -> crate::wallet::Result<SendOutputContext> {
         let account_addresses = self.addresses().await?;
-        let default_return_address = account_addresses.first().ok_or(Error::FailedToGetRemainder)?;
 
-        let local_time = self.client().get_time_checked().await?;
+        Ok(SendOutputContext {
+            rent_structure: self.client().get_rent_structure().await?,
+            token_supply: self.client().get_token_supply().await?,
+            bech32_hrp: self.client().get_bech32_hrp().await?,
+            default_return_address: account_addresses.first().ok_or(Error::FailedToGetRemainder)?.address,
+            local_time: self.client().get_time_checked().await?,
+        })
+    }
+}
+
+/// What the outputs of [`SendParams`] are built with, so that building them doesn't fail because of the network.
+pub(crate) struct SendOutputContext {
+    rent_structure: RentStructure,
+    token_supply: u64,
+    bech32_hrp: Hrp,
+    default_return_address: Bech32Address,
+    local_time: u32,
+}
 
-        let mut outputs = Vec::new();
-        for SendParams {
+impl SendOutputContext {
+    /// Builds the output sending the amount of the params, with a storage deposit return if it's a micro amount.
+    pub(crate) fn build_output(
+        &self,
+        SendParams {
             address,
             amount,
             return_address,
             expiration,
-        } in params
-        {
-            self.client().bech32_hrp_matches(address.hrp()).await?;
-            let return_address = return_address
-                .map(|return_address| {
-                    if return_address.hrp() != address.hrp() {
-                        Err(crate::client::Error::Bech32HrpMismatch {
-                            provided: return_address.hrp().to_string(),
-                            expected: address.hrp().to_string(),
-                        })?;
-                    }
-                    Ok::<_, Error>(return_address)
-                })
-                .transpose()?
-                .unwrap_or(default_return_address.address);
-
-            // Get the minimum required amount for an output assuming it does not need a storage deposit.
-            let output = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
-                .add_unlock_condition(AddressUnlockCondition::new(address))
-                .finish_output(token_supply)?;
-
-            if amount >= output.amount() {
-                outputs.push(
-                    BasicOutputBuilder::from(output.as_basic())
-                        .with_amount(amount)
-                        .finish_output(token_supply)?,
-                )
-            } else {
-                let expiration_time = expiration.map_or(local_time + DEFAULT_EXPIRATION_TIME, |expiration_time| {
-                    local_time + expiration_time
-                });
+        }: SendParams,
+        options: Option<&TransactionOptions>,
+    ) -> crate::wallet::Result<Output> {
+        let Self {
+            rent_structure,
+            token_supply,
+            local_time,
+            ..
+        } = *self;
 
-                // Since it does need a storage deposit, calculate how much that should be
-                let storage_deposit_amount = MinimumStorageDepositBasicOutput::new(rent_structure, token_supply)
-                    .with_storage_deposit_return()?
-                    .with_expiration()?
-                    .finish()?;
-
-                if !options.as_ref().map(|o| o.allow_micro_amount).unwrap_or_default() {
-                    return Err(Error::InsufficientFunds {
-                        available: amount,
-                        required: amount + storage_deposit_amount,
-                    });
+        if address.hrp() != &self.bech32_hrp {
+            Err(crate::client::Error::Bech32HrpMismatch {
+                provided: address.hrp().to_string(),
+                expected: self.bech32_hrp.to_string(),
+            })?;
+        }
+        let return_address = return_address
+            .map(|return_address| {
+                if return_address.hrp() != address.hrp() {
+                    Err(crate::client::Error::Bech32HrpMismatch {
+                        provided: return_address.hrp().to_string(),
+                        expected: address.hrp().to_string(),
+                    })?;
                 }
+                Ok::<_, Error>(return_address)
+            })
+            .transpose()?
+            .unwrap_or(self.default_return_address);
 
-                outputs.push(
-                    // Add address_and_amount.amount+storage_deposit_amount, so receiver can get
-                    // address_and_amount.amount
-                    BasicOutputBuilder::from(output.as_basic())
-                        .with_amount(amount + storage_deposit_amount)
-                        .add_unlock_condition(
-                            // We send the storage_deposit_amount back to the sender, so only the additional amount is
-                            // sent
-                            StorageDepositReturnUnlockCondition::new(
-                                return_address,
-                                storage_deposit_amount,
-                                token_supply,
-                            )?,
-                        )
-                        .add_unlock_condition(ExpirationUnlockCondition::new(return_address, expiration_time)?)
-                        .finish_output(token_supply)?,
-                )
+        // Get the minimum required amount for an output assuming it does not need a storage deposit.
+        let output = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_output(token_supply)?;
+
+        if amount >= output.amount() {
+            Ok(BasicOutputBuilder::from(output.as_basic())
+                .with_amount(amount)
+                .finish_output(token_supply)?)
+        } else {
+            let expiration_time = expiration.map_or(local_time + DEFAULT_EXPIRATION_TIME, |expiration_time| {
+                local_time + expiration_time
+            });
+
+            // Since it does need a storage deposit, calculate how much that should be
+            let storage_deposit_amount = MinimumStorageDepositBasicOutput::new(rent_structure, token_supply)
+                .with_storage_deposit_return()?
+                .with_expiration()?
+                .finish()?;
+
+            if !options.map(|o| o.allow_micro_amount).unwrap_or_default() {
+                return Err(Error::InsufficientFunds {
+                    available: amount,
+                    required: amount + storage_deposit_amount,
+                });
             }
-        }
 
-        self.prepare_transaction(outputs, options).await
+            // Add address_and_amount.amount+storage_deposit_amount, so receiver can get
+            // address_and_amount.amount
+            Ok(BasicOutputBuilder::from(output.as_basic())
+                .with_amount(amount + storage_deposit_amount)
+                .add_unlock_condition(
+                    // We send the storage_deposit_amount back to the sender, so only the additional amount is
+                    // sent
+                    StorageDepositReturnUnlockCondition::new(return_address, storage_deposit_amount, token_supply)?,
+                )
+                .add_unlock_condition(ExpirationUnlockCondition::new(return_address, expiration_time)?)
+                .finish_output(token_supply)?)
+        }
     }
 }
@@ -13,13 +13,14 @@ use crate::{
             unlock_condition::{
                 AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition,
             },
-            BasicOutputBuilder, MinimumStorageDepositBasicOutput, NativeToken, NativeTokens, TokenId,
+            BasicOutputBuilder, MinimumStorageDepositBasicOutput, NativeToken, NativeTokens, Output, TokenId,
         },
         ConvertTo,
     },
     wallet::{
         account::{
-            constants::DEFAULT_EXPIRATION_TIME, operations::transaction::Transaction, Account, TransactionOptions,
+            constants::DEFAULT_EXPIRATION_TIME, operations::transaction::Transaction, Account, TransactionBatch,
+            TransactionOptions,
         },
         Error, Result,
     },
@@ -128,6 +129,34 @@ where
         I::IntoIter: Send,
     {
         log::debug!("[TRANSACTION] prepare_send_native_tokens");
+        let outputs = self.build_send_native_tokens_outputs(params).await?;
+
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Sends native tokens to any number of addresses, with as many transactions as required.
+    ///
+    /// Calls [Account::send_outputs_batched()](crate::wallet::Account::send_outputs_batched) internally.
+    pub async fn send_native_tokens_batched<I: IntoIterator<Item = SendNativeTokensParams> + Send>(
+        &self,
+        params: I,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<TransactionBatch>
+    where
+        I::IntoIter: Send,
+    {
+        let outputs = self.build_send_native_tokens_outputs(params).await?;
+
+        self.send_outputs_batched(outputs, options).await
+    }
+
+    async fn build_send_native_tokens_outputs<I: IntoIterator<Item = SendNativeTokensParams> + Send>(
+        &self,
+        params: I,
+    ) -> crate::wallet::Result<Vec<Output>>
+    where
+        I::IntoIter: Send,
+    {
         let rent_structure = self.client().get_rent_structure().await?;
         let token_supply = self.client().get_token_supply().await?;
 
@@ -194,6 +223,6 @@ where
             )
         }
 
-        self.prepare_transaction(outputs, options).await
+        Ok(outputs)
     }
 }
@@ -0,0 +1,323 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{api::input_selection::sdruc_not_expired, secret::SecretManage},
+    types::block::{
+        address::{Address, Bech32Address},
+        input::INPUT_COUNT_MAX,
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, GovernorAddressUnlockCondition, StateControllerAddressUnlockCondition,
+                UnlockCondition,
+            },
+            AliasOutputBuilder, AliasTransition, BasicOutputBuilder, NativeTokens, NativeTokensBuilder, NftOutput,
+            NftOutputBuilder, Output, RentStructure, OUTPUT_COUNT_MAX,
+        },
+        ConvertTo,
+    },
+    wallet::{
+        account::{
+            operations::{helpers::time::can_output_be_unlocked_now, output_claiming::get_new_native_token_count},
+            types::{OutputData, Transaction},
+            Account, TransactionOptions,
+        },
+        Error,
+    },
+};
+
+/// Options for [Account::sweep()](crate::wallet::Account::sweep).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepOptions {
+    /// Also transfers the aliases governed by the account, and with them the control of their foundries.
+    #[serde(default)]
+    pub include_aliases: bool,
+}
+
+impl SweepOptions {
+    /// Creates new [`SweepOptions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the aliases governed by the account are transferred too.
+    pub fn with_include_aliases(mut self, include_aliases: bool) -> Self {
+        self.include_aliases = include_aliases;
+        self
+    }
+}
+
+/// The transactions sent by [Account::sweep()](crate::wallet::Account::sweep).
+#[derive(Debug)]
+pub struct Sweep {
+    /// The transactions that were sent.
+    pub transactions: Vec<Transaction>,
+    /// The error that stopped the sweep after some transactions were sent, so not everything was swept.
+    pub error: Option<Error>,
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Sends everything the account holds to a target address: base coins, native tokens, NFTs and, if enabled in the
+    /// options, aliases with their foundries.
+    ///
+    /// Outputs that can be claimed are claimed on the way, returning their storage deposits, timelocked outputs are
+    /// left. Uses as many transactions as required, which don't depend on each other, and returns them. If one of them
+    /// fails, the transactions sent before are returned with the error.
+    pub async fn sweep(
+        &self,
+        target: impl ConvertTo<Bech32Address>,
+        options: impl Into<Option<SweepOptions>> + Send,
+    ) -> crate::wallet::Result<Sweep> {
+        log::debug!("[TRANSACTION] sweep");
+        let target = target.convert()?;
+        self.client().bech32_hrp_matches(target.hrp()).await?;
+        let options = options.into().unwrap_or_default();
+        let current_time = self.client().get_time_checked().await?;
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+
+        let mut inputs = Vec::new();
+        let account_details = self.details().await;
+
+        for (output_id, output_data) in account_details.unspent_outputs() {
+            if account_details.locked_outputs.contains(output_id) {
+                continue;
+            }
+
+            let alias_transition = match &output_data.output {
+                Output::Basic(_) | Output::Nft(_) => None,
+                // Transferring the alias requires a governance transition.
+                Output::Alias(_) if options.include_aliases => Some(AliasTransition::Governance),
+                _ => continue,
+            };
+
+            if can_output_be_unlocked_now(
+                &account_details.addresses_with_unspent_outputs,
+                &[],
+                output_data,
+                current_time,
+                alias_transition,
+            )? {
+                inputs.push(output_data.clone());
+            }
+        }
+
+        drop(account_details);
+
+        // Inputs that need funds from other inputs, because they become chain outputs or have a storage deposit to
+        // return, come first so they end up in the same transaction as the highest amounts.
+        inputs.sort_by_key(|input| {
+            (
+                input.output.is_basic() && sdruc_not_expired(&input.output, current_time).is_none(),
+                std::cmp::Reverse(input.output.amount()),
+            )
+        });
+
+        let mut transactions = Vec::new();
+
+        for inputs in split_sweep_inputs(inputs, current_time)? {
+            log::debug!("[TRANSACTION] sweeping {} inputs", inputs.len());
+
+            match self
+                .sweep_inputs(&inputs, target.into_inner(), current_time, rent_structure, token_supply)
+                .await
+            {
+                Ok(transaction) => transactions.push(transaction),
+                // Nothing was sent yet, so there is nothing to return with the error.
+                Err(error) if transactions.is_empty() => return Err(error),
+                Err(error) => {
+                    return Ok(Sweep {
+                        transactions,
+                        error: Some(error),
+                    });
+                }
+            }
+        }
+
+        Ok(Sweep {
+            transactions,
+            error: None,
+        })
+    }
+
+    async fn sweep_inputs(
+        &self,
+        inputs: &[OutputData],
+        target: Address,
+        current_time: u32,
+        rent_structure: RentStructure,
+        token_supply: u64,
+    ) -> crate::wallet::Result<Transaction> {
+        let outputs = sweep_outputs(inputs, target, current_time, rent_structure, token_supply)?;
+        let options = TransactionOptions {
+            custom_inputs: Some(inputs.iter().map(|input| input.output_id).collect()),
+            ..Default::default()
+        };
+
+        let prepared_transaction = self.prepare_transaction(outputs, options.clone()).await?;
+
+        self.sign_and_submit_transaction(prepared_transaction, options).await
+    }
+}
+
+// Splits the inputs in sets that don't exceed the maximum number of inputs, outputs and native tokens of a transaction.
+fn split_sweep_inputs(mut inputs: Vec<OutputData>, current_time: u32) -> crate::wallet::Result<Vec<Vec<OutputData>>> {
+    let mut sets = Vec::new();
+
+    while !inputs.is_empty() {
+        let mut set = Vec::new();
+        let mut skipped = Vec::new();
+        let mut native_tokens = NativeTokensBuilder::new();
+        let mut return_addresses = HashSet::new();
+        // The output sweeping the base coins and native tokens.
+        let mut output_count = 1;
+
+        for input in inputs {
+            let return_address = sdruc_not_expired(&input.output, current_time)
+                .map(|sdr| *sdr.return_address())
+                .filter(|address| !return_addresses.contains(address));
+            let new_output_count =
+                output_count + usize::from(!input.output.is_basic()) + usize::from(return_address.is_some());
+            let exceeds_native_tokens = match &input.output {
+                Output::Basic(basic) => {
+                    get_new_native_token_count(&native_tokens, basic.native_tokens())? > NativeTokens::COUNT_MAX.into()
+                }
+                _ => false,
+            };
+
+            if set.len() == usize::from(INPUT_COUNT_MAX)
+                || new_output_count > usize::from(OUTPUT_COUNT_MAX)
+                || exceeds_native_tokens
+            {
+                skipped.push(input);
+                continue;
+            }
+
+            if let Output::Basic(basic) = &input.output {
+                native_tokens.add_native_tokens(basic.native_tokens().clone())?;
+            }
+            if let Some(return_address) = return_address {
+                return_addresses.insert(return_address);
+            }
+            output_count = new_output_count;
+            set.push(input);
+        }
+
+        sets.push(set);
+        inputs = skipped;
+    }
+
+    Ok(sets)
+}
+
+// Builds the outputs sending the inputs to the target, storage deposits to return are added by the input selection.
+fn sweep_outputs(
+    inputs: &[OutputData],
+    target: Address,
+    current_time: u32,
+    rent_structure: RentStructure,
+    token_supply: u64,
+) -> crate::wallet::Result<Vec<Output>> {
+    let mut outputs = Vec::new();
+    let mut available = 0;
+    let mut required = 0;
+    let mut native_tokens = NativeTokensBuilder::new();
+
+    for input in inputs {
+        available += input.output.amount();
+
+        if let Some(sdr) = sdruc_not_expired(&input.output, current_time) {
+            required += sdr.amount();
+        }
+
+        match &input.output {
+            Output::Basic(basic) => {
+                native_tokens.add_native_tokens(basic.native_tokens().clone())?;
+            }
+            Output::Nft(nft) => {
+                let output = NftOutputBuilder::from(nft)
+                    .with_nft_id(nft.nft_id_non_null(&input.output_id))
+                    .with_unlock_conditions([AddressUnlockCondition::new(target)])
+                    .finish_output(token_supply)?;
+
+                required += output.amount();
+                outputs.push(output);
+            }
+            Output::Alias(alias) => {
+                let output = AliasOutputBuilder::from(alias)
+                    .with_alias_id(alias.alias_id_non_null(&input.output_id))
+                    .with_unlock_conditions([
+                        UnlockCondition::from(StateControllerAddressUnlockCondition::new(target)),
+                        UnlockCondition::from(GovernorAddressUnlockCondition::new(target)),
+                    ])
+                    .finish_output(token_supply)?;
+
+                required += output.amount();
+                outputs.push(output);
+            }
+            _ => {}
+        }
+    }
+
+    let native_tokens = native_tokens.finish()?;
+
+    if available < required {
+        return Err(Error::InsufficientFunds { available, required });
+    }
+
+    let remainder = available - required;
+
+    // Only chain outputs were swept and their amounts are exactly covered.
+    if remainder == 0 && native_tokens.is_empty() && !outputs.is_empty() {
+        return Ok(outputs);
+    }
+
+    let remainder_output = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+        .with_native_tokens(native_tokens.clone())
+        .add_unlock_condition(AddressUnlockCondition::new(target))
+        .finish_output(token_supply)?;
+    let minimum_amount = remainder_output.amount();
+
+    if remainder >= minimum_amount {
+        outputs.push(
+            BasicOutputBuilder::from(remainder_output.as_basic())
+                .with_amount(remainder)
+                .finish_output(token_supply)?,
+        );
+    } else if let Some(output) = outputs.iter_mut().find(|output| output.is_nft()) {
+        // The remainder doesn't cover the storage deposit of an output of its own, so an NFT output takes it. Alias
+        // outputs can't, their amount and native tokens can't change in a governance transition.
+        *output = merge_remainder(output.as_nft(), remainder, native_tokens, token_supply)?;
+    } else {
+        return Err(Error::InsufficientFunds {
+            available,
+            required: required + minimum_amount,
+        });
+    }
+
+    Ok(outputs)
+}
+
+// Adds the base coins and native tokens of the remainder to an NFT output.
+fn merge_remainder(
+    nft: &NftOutput,
+    amount: u64,
+    native_tokens: NativeTokens,
+    token_supply: u64,
+) -> crate::wallet::Result<Output> {
+    let mut merged_native_tokens = NativeTokensBuilder::from(native_tokens);
+    merged_native_tokens.add_native_tokens(nft.native_tokens().clone())?;
+
+    Ok(NftOutputBuilder::from(nft)
+        .with_amount(nft.amount() + amount)
+        .with_native_tokens(merged_native_tokens.finish()?)
+        .finish_output(token_supply)?)
+}
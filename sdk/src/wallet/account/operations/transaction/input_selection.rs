@@ -1,13 +1,21 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{hash_map::Values, HashSet};
+use std::{
+    collections::{hash_map::Values, HashSet},
+    sync::Arc,
+};
 
 #[cfg(feature = "events")]
-use crate::wallet::events::types::{TransactionProgressEvent, WalletEvent};
+use crate::{
+    client::api::input_selection::SelectionTraceDto,
+    wallet::events::types::{TransactionProgressEvent, WalletEvent},
+};
 use crate::{
     client::{
-        api::input_selection::{is_alias_transition, Burn, InputSelection, Selected},
+        api::input_selection::{
+            is_alias_transition, Burn, InputSelection, RejectionReason, Selected, SelectionStrategy, SelectionTrace,
+        },
         secret::{types::InputSigningData, SecretManage},
     },
     types::block::{
@@ -31,6 +39,7 @@ where
         mandatory_inputs: Option<HashSet<OutputId>>,
         remainder_address: Option<Address>,
         burn: Option<&Burn>,
+        selection_strategy: Option<&Arc<dyn SelectionStrategy>>,
     ) -> crate::wallet::Result<Selected> {
         log::debug!("[TRANSACTION] select_inputs");
         // Voting output needs to be requested before to prevent a deadlock
@@ -71,6 +80,7 @@ where
 
         // Filter inputs to not include inputs that require additional outputs for storage deposit return or could be
         // still locked.
+        let mut trace = SelectionTrace::default();
         let available_outputs_signing_data = filter_inputs(
             &account_details,
             account_details.unspent_outputs.values(),
@@ -79,6 +89,7 @@ where
             burn,
             custom_inputs.as_ref(),
             mandatory_inputs.as_ref(),
+            &mut trace,
         )?;
 
         // if custom inputs are provided we should only use them (validate if we have the outputs in this account and
@@ -110,7 +121,13 @@ where
                 input_selection = input_selection.burn(burn.clone());
             }
 
-            let selected_transaction_data = input_selection.select()?;
+            if let Some(strategy) = selection_strategy {
+                input_selection = input_selection.strategy(strategy.clone());
+            }
+
+            let selected_transaction_data = self
+                .select_with_trace(account_details.index, input_selection, trace)
+                .await?;
 
             // lock outputs so they don't get used by another transaction
             for output in &selected_transaction_data.inputs {
@@ -145,7 +162,13 @@ where
                 input_selection = input_selection.burn(burn.clone());
             }
 
-            let selected_transaction_data = input_selection.select()?;
+            if let Some(strategy) = selection_strategy {
+                input_selection = input_selection.strategy(strategy.clone());
+            }
+
+            let selected_transaction_data = self
+                .select_with_trace(account_details.index, input_selection, trace)
+                .await?;
 
             // lock outputs so they don't get used by another transaction
             for output in &selected_transaction_data.inputs {
@@ -176,22 +199,13 @@ where
             input_selection = input_selection.burn(burn.clone());
         }
 
-        let selected_transaction_data = match input_selection.select() {
-            Ok(r) => r,
-            // TODO this error doesn't exist with the new ISA
-            // Err(crate::client::Error::ConsolidationRequired(output_count)) => {
-            //     #[cfg(feature = "events")]
-            //     self.event_emitter
-            //         .lock()
-            //         .await
-            //         .emit(account.index, WalletEvent::ConsolidationRequired);
-            //     return Err(crate::wallet::Error::ConsolidationRequired {
-            //         output_count,
-            //         output_count_max: INPUT_COUNT_MAX,
-            //     });
-            // }
-            Err(e) => return Err(e.into()),
-        };
+        if let Some(strategy) = selection_strategy {
+            input_selection = input_selection.strategy(strategy.clone());
+        }
+
+        let selected_transaction_data = self
+            .select_with_trace(account_details.index, input_selection, trace)
+            .await?;
 
         // lock outputs so they don't get used by another transaction
         for output in &selected_transaction_data.inputs {
@@ -201,6 +215,44 @@ where
 
         Ok(selected_transaction_data)
     }
+
+    /// Returns the trace of the last input selection of the account if it failed, explaining which requirement
+    /// couldn't be met and why inputs weren't available.
+    pub async fn last_selection_trace(&self) -> Option<SelectionTrace> {
+        self.last_selection_trace.lock().await.clone()
+    }
+
+    /// Runs the input selection and, if it fails, keeps and emits the trace explaining why.
+    #[cfg_attr(not(feature = "events"), allow(unused_variables))]
+    async fn select_with_trace(
+        &self,
+        account_index: u32,
+        input_selection: InputSelection,
+        mut trace: SelectionTrace,
+    ) -> crate::wallet::Result<Selected> {
+        let (selected_transaction_data, input_selection_trace) = input_selection.select_with_trace();
+
+        if selected_transaction_data.is_err() {
+            // Inputs rejected by the wallet come first, as they never reached the input selection.
+            trace.requirements = input_selection_trace.requirements;
+            trace.rejected_inputs.extend(input_selection_trace.rejected_inputs);
+
+            #[cfg(feature = "events")]
+            self.emit(
+                account_index,
+                WalletEvent::TransactionProgress(TransactionProgressEvent::SelectingInputsFailed(Box::new(
+                    SelectionTraceDto::from(&trace),
+                ))),
+            )
+            .await;
+
+            *self.last_selection_trace.lock().await = Some(trace);
+        } else {
+            *self.last_selection_trace.lock().await = None;
+        }
+
+        Ok(selected_transaction_data?)
+    }
 }
 
 /// Filter available outputs to only include outputs that don't have unlock conditions, that could create
@@ -230,6 +282,7 @@ fn filter_inputs(
     burn: Option<&Burn>,
     custom_inputs: Option<&HashSet<OutputId>>,
     mandatory_inputs: Option<&HashSet<OutputId>>,
+    trace: &mut SelectionTrace,
 ) -> crate::wallet::Result<Vec<InputSigningData>> {
     let mut available_outputs_signing_data = Vec::new();
 
@@ -251,6 +304,10 @@ fn filter_inputs(
 
             // Outputs that could get unlocked in the future will not be included
             if !output_can_be_unlocked_now_and_in_future {
+                trace.reject(
+                    output_data.output_id,
+                    rejection_reason(&output_data.output, current_time),
+                );
                 continue;
             }
         }
@@ -265,3 +322,32 @@ fn filter_inputs(
 
     Ok(available_outputs_signing_data)
 }
+
+// Why an output that can't be unlocked forever from now on is not available for the input selection.
+fn rejection_reason(output: &Output, current_time: u32) -> RejectionReason {
+    let Some(unlock_conditions) = output.unlock_conditions() else {
+        return RejectionReason::UnsupportedKind;
+    };
+
+    if let Some(timelock) = unlock_conditions.timelock() {
+        if unlock_conditions.is_time_locked(current_time) {
+            return RejectionReason::Timelocked {
+                until: timelock.timestamp(),
+            };
+        }
+    }
+
+    let Some(expiration) = unlock_conditions.expiration() else {
+        return RejectionReason::UnsupportedKind;
+    };
+
+    // If expired, the return address doesn't belong to the account.
+    expiration.return_address_expired(current_time).map_or_else(
+        || RejectionReason::Expiring {
+            expires_at: expiration.timestamp(),
+        },
+        |return_address| RejectionReason::NotOwned {
+            required_address: *return_address,
+        },
+    )
+}
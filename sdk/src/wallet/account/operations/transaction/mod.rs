@@ -1,12 +1,14 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod batch;
 mod build_transaction;
 pub(crate) mod high_level;
 mod input_selection;
 mod options;
 pub(crate) mod prepare_output;
 mod prepare_transaction;
+pub(crate) mod replace;
 mod sign_transaction;
 pub(crate) mod submit_transaction;
 
@@ -20,8 +22,9 @@ use crate::{
     types::{
         api::core::response::OutputWithMetadataResponse,
         block::{
+            input::Input,
             output::{dto::OutputDto, Output},
-            payload::transaction::TransactionPayload,
+            payload::transaction::{TransactionEssence, TransactionId, TransactionPayload},
             semantic::ConflictReason,
         },
     },
@@ -201,8 +204,54 @@ where
         Ok(transaction)
     }
 
+    /// Makes sure a signed transaction that may not have been submitted before a crash is sent, by submitting it again
+    /// if it isn't stored in the account and its inputs are unspent. Returns false if its inputs were spent by another
+    /// transaction, so it has to be prepared again.
+    pub(crate) async fn ensure_transaction_submitted(
+        &self,
+        signed_transaction_data: SignedTransactionData,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<bool> {
+        let transaction_id = signed_transaction_data.transaction_payload.id();
+
+        if self.details().await.transactions.contains_key(&transaction_id) {
+            return Ok(true);
+        }
+
+        match self.spending_transaction(&signed_transaction_data).await? {
+            Some(spending_transaction_id) => Ok(spending_transaction_id == transaction_id),
+            None => {
+                log::debug!("[TRANSACTION] submitting transaction {transaction_id} again");
+                self.submit_and_store_transaction(signed_transaction_data, options)
+                    .await?;
+                Ok(true)
+            }
+        }
+    }
+
+    // Returns the id of the transaction that spent the inputs of a signed transaction, if they are spent.
+    pub(crate) async fn spending_transaction(
+        &self,
+        signed_transaction_data: &SignedTransactionData,
+    ) -> crate::wallet::Result<Option<TransactionId>> {
+        let TransactionEssence::Regular(essence) = signed_transaction_data.transaction_payload.essence();
+
+        for input in essence.inputs() {
+            let Input::Utxo(input) = input else {
+                continue;
+            };
+            let metadata = self.client().get_output_metadata(input.output_id()).await?;
+
+            if let Some(transaction_id) = metadata.transaction_id_spent() {
+                return Ok(Some(*transaction_id));
+            }
+        }
+
+        Ok(None)
+    }
+
     // unlock outputs
-    async fn unlock_inputs(&self, inputs: &[InputSigningData]) -> crate::wallet::Result<()> {
+    pub(crate) async fn unlock_inputs(&self, inputs: &[InputSigningData]) -> crate::wallet::Result<()> {
         let mut account_details = self.details_mut().await;
         for input_signing_data in inputs {
             let output_id = input_signing_data.output_id();
@@ -1,10 +1,12 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    client::api::input_selection::{Burn, BurnDto},
+    client::api::input_selection::{BuiltinSelectionStrategy, Burn, BurnDto, SelectionStrategy},
     types::block::{
         output::OutputId,
         payload::{dto::TaggedDataPayloadDto, tagged_data::TaggedDataPayload},
@@ -25,6 +27,8 @@ pub struct TransactionOptions {
     pub burn: Option<Burn>,
     pub note: Option<String>,
     pub allow_micro_amount: bool,
+    // The strategy used to select the inputs, smallest amounts first by default.
+    pub selection_strategy: Option<Arc<dyn SelectionStrategy>>,
 }
 
 impl TransactionOptions {
@@ -38,6 +42,9 @@ impl TransactionOptions {
             burn: value.burn.map(Burn::try_from).transpose()?,
             note: value.note,
             allow_micro_amount: value.allow_micro_amount,
+            selection_strategy: value
+                .selection_strategy
+                .map(|strategy| Arc::new(strategy) as Arc<dyn SelectionStrategy>),
         })
     }
 }
@@ -60,6 +67,8 @@ pub struct TransactionOptionsDto {
     pub note: Option<String>,
     #[serde(default)]
     pub allow_micro_amount: bool,
+    #[serde(default)]
+    pub selection_strategy: Option<BuiltinSelectionStrategy>,
 }
 
 #[allow(clippy::enum_variant_names)]
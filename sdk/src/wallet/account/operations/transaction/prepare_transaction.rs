@@ -113,6 +113,7 @@ where
                     .map(|inputs| HashSet::from_iter(inputs.clone())),
                 remainder_address,
                 options.as_ref().and_then(|options| options.burn.as_ref()),
+                options.as_ref().and_then(|options| options.selection_strategy.as_ref()),
             )
             .await?;
 
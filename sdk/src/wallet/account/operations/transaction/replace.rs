@@ -0,0 +1,219 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use crate::{
+    client::{api::input_selection::sdruc_not_expired, secret::SecretManage},
+    types::block::{
+        input::Input,
+        output::{
+            unlock_condition::AddressUnlockCondition, AliasOutputBuilder, BasicOutputBuilder, FoundryOutputBuilder,
+            NativeTokensBuilder, NftOutputBuilder, Output, OutputId,
+        },
+        payload::transaction::{TransactionEssence, TransactionId},
+    },
+    wallet::{
+        account::{
+            types::{OutputData, Transaction},
+            Account, TransactionOptions,
+        },
+        Error,
+    },
+};
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Replaces a pending transaction by a transaction creating the provided outputs instead.
+    ///
+    /// The replacement spends the inputs of the pending transaction again, and additional inputs if required, so only
+    /// one of both transactions can be confirmed. Once one of them is, syncing marks the other one as conflicting and
+    /// makes its inputs that didn't get spent available again.
+    pub async fn replace_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        outputs: impl Into<Vec<Output>> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        log::debug!("[TRANSACTION] replace_transaction {transaction_id}");
+        let (_, inputs) = self.replaceable_transaction(transaction_id).await?;
+
+        self.finish_replacement(&inputs, outputs.into(), options.into()).await
+    }
+
+    /// Cancels a pending transaction by sending its inputs back to the account in a transaction conflicting with it.
+    ///
+    /// Aliases, foundries and NFTs stay as they are, base coins and native tokens are sent to an address of the
+    /// account. See [Account::replace_transaction()](crate::wallet::Account::replace_transaction).
+    pub async fn cancel_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        log::debug!("[TRANSACTION] cancel_transaction {transaction_id}");
+        let (transaction, inputs) = self.replaceable_transaction(transaction_id).await?;
+        let current_time = self.client().get_time_checked().await?;
+        let token_supply = self.client().get_token_supply().await?;
+        let outputs = cancel_outputs(&transaction, &inputs, current_time, token_supply)?;
+
+        self.finish_replacement(&inputs, outputs, options.into()).await
+    }
+
+    // Returns a pending transaction with its inputs, which have to be unspent to spend them again.
+    async fn replaceable_transaction(
+        &self,
+        transaction_id: &TransactionId,
+    ) -> crate::wallet::Result<(Transaction, Vec<OutputData>)> {
+        let account_details = self.details().await;
+        let transaction = account_details
+            .transactions
+            .get(transaction_id)
+            .ok_or(Error::TransactionNotFound(*transaction_id))?;
+
+        if !account_details.pending_transactions.contains(transaction_id) {
+            return Err(Error::TransactionNotPending(*transaction_id));
+        }
+
+        let inputs = transaction_inputs(transaction)
+            .iter()
+            .map(|output_id| {
+                account_details
+                    .unspent_outputs
+                    .get(output_id)
+                    .cloned()
+                    .ok_or(Error::TransactionNotPending(*transaction_id))
+            })
+            .collect::<crate::wallet::Result<Vec<_>>>()?;
+
+        Ok((transaction.clone(), inputs))
+    }
+
+    // Sends a transaction spending the inputs of a replaced transaction again. They are locked by the replaced
+    // transaction, so they get unlocked to be selected and locked again if the replacement fails.
+    async fn finish_replacement(
+        &self,
+        inputs: &[OutputData],
+        outputs: Vec<Output>,
+        options: Option<TransactionOptions>,
+    ) -> crate::wallet::Result<Transaction> {
+        let input_ids = inputs.iter().map(|input| input.output_id).collect::<HashSet<_>>();
+        let mut options = options.unwrap_or_default();
+        // Custom inputs are the only inputs, so the replaced ones have to be part of them.
+        let required_inputs = match &mut options.custom_inputs {
+            Some(custom_inputs) => custom_inputs,
+            None => options.mandatory_inputs.get_or_insert_with(Vec::new),
+        };
+        required_inputs.retain(|output_id| !input_ids.contains(output_id));
+        required_inputs.extend(input_ids.iter().copied());
+
+        let mut account_details = self.details_mut().await;
+        for output_id in &input_ids {
+            account_details.locked_outputs.remove(output_id);
+        }
+        drop(account_details);
+
+        let result = match self.prepare_transaction(outputs, options.clone()).await {
+            Ok(prepared_transaction) => self.sign_and_submit_transaction(prepared_transaction, options).await,
+            Err(err) => Err(err),
+        };
+
+        if result.is_err() {
+            self.details_mut().await.locked_outputs.extend(input_ids);
+        }
+
+        result
+    }
+}
+
+/// Returns the ids of the outputs spent by a transaction.
+pub(crate) fn transaction_inputs(transaction: &Transaction) -> HashSet<OutputId> {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+
+    essence
+        .inputs()
+        .iter()
+        .filter_map(|input| match input {
+            Input::Utxo(input) => Some(*input.output_id()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Builds the outputs sending the inputs back to the account. Chain outputs are kept, other funds go to the remainder
+// unless there is no chain output.
+fn cancel_outputs(
+    transaction: &Transaction,
+    inputs: &[OutputData],
+    current_time: u32,
+    token_supply: u64,
+) -> crate::wallet::Result<Vec<Output>> {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    let mut outputs = Vec::new();
+    let mut amount = 0;
+    let mut native_tokens = NativeTokensBuilder::new();
+    let mut address = None;
+
+    for input in inputs {
+        match &input.output {
+            Output::Basic(basic) => {
+                let (unlock_address, _) =
+                    input
+                        .output
+                        .required_and_unlocked_address(current_time, &input.output_id, None)?;
+
+                amount += basic.amount() - sdruc_not_expired(&input.output, current_time).map_or(0, |sdr| sdr.amount());
+                native_tokens.add_native_tokens(basic.native_tokens().clone())?;
+                address.get_or_insert(unlock_address);
+            }
+            Output::Nft(nft) => {
+                let (unlock_address, _) =
+                    input
+                        .output
+                        .required_and_unlocked_address(current_time, &input.output_id, None)?;
+
+                outputs.push(
+                    NftOutputBuilder::from(nft)
+                        .with_nft_id(nft.nft_id_non_null(&input.output_id))
+                        .with_unlock_conditions([AddressUnlockCondition::new(unlock_address)])
+                        .finish_output(token_supply)?,
+                );
+            }
+            Output::Alias(alias) => {
+                let alias_id = alias.alias_id_non_null(&input.output_id);
+                // Keep the kind of transition of the replaced transaction, as it determines the required signature.
+                let state_transition = essence.outputs().iter().any(|output| match output {
+                    Output::Alias(output) => {
+                        output.alias_id() == &alias_id && output.state_index() == alias.state_index() + 1
+                    }
+                    _ => false,
+                });
+
+                outputs.push(
+                    AliasOutputBuilder::from(alias)
+                        .with_alias_id(alias_id)
+                        .with_state_index(alias.state_index() + u32::from(state_transition))
+                        .finish_output(token_supply)?,
+                );
+            }
+            Output::Foundry(foundry) => {
+                outputs.push(FoundryOutputBuilder::from(foundry).finish_output(token_supply)?);
+            }
+            Output::Treasury(_) => {}
+        }
+    }
+
+    if outputs.is_empty() {
+        let address = address.ok_or(Error::MissingParameter("address"))?;
+
+        outputs.push(
+            BasicOutputBuilder::new_with_amount(amount)
+                .with_native_tokens(native_tokens.finish()?)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .finish_output(token_supply)?,
+        );
+    }
+
+    Ok(outputs)
+}
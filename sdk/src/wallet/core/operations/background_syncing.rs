@@ -56,7 +56,12 @@ where
                             break 'outer;
                         }
                         match account.sync(options.clone()).await {
-                            Ok(_) => {}
+                            Ok(_) => {
+                                // Send the instalments of scheduled payments that are due
+                                if let Err(err) = account.execute_scheduled_payments(None).await {
+                                    log::debug!("[background_syncing] scheduled payments error: {}", err);
+                                }
+                            }
                             Err(err) => log::debug!("[background_syncing] error: {}", err),
                         };
                     }
@@ -1,11 +1,16 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use super::storage::SaveLoadWallet;
 use crate::{
     client::{
+        cache::ResponseCache,
+        light_client::MilestoneVerifier,
         node_manager::{
             builder::NodeManagerBuilder,
             node::{Node, NodeAuth, NodeDto},
@@ -44,6 +49,9 @@ where
             pow_worker_count,
             #[cfg(not(target_family = "wasm"))]
             max_parallel_api_requests,
+            response_cache_capacity,
+            response_cache_storage,
+            milestone_verification,
         } = client_options;
 
         // Only check bech32 if something in the node_manager_builder changed
@@ -64,6 +72,22 @@ where
         {
             *self.client.mqtt.broker_options.write().await = broker_options;
         }
+        {
+            let mut response_cache = self.client.response_cache.write().await;
+            let unchanged = response_cache.as_ref().map(|cache| (cache.capacity(), cache.storage()))
+                == response_cache_capacity.map(|capacity| (capacity, response_cache_storage.clone()));
+            if !unchanged {
+                *response_cache = response_cache_capacity
+                    .map(|capacity| Arc::new(ResponseCache::new(capacity, response_cache_storage)));
+            }
+        }
+        {
+            let mut milestone_verifier = self.client.milestone_verifier.write().await;
+            // Keep the verified milestones if the coordinator configuration didn't change.
+            if milestone_verifier.as_ref().map(|verifier| verifier.config()) != milestone_verification.as_ref() {
+                *milestone_verifier = milestone_verification.map(|config| Arc::new(MilestoneVerifier::new(config)));
+            }
+        }
 
         if change_in_node_manager {
             // Update the protocol of the network_info to not have the default data, which can be wrong
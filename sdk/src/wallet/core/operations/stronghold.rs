@@ -3,7 +3,7 @@
 
 use std::time::Duration;
 
-use crypto::keys::bip39::Mnemonic;
+use crypto::keys::bip39::{Mnemonic, Passphrase};
 
 use crate::{
     client::{secret::SecretManager, stronghold::StrongholdAdapter, utils::Password},
@@ -61,6 +61,20 @@ impl Wallet {
         }
     }
 
+    /// Stores a mnemonic into the Stronghold vault, deriving the seed with a BIP-39 passphrase
+    pub async fn store_mnemonic_with_passphrase(
+        &self,
+        mnemonic: Mnemonic,
+        passphrase: Passphrase,
+    ) -> crate::wallet::Result<()> {
+        if let SecretManager::Stronghold(stronghold) = &mut *self.secret_manager.write().await {
+            stronghold.store_mnemonic_with_passphrase(mnemonic, passphrase).await?;
+            Ok(())
+        } else {
+            Err(crate::client::Error::SecretManagerMismatch.into())
+        }
+    }
+
     /// Clears the Stronghold password from memory.
     pub async fn clear_stronghold_password(&self) -> crate::wallet::Result<()> {
         log::debug!("[clear_stronghold_password]");
@@ -112,6 +126,20 @@ impl Wallet<StrongholdAdapter> {
         Ok(self.secret_manager.write().await.store_mnemonic(mnemonic).await?)
     }
 
+    /// Stores a mnemonic into the Stronghold vault, deriving the seed with a BIP-39 passphrase
+    pub async fn store_mnemonic_with_passphrase(
+        &self,
+        mnemonic: Mnemonic,
+        passphrase: Passphrase,
+    ) -> crate::wallet::Result<()> {
+        Ok(self
+            .secret_manager
+            .write()
+            .await
+            .store_mnemonic_with_passphrase(mnemonic, passphrase)
+            .await?)
+    }
+
     /// Clears the Stronghold password from memory.
     pub async fn clear_stronghold_password(&self) -> crate::wallet::Result<()> {
         log::debug!("[clear_stronghold_password]");
@@ -0,0 +1,124 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::Ordering,
+};
+
+use crypto::keys::bip39::{Mnemonic, Passphrase};
+use zeroize::Zeroizing;
+
+use crate::{
+    client::{
+        secret::{mnemonic::MnemonicSecretManager, SecretManage},
+        slip39::ShareGroup,
+        storage::StorageAdapter,
+        stronghold::StrongholdAdapter,
+        utils::{split_mnemonic, Password},
+    },
+    wallet::Wallet,
+};
+
+const MNEMONIC_SHARE_KEY: &str = "mnemonic_share";
+
+impl<S: 'static + SecretManage> Wallet<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Splits the mnemonic of the wallet into SLIP-39 shares and writes each of them to its own Stronghold file,
+    /// encrypted with its password from `passwords`, by group and member.
+    /// The files are named `share_{group}_{member}.stronghold` in `directory`, with indexes starting at 1, and the
+    /// mnemonic is recovered from `group_threshold` groups with [`Wallet::read_mnemonic_share()`] and
+    /// [`combine_mnemonic_shares()`](crate::client::utils::combine_mnemonic_shares).
+    /// Returns an error if the first address of the mnemonic and passphrase doesn't match the one of the wallet, or if
+    /// `directory` isn't empty. The passphrase isn't part of the shares.
+    pub async fn backup_mnemonic_shares(
+        &self,
+        mnemonic: Mnemonic,
+        passphrase: impl Into<Option<Passphrase>> + Send,
+        group_threshold: u8,
+        groups: &[ShareGroup],
+        directory: impl AsRef<Path> + Send,
+        passwords: Vec<Vec<Password>>,
+    ) -> crate::wallet::Result<Vec<Vec<PathBuf>>> {
+        log::debug!("[backup_mnemonic_shares]");
+        if passwords.len() != groups.len()
+            || passwords
+                .iter()
+                .zip(groups)
+                .any(|(passwords, group)| passwords.len() != group.member_count as usize)
+        {
+            return Err(crate::wallet::Error::Backup("a password is required for every share"));
+        }
+        // Shares of an earlier split would be mixed up with the new ones.
+        if directory.as_ref().exists() && std::fs::read_dir(&directory)?.next().is_some() {
+            return Err(crate::wallet::Error::Backup("the mnemonic share directory isn't empty"));
+        }
+
+        let coin_type = self.coin_type.load(Ordering::Relaxed);
+        let wallet_address = self
+            .secret_manager
+            .read()
+            .await
+            .generate_ed25519_addresses(coin_type, 0, 0..1, None)
+            .await?;
+        let mnemonic_secret_manager = match passphrase.into() {
+            Some(passphrase) => MnemonicSecretManager::try_from_mnemonic_with_passphrase(mnemonic.clone(), passphrase)?,
+            None => MnemonicSecretManager::try_from_mnemonic(mnemonic.clone())?,
+        };
+        let mnemonic_address = mnemonic_secret_manager
+            .generate_ed25519_addresses(coin_type, 0, 0..1, None)
+            .await?;
+        if wallet_address != mnemonic_address {
+            return Err(crate::wallet::Error::Backup(
+                "the mnemonic doesn't belong to the wallet",
+            ));
+        }
+
+        let shares = split_mnemonic(mnemonic, group_threshold, groups)?;
+        std::fs::create_dir_all(&directory)?;
+
+        let mut paths = Vec::with_capacity(shares.len());
+        for (group_index, (group, passwords)) in shares.iter().zip(passwords).enumerate() {
+            let mut group_paths = Vec::with_capacity(group.len());
+            for (member_index, (share, password)) in group.iter().zip(passwords).enumerate() {
+                let path =
+                    directory
+                        .as_ref()
+                        .join(format!("share_{}_{}.stronghold", group_index + 1, member_index + 1));
+                let stronghold = StrongholdAdapter::builder().password(password).build(&path)?;
+                stronghold.set_bytes(MNEMONIC_SHARE_KEY, share.as_bytes()).await?;
+                stronghold.write_stronghold_snapshot(None).await?;
+                group_paths.push(path);
+            }
+            paths.push(group_paths);
+        }
+
+        Ok(paths)
+    }
+}
+
+impl Wallet {
+    /// Reads a SLIP-39 mnemonic share from a Stronghold file written by [`Wallet::backup_mnemonic_shares()`].
+    pub async fn read_mnemonic_share(
+        path: impl AsRef<Path> + Send,
+        password: impl Into<Password> + Send,
+    ) -> crate::wallet::Result<Mnemonic> {
+        log::debug!("[read_mnemonic_share]");
+        if !path.as_ref().is_file() {
+            return Err(crate::wallet::Error::Backup("mnemonic share path doesn't exist"));
+        }
+
+        let stronghold = StrongholdAdapter::builder().password(password).build(path.as_ref())?;
+        let share = stronghold
+            .get_bytes(MNEMONIC_SHARE_KEY)
+            .await?
+            .map(Zeroizing::new)
+            .ok_or(crate::wallet::Error::Backup("no mnemonic share in the Stronghold file"))?;
+
+        Ok(Mnemonic::from(
+            std::str::from_utf8(&share).map_err(|_| crate::wallet::Error::Backup("invalid mnemonic share"))?,
+        ))
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod mnemonic_shares;
 pub(crate) mod stronghold_snapshot;
 
 use std::{fs, path::PathBuf, sync::atomic::Ordering};
@@ -64,6 +65,8 @@ impl Wallet {
     /// Replaces client_options, coin_type, secret_manager and accounts. Returns an error if accounts were already
     /// created If Stronghold is used as secret_manager, the existing Stronghold file will be overwritten. If a
     /// mnemonic was stored, it will be gone.
+    /// The backup holds the seed, which is already derived with the BIP-39 passphrase of the mnemonic, if any, so
+    /// wallets using a passphrase are restored without it.
     /// if ignore_if_coin_type_mismatch.is_some(), client options will not be restored
     /// if ignore_if_coin_type_mismatch == Some(true), client options coin type and accounts will not be restored if the
     /// coin type doesn't match
@@ -249,6 +252,8 @@ impl Wallet<StrongholdSecretManager> {
     /// Replaces client_options, coin_type, secret_manager and accounts. Returns an error if accounts were already
     /// created If Stronghold is used as secret_manager, the existing Stronghold file will be overwritten. If a
     /// mnemonic was stored, it will be gone.
+    /// The backup holds the seed, which is already derived with the BIP-39 passphrase of the mnemonic, if any, so
+    /// wallets using a passphrase are restored without it.
     /// if ignore_if_coin_type_mismatch.is_some(), client options will not be restored
     /// if ignore_if_coin_type_mismatch == Some(true), client options coin type and accounts will not be restored if the
     /// coin type doesn't match
@@ -61,6 +61,9 @@ pub enum Error {
     /// Invalid output kind.
     #[error("invalid output kind: {0}")]
     InvalidOutputKind(String),
+    /// Invalid payment schedule
+    #[error("invalid payment schedule: {0}")]
+    InvalidPaymentSchedule(&'static str),
     /// IO error. (storage, backup, restore)
     #[error("`{0}`")]
     Io(#[from] std::io::Error),
@@ -109,6 +112,9 @@ pub enum Error {
     /// Transaction not found
     #[error("transaction {0} not found")]
     TransactionNotFound(TransactionId),
+    /// Transaction not pending
+    #[error("transaction {0} is not pending")]
+    TransactionNotPending(TransactionId),
     // TODO more precise error
     /// Voting error
     #[cfg(feature = "participation")]
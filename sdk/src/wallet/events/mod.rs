@@ -44,6 +44,8 @@ impl EventEmitter {
                 WalletEventType::ConsolidationRequired,
                 #[cfg(feature = "ledger_nano")]
                 WalletEventType::LedgerAddressGeneration,
+                WalletEventType::PaymentStatus,
+                WalletEventType::ScheduledPaymentExecuted,
             ] {
                 self.handlers.entry(event_type).or_default().push(handler.clone());
             }
@@ -77,6 +79,8 @@ impl EventEmitter {
             WalletEvent::ConsolidationRequired => WalletEventType::ConsolidationRequired,
             #[cfg(feature = "ledger_nano")]
             WalletEvent::LedgerAddressGeneration(_) => WalletEventType::LedgerAddressGeneration,
+            WalletEvent::PaymentStatus(_) => WalletEventType::PaymentStatus,
+            WalletEvent::ScheduledPaymentExecuted(_) => WalletEventType::ScheduledPaymentExecuted,
         };
         let event = Event { account_index, event };
         if let Some(handlers) = self.handlers.get(&event_type) {
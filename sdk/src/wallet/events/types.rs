@@ -5,7 +5,7 @@ use getset::Getters;
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::{
-    client::api::PreparedTransactionDataDto,
+    client::api::{input_selection::SelectionTraceDto, PreparedTransactionDataDto},
     types::{
         api::core::response::OutputWithMetadataResponse,
         block::{
@@ -36,6 +36,8 @@ pub enum WalletEvent {
     SpentOutput(Box<SpentOutputEvent>),
     TransactionInclusion(TransactionInclusionEvent),
     TransactionProgress(TransactionProgressEvent),
+    PaymentStatus(PaymentStatusEvent),
+    ScheduledPaymentExecuted(ScheduledPaymentExecutedEvent),
 }
 
 impl Serialize for WalletEvent {
@@ -58,6 +60,8 @@ impl Serialize for WalletEvent {
             T3(&'a SpentOutputEvent),
             T4(&'a TransactionInclusionEvent),
             T5(TransactionProgressEvent_<'a>),
+            T6(&'a PaymentStatusEvent),
+            T7(&'a ScheduledPaymentExecutedEvent),
         }
         #[derive(Serialize)]
         struct TypedWalletEvent_<'a> {
@@ -92,6 +96,14 @@ impl Serialize for WalletEvent {
                 kind: WalletEventType::TransactionProgress as u8,
                 event: WalletEvent_::T5(TransactionProgressEvent_ { progress: e }),
             },
+            Self::PaymentStatus(e) => TypedWalletEvent_ {
+                kind: WalletEventType::PaymentStatus as u8,
+                event: WalletEvent_::T6(e),
+            },
+            Self::ScheduledPaymentExecuted(e) => TypedWalletEvent_ {
+                kind: WalletEventType::ScheduledPaymentExecuted as u8,
+                event: WalletEvent_::T7(e),
+            },
         };
         event.serialize(serializer)
     }
@@ -143,6 +155,15 @@ impl<'de> Deserialize<'de> for WalletEvent {
                         })?
                         .progress,
                 ),
+                WalletEventType::PaymentStatus => Self::PaymentStatus(
+                    PaymentStatusEvent::deserialize(value)
+                        .map_err(|e| serde::de::Error::custom(format!("cannot deserialize PaymentStatus: {e}")))?,
+                ),
+                WalletEventType::ScheduledPaymentExecuted => {
+                    Self::ScheduledPaymentExecuted(ScheduledPaymentExecutedEvent::deserialize(value).map_err(|e| {
+                        serde::de::Error::custom(format!("cannot deserialize ScheduledPaymentExecuted: {e}"))
+                    })?)
+                }
             },
         )
     }
@@ -160,6 +181,8 @@ pub enum WalletEventType {
     SpentOutput = 3,
     TransactionInclusion = 4,
     TransactionProgress = 5,
+    PaymentStatus = 6,
+    ScheduledPaymentExecuted = 7,
 }
 
 impl TryFrom<u8> for WalletEventType {
@@ -174,6 +197,8 @@ impl TryFrom<u8> for WalletEventType {
             3 => Self::SpentOutput,
             4 => Self::TransactionInclusion,
             5 => Self::TransactionProgress,
+            6 => Self::PaymentStatus,
+            7 => Self::ScheduledPaymentExecuted,
             _ => return Err(format!("invalid event type {value}")),
         };
         Ok(event_type)
@@ -206,6 +231,39 @@ pub struct TransactionInclusionEvent {
     pub inclusion_state: InclusionState,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentStatusEvent {
+    /// The id of the queued payment.
+    pub payment_id: u64,
+    /// The new status of the payment.
+    pub status: PaymentStatus,
+}
+
+/// The status of a payment of the payment queue.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PaymentStatus {
+    /// The payment was added to the queue.
+    Queued,
+    /// The payment was sent in a transaction.
+    #[serde(rename_all = "camelCase")]
+    Sent { transaction_id: TransactionId },
+    /// Sending the payment failed, it stays queued until it's sent or removed.
+    Failed { error: String },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledPaymentExecutedEvent {
+    /// The id of the scheduled payment.
+    pub payment_id: u64,
+    /// The index of the instalment that was sent, starting at 0.
+    pub instalment: u32,
+    /// The transaction that sent the instalment.
+    pub transaction_id: TransactionId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum TransactionProgressEvent {
@@ -223,6 +281,8 @@ pub enum TransactionProgressEvent {
     PerformingPow,
     /// Broadcasting.
     Broadcasting,
+    /// Input selection failed, with a trace explaining the evaluated requirements and the rejected inputs.
+    SelectingInputsFailed(Box<SelectionTraceDto>),
 }
 
 impl Serialize for TransactionProgressEvent {
@@ -245,6 +305,7 @@ impl Serialize for TransactionProgressEvent {
             T4,
             T5,
             T6,
+            T7(&'a SelectionTraceDto),
         }
         #[derive(Serialize)]
         struct TypedTransactionProgressEvent_<'a> {
@@ -282,6 +343,10 @@ impl Serialize for TransactionProgressEvent {
                 kind: 6,
                 event: TransactionProgressEvent_::T6,
             },
+            Self::SelectingInputsFailed(e) => TypedTransactionProgressEvent_ {
+                kind: 7,
+                event: TransactionProgressEvent_::T7(e),
+            },
         };
         event.serialize(serializer)
     }
@@ -319,6 +384,10 @@ impl<'de> Deserialize<'de> for TransactionProgressEvent {
                 4 => Self::SigningTransaction,
                 5 => Self::PerformingPow,
                 6 => Self::Broadcasting,
+                7 => Self::SelectingInputsFailed(Box::new(
+                    SelectionTraceDto::deserialize(value)
+                        .map_err(|e| serde::de::Error::custom(format!("cannot deserialize SelectionTraceDto: {e}")))?,
+                )),
                 _ => return Err(serde::de::Error::custom("invalid transaction progress event type")),
             },
         )
@@ -23,6 +23,9 @@ pub(crate) const ACCOUNTS_INDEXATION_KEY: &str = "iota-wallet-accounts";
 pub(crate) const ACCOUNT_INDEXATION_KEY: &str = "iota-wallet-account-";
 
 pub(crate) const ACCOUNT_SYNC_OPTIONS: &str = "sync-options";
+pub(crate) const ACCOUNT_PAYMENT_QUEUE: &str = "payment-queue";
+pub(crate) const ACCOUNT_SCHEDULED_PAYMENTS: &str = "scheduled-payments";
+pub(crate) const ACCOUNT_TRANSACTION_BATCHES: &str = "transaction-batches";
 
 pub(crate) const DATABASE_SCHEMA_VERSION: u8 = 1;
 pub(crate) const DATABASE_SCHEMA_VERSION_KEY: &str = "database-schema-version";
@@ -8,7 +8,10 @@ use crate::{
     client::storage::StorageAdapter,
     types::TryFromDto,
     wallet::{
-        account::{AccountDetails, AccountDetailsDto, SyncOptions},
+        account::{
+            operations::{payment_queue::PaymentQueue, scheduled_payments::ScheduledPayments},
+            AccountDetails, AccountDetailsDto, SyncOptions,
+        },
         migration::migrate,
         storage::{constants::*, DynStorageAdapter, Storage},
     },
@@ -112,6 +115,37 @@ impl StorageManager {
         let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_SYNC_OPTIONS}");
         self.get(&key).await
     }
+
+    pub(crate) async fn set_payment_queue(
+        &self,
+        account_index: u32,
+        payment_queue: &PaymentQueue,
+    ) -> crate::wallet::Result<()> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_PAYMENT_QUEUE}");
+        self.set(&key, payment_queue).await
+    }
+
+    pub(crate) async fn get_payment_queue(&self, account_index: u32) -> crate::wallet::Result<Option<PaymentQueue>> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_PAYMENT_QUEUE}");
+        self.get(&key).await
+    }
+
+    pub(crate) async fn set_scheduled_payments(
+        &self,
+        account_index: u32,
+        scheduled_payments: &ScheduledPayments,
+    ) -> crate::wallet::Result<()> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_SCHEDULED_PAYMENTS}");
+        self.set(&key, scheduled_payments).await
+    }
+
+    pub(crate) async fn get_scheduled_payments(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<Option<ScheduledPayments>> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_SCHEDULED_PAYMENTS}");
+        self.get(&key).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -178,6 +212,28 @@ mod tests {
         assert!(storage_manager.get_accounts().await.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn set_get_account_records() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert!(storage_manager.get_payment_queue(0).await.unwrap().is_none());
+        assert!(storage_manager.get_scheduled_payments(0).await.unwrap().is_none());
+
+        storage_manager
+            .set_payment_queue(0, &PaymentQueue::default())
+            .await
+            .unwrap();
+        storage_manager
+            .set_scheduled_payments(0, &ScheduledPayments::default())
+            .await
+            .unwrap();
+
+        // The records are kept per account.
+        assert!(storage_manager.get_payment_queue(0).await.unwrap().is_some());
+        assert!(storage_manager.get_scheduled_payments(0).await.unwrap().is_some());
+        assert!(storage_manager.get_payment_queue(1).await.unwrap().is_none());
+        assert!(storage_manager.get_scheduled_payments(1).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn save_get_wallet_data() {
         let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
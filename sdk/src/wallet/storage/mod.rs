@@ -15,6 +15,8 @@ mod options;
 #[cfg(feature = "participation")]
 #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
 mod participation;
+/// Storage functions related to transaction batches.
+mod transaction_batch;
 
 use async_trait::async_trait;
 use crypto::ciphers::chacha;
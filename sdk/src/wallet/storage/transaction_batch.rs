@@ -0,0 +1,113 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::manager::StorageManager;
+use crate::{
+    client::storage::StorageAdapter,
+    types::TryFromDto,
+    wallet::{
+        account::{TransactionBatch, TransactionBatchDto, TransactionBatchId},
+        storage::constants::{ACCOUNT_INDEXATION_KEY, ACCOUNT_TRANSACTION_BATCHES},
+    },
+};
+
+impl StorageManager {
+    pub(crate) async fn save_transaction_batch(
+        &self,
+        account_index: u32,
+        batch: &TransactionBatch,
+    ) -> crate::wallet::Result<()> {
+        log::debug!("save_transaction_batch {}", batch.id);
+
+        let mut batches = self.get_transaction_batch_dtos(account_index).await?;
+
+        batches.insert(batch.id, TransactionBatchDto::from(batch));
+
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_TRANSACTION_BATCHES}");
+        self.storage.set(&key, &batches).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove_transaction_batch(
+        &self,
+        account_index: u32,
+        id: &TransactionBatchId,
+    ) -> crate::wallet::Result<()> {
+        log::debug!("remove_transaction_batch {id}");
+
+        let mut batches = self.get_transaction_batch_dtos(account_index).await?;
+
+        if batches.remove(id).is_some() {
+            let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_TRANSACTION_BATCHES}");
+            self.storage.set(&key, &batches).await?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_transaction_batches(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<Vec<TransactionBatch>> {
+        log::debug!("get_transaction_batches");
+
+        self.get_transaction_batch_dtos(account_index)
+            .await?
+            .into_values()
+            .map(TransactionBatch::try_from_dto)
+            .collect()
+    }
+
+    async fn get_transaction_batch_dtos(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<HashMap<TransactionBatchId, TransactionBatchDto>> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_TRANSACTION_BATCHES}");
+
+        Ok(self.storage.get(&key).await?.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::block::{
+            address::Ed25519Address,
+            output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder},
+        },
+        wallet::storage::adapter::memory::Memory,
+    };
+
+    #[tokio::test]
+    async fn save_get_remove_transaction_batch() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert!(storage_manager.get_transaction_batches(0).await.unwrap().is_empty());
+
+        let output = BasicOutputBuilder::new_with_amount(1_000_000)
+            .add_unlock_condition(AddressUnlockCondition::new(Ed25519Address::new([1; 32])))
+            .finish_output(1_000_000)
+            .unwrap();
+        let mut batch = TransactionBatch::new(vec![output; 200]).unwrap();
+
+        storage_manager.save_transaction_batch(0, &batch).await.unwrap();
+        assert_eq!(
+            storage_manager.get_transaction_batches(0).await.unwrap(),
+            vec![batch.clone()]
+        );
+        assert!(storage_manager.get_transaction_batches(1).await.unwrap().is_empty());
+
+        batch.transactions[0].confirmed = true;
+        storage_manager.save_transaction_batch(0, &batch).await.unwrap();
+        assert_eq!(
+            storage_manager.get_transaction_batches(0).await.unwrap(),
+            vec![batch.clone()]
+        );
+
+        storage_manager.remove_transaction_batch(0, &batch.id).await.unwrap();
+        assert!(storage_manager.get_transaction_batches(0).await.unwrap().is_empty());
+    }
+}
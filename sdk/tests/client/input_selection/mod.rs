@@ -10,4 +10,6 @@ mod native_tokens;
 mod nft_outputs;
 mod outputs;
 mod storage_deposit_return;
+mod strategy;
 mod timelock;
+mod trace;
@@ -0,0 +1,170 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use iota_sdk::{
+    client::{
+        api::input_selection::{BuiltinSelectionStrategy, InputSelection, SelectionStrategy},
+        secret::types::InputSigningData,
+    },
+    types::block::{output::Output, protocol::protocol_parameters},
+};
+
+use crate::client::{
+    addresses, build_inputs, build_outputs,
+    Build::{self, Basic},
+    BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1, BECH32_ADDRESS_ED25519_2,
+};
+
+fn basic(amount: u64, address: &'static str) -> Build<'static> {
+    Basic(amount, address, None, None, None, None, None, None)
+}
+
+fn select(inputs: &[InputSigningData], amount: u64, strategy: BuiltinSelectionStrategy) -> (Vec<u64>, Vec<Output>) {
+    let outputs = build_outputs([basic(amount, BECH32_ADDRESS_ED25519_2)]);
+
+    let selected = InputSelection::new(
+        inputs.to_vec(),
+        outputs,
+        addresses([BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1]),
+        protocol_parameters(),
+    )
+    .strategy(Arc::new(strategy))
+    .select()
+    .unwrap();
+
+    let mut amounts = selected
+        .inputs
+        .iter()
+        .map(|input| input.output.amount())
+        .collect::<Vec<_>>();
+    amounts.sort_unstable();
+
+    (amounts, selected.outputs)
+}
+
+fn inputs() -> Vec<InputSigningData> {
+    build_inputs([
+        basic(300_000, BECH32_ADDRESS_ED25519_0),
+        basic(400_000, BECH32_ADDRESS_ED25519_0),
+        basic(600_000, BECH32_ADDRESS_ED25519_0),
+        basic(2_500_000, BECH32_ADDRESS_ED25519_0),
+        basic(5_000_000, BECH32_ADDRESS_ED25519_0),
+    ])
+}
+
+#[test]
+fn strategy_smallest_first() {
+    let (amounts, outputs) = select(&inputs(), 1_200_000, BuiltinSelectionStrategy::SmallestFirst);
+
+    assert_eq!(amounts, [300_000, 400_000, 600_000, 2_500_000]);
+    assert_eq!(outputs.len(), 2);
+}
+
+#[test]
+fn strategy_largest_first() {
+    let (amounts, outputs) = select(&inputs(), 1_200_000, BuiltinSelectionStrategy::LargestFirst);
+
+    assert_eq!(amounts, [5_000_000]);
+    assert_eq!(outputs.len(), 2);
+}
+
+#[test]
+fn strategy_fewest_inputs() {
+    let (amounts, outputs) = select(&inputs(), 1_200_000, BuiltinSelectionStrategy::FewestInputs);
+
+    assert_eq!(amounts, [2_500_000]);
+    assert_eq!(outputs.len(), 2);
+
+    // No single input is enough, highest amounts first.
+    let (amounts, _) = select(&inputs(), 6_000_000, BuiltinSelectionStrategy::FewestInputs);
+
+    assert_eq!(amounts, [2_500_000, 5_000_000]);
+}
+
+#[test]
+fn strategy_branch_and_bound() {
+    let (amounts, outputs) = select(&inputs(), 1_300_000, BuiltinSelectionStrategy::BranchAndBound);
+
+    // Exact match, no remainder.
+    assert_eq!(amounts, [300_000, 400_000, 600_000]);
+    assert_eq!(outputs.len(), 1);
+
+    // No exact match, falls back to largest first.
+    let (amounts, outputs) = select(&inputs(), 1_250_000, BuiltinSelectionStrategy::BranchAndBound);
+
+    assert_eq!(amounts, [5_000_000]);
+    assert_eq!(outputs.len(), 2);
+}
+
+#[test]
+fn strategy_privacy() {
+    let inputs = build_inputs([
+        basic(1_000_000, BECH32_ADDRESS_ED25519_0),
+        basic(1_000_000, BECH32_ADDRESS_ED25519_0),
+        basic(3_000_000, BECH32_ADDRESS_ED25519_1),
+    ]);
+
+    // Both addresses could cover the amount, the smallest one is used alone.
+    let (amounts, _) = select(&inputs, 1_500_000, BuiltinSelectionStrategy::Privacy);
+
+    assert_eq!(amounts, [1_000_000, 1_000_000]);
+
+    // Only the second address can cover the amount, the first one isn't linked to it.
+    let (amounts, _) = select(&inputs, 2_500_000, BuiltinSelectionStrategy::Privacy);
+
+    assert_eq!(amounts, [3_000_000]);
+
+    // A single address isn't enough, the largest is used first.
+    let (amounts, _) = select(&inputs, 3_500_000, BuiltinSelectionStrategy::Privacy);
+
+    assert_eq!(amounts, [1_000_000, 3_000_000]);
+}
+
+#[test]
+fn strategy_custom() {
+    // Prefers inputs of the second address.
+    #[derive(Debug)]
+    struct PreferAddress;
+
+    impl SelectionStrategy for PreferAddress {
+        fn order(&self, inputs: &mut [InputSigningData], _amount: u64, _timestamp: u32) {
+            let address = addresses([BECH32_ADDRESS_ED25519_1])[0];
+
+            inputs.sort_by_key(|input| {
+                input.output.unlock_conditions().unwrap().address().unwrap().address() != &address
+            });
+        }
+    }
+
+    let inputs = build_inputs([
+        basic(1_000_000, BECH32_ADDRESS_ED25519_0),
+        basic(2_000_000, BECH32_ADDRESS_ED25519_1),
+    ]);
+    let outputs = build_outputs([basic(1_000_000, BECH32_ADDRESS_ED25519_2)]);
+
+    let selected = InputSelection::new(
+        inputs.clone(),
+        outputs,
+        addresses([BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1]),
+        protocol_parameters(),
+    )
+    .strategy(Arc::new(PreferAddress))
+    .select()
+    .unwrap();
+
+    assert_eq!(selected.inputs, [inputs[1].clone()]);
+}
+
+#[test]
+fn strategy_serde() {
+    assert_eq!(
+        serde_json::to_string(&BuiltinSelectionStrategy::BranchAndBound).unwrap(),
+        "\"branchAndBound\""
+    );
+    assert_eq!(
+        serde_json::from_str::<BuiltinSelectionStrategy>("\"fewestInputs\"").unwrap(),
+        BuiltinSelectionStrategy::FewestInputs
+    );
+}
@@ -0,0 +1,161 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use iota_sdk::{
+    client::api::input_selection::{Error, InputSelection, RejectionReason, Requirement, SelectionTraceDto},
+    types::block::protocol::protocol_parameters,
+};
+
+use crate::client::{
+    addresses, build_inputs, build_outputs, Build::Basic, BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1,
+};
+
+#[test]
+fn trace_insufficient_amount() {
+    let protocol_parameters = protocol_parameters();
+    let addresses_1 = addresses([BECH32_ADDRESS_ED25519_1]);
+
+    let inputs = build_inputs([
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+        Basic(
+            2_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            Some(200),
+            None,
+            None,
+        ),
+        Basic(
+            2_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            Some((BECH32_ADDRESS_ED25519_1, 50)),
+            None,
+        ),
+        Basic(2_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+        Basic(
+            500_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            Some((BECH32_ADDRESS_ED25519_1, 500_000)),
+            None,
+            None,
+            None,
+        ),
+    ]);
+    let outputs = build_outputs([Basic(
+        3_000_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let (selected, trace) = InputSelection::new(
+        inputs.clone(),
+        outputs,
+        addresses([BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .forbidden_inputs(HashSet::from([*inputs[3].output_id()]))
+    .timestamp(100)
+    .select_with_trace();
+
+    assert!(matches!(selected, Err(Error::InsufficientAmount { .. })));
+
+    assert_eq!(trace.rejection(inputs[0].output_id()), None);
+    assert_eq!(
+        trace.rejection(inputs[1].output_id()),
+        Some(&RejectionReason::Timelocked { until: 200 })
+    );
+    assert_eq!(
+        trace.rejection(inputs[2].output_id()),
+        Some(&RejectionReason::NotOwned {
+            required_address: addresses_1[0]
+        })
+    );
+    assert_eq!(
+        trace.rejection(inputs[3].output_id()),
+        Some(&RejectionReason::Forbidden)
+    );
+    assert_eq!(
+        trace.rejection(inputs[4].output_id()),
+        Some(&RejectionReason::StorageDepositReturn {
+            return_address: addresses_1[0],
+            amount: 500_000
+        })
+    );
+
+    let amount = trace
+        .requirements
+        .iter()
+        .find(|requirement| requirement.requirement == Requirement::Amount)
+        .unwrap();
+    assert!(!amount.fulfilled);
+    assert!(trace.requirements.last().unwrap() == amount);
+
+    let json = serde_json::to_value(SelectionTraceDto::from(&trace)).unwrap();
+    assert_eq!(json["rejectedInputs"].as_array().unwrap().len(), 4);
+    assert_eq!(
+        json["requirements"].as_array().unwrap().last().unwrap()["requirement"]["type"],
+        "amount"
+    );
+    assert_eq!(
+        serde_json::from_value::<SelectionTraceDto>(json).unwrap(),
+        SelectionTraceDto::from(&trace)
+    );
+}
+
+#[test]
+fn trace_success() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs([
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+        Basic(1_000_000, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None),
+    ]);
+    let outputs = build_outputs([Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_1,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let (selected, trace) = InputSelection::new(
+        inputs.clone(),
+        outputs,
+        addresses([BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters,
+    )
+    .select_with_trace();
+
+    assert_eq!(selected.unwrap().inputs.len(), 2);
+    assert!(trace.rejected_inputs.is_empty());
+    assert!(trace.requirements.iter().all(|requirement| requirement.fulfilled));
+
+    let amount = trace
+        .requirements
+        .iter()
+        .find(|requirement| requirement.requirement == Requirement::Amount)
+        .unwrap();
+    let mut selected_inputs = amount.selected_inputs.clone();
+    selected_inputs.sort();
+    let mut input_ids = inputs.iter().map(|input| *input.output_id()).collect::<Vec<_>>();
+    input_ids.sort();
+    assert_eq!(selected_inputs, input_ids);
+}
@@ -0,0 +1,275 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::signatures::ed25519;
+use iota_sdk::{
+    client::{
+        light_client::{CoordinatorConfig, MilestonePublicKeyRange, MilestoneVerificationError, MilestoneVerifier},
+        Error,
+    },
+    types::block::{
+        parent::Parents,
+        payload::{
+            milestone::{MerkleRoot, MilestoneEssence, MilestoneId, MilestoneIndex, MilestoneOptions},
+            MilestonePayload,
+        },
+        rand::block::rand_block_id,
+        signature::{Ed25519Signature, Signature},
+    },
+};
+
+fn key_range(key: &ed25519::SecretKey, start: u32, end: u32) -> MilestonePublicKeyRange {
+    MilestonePublicKeyRange {
+        key: hex::encode(key.public_key().to_bytes()),
+        start,
+        end,
+    }
+}
+
+fn milestone(index: u32, previous_milestone_id: MilestoneId, keys: &[&ed25519::SecretKey]) -> MilestonePayload {
+    let essence = MilestoneEssence::new(
+        MilestoneIndex::new(index),
+        index,
+        2,
+        previous_milestone_id,
+        Parents::from_vec(vec![rand_block_id()]).unwrap(),
+        MerkleRoot::null(),
+        MerkleRoot::null(),
+        Vec::new(),
+        MilestoneOptions::from_vec(Vec::new()).unwrap(),
+    )
+    .unwrap();
+    let mut signatures = keys
+        .iter()
+        .map(|key| Signature::from(Ed25519Signature::new(key.public_key(), key.sign(&essence.hash()))))
+        .collect::<Vec<_>>();
+    signatures.sort_by_key(|Signature::Ed25519(signature)| signature.public_key().to_bytes());
+
+    MilestonePayload::new(essence, signatures).unwrap()
+}
+
+#[test]
+fn milestone_signature_threshold() {
+    let key_1 = ed25519::SecretKey::generate().unwrap();
+    let key_2 = ed25519::SecretKey::generate().unwrap();
+    let key_3 = ed25519::SecretKey::generate().unwrap();
+    let verifier = MilestoneVerifier::new(CoordinatorConfig {
+        milestone_public_key_count: 2,
+        public_key_ranges: vec![
+            key_range(&key_1, 0, 0),
+            key_range(&key_2, 0, 10),
+            key_range(&key_3, 11, 0),
+        ],
+    });
+
+    let ms_10 = milestone(10, MilestoneId::null(), &[&key_1, &key_2]);
+    verifier.verify(&ms_10).unwrap();
+    assert_eq!(verifier.verified_milestone_id(10), Some(ms_10.id()));
+
+    // Not enough signatures.
+    assert!(matches!(
+        verifier.verify(&milestone(11, ms_10.id(), &[&key_1])),
+        Err(MilestoneVerificationError::InvalidSignatures { index: 11, .. })
+    ));
+    // The range of the second key ended.
+    assert!(matches!(
+        verifier.verify(&milestone(11, ms_10.id(), &[&key_1, &key_2])),
+        Err(MilestoneVerificationError::InvalidSignatures { index: 11, .. })
+    ));
+    verifier.verify(&milestone(11, ms_10.id(), &[&key_1, &key_3])).unwrap();
+}
+
+#[test]
+fn milestone_chain_linkage() {
+    let key = ed25519::SecretKey::generate().unwrap();
+    let verifier = MilestoneVerifier::new(CoordinatorConfig {
+        milestone_public_key_count: 1,
+        public_key_ranges: vec![key_range(&key, 0, 0)],
+    });
+
+    let ms_1 = milestone(1, MilestoneId::null(), &[&key]);
+    let ms_2 = milestone(2, ms_1.id(), &[&key]);
+    let ms_3 = milestone(3, ms_2.id(), &[&key]);
+
+    verifier.verify(&ms_1).unwrap();
+    verifier.verify(&ms_3).unwrap();
+    // Doesn't link to the verified milestone 1.
+    assert!(matches!(
+        verifier.verify(&milestone(2, MilestoneId::null(), &[&key])),
+        Err(MilestoneVerificationError::BrokenChain { index: 2, .. })
+    ));
+    // Milestone 3 doesn't link to it.
+    assert!(matches!(
+        verifier.verify(&milestone(2, ms_1.id(), &[&key])),
+        Err(MilestoneVerificationError::BrokenChain { index: 3, .. })
+    ));
+    verifier.verify(&ms_2).unwrap();
+    assert!(matches!(
+        verifier.verify(&milestone(1, MilestoneId::null(), &[&key])),
+        Err(MilestoneVerificationError::Conflicting { index: 1, .. })
+    ));
+}
+
+#[cfg(feature = "mock_node")]
+#[tokio::test]
+async fn client_milestone_verification() -> iota_sdk::client::Result<()> {
+    use iota_sdk::{client::mock_node::MockNode, types::block::address::Ed25519Address};
+
+    let node = MockNode::builder().finish().await?;
+    let config = |key| CoordinatorConfig {
+        milestone_public_key_count: 1,
+        public_key_ranges: vec![MilestonePublicKeyRange { key, start: 0, end: 0 }],
+    };
+    let output_id = node.fund(Ed25519Address::new([1; 32]), 1_000_000).await?;
+    node.issue_milestone().await;
+
+    let client = iota_sdk::client::Client::builder()
+        .with_node(node.url())?
+        .with_milestone_verification(config(node.milestone_public_key().await))
+        .finish()
+        .await?;
+    client.get_milestone_by_index(1).await?;
+    client.get_output(&output_id).await?;
+
+    let untrusting_client = iota_sdk::client::Client::builder()
+        .with_node(node.url())?
+        .with_milestone_verification(config(hex::encode([0; 32])))
+        .finish()
+        .await?;
+    assert!(matches!(
+        untrusting_client.get_milestone_by_index(1).await,
+        Err(Error::MilestoneVerification(
+            MilestoneVerificationError::InvalidSignatures { index: 1, .. }
+        ))
+    ));
+    assert!(matches!(
+        untrusting_client.get_output(&output_id).await,
+        Err(Error::MilestoneVerification(_))
+    ));
+
+    Ok(())
+}
+
+// Serves the responses of a node, passing the JSON ones through `forge` with their path first.
+#[cfg(feature = "mock_node")]
+async fn forging_proxy(node_url: &str, forge: impl Fn(&str, &mut serde_json::Value) + Send + Sync + 'static) -> String {
+    use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+    use hyper::{
+        header::HOST,
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    };
+
+    let node_url = node_url.to_string();
+    let forge = Arc::new(forge);
+    let make_service = make_service_fn(move |_| {
+        let (node_url, forge) = (node_url.clone(), forge.clone());
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let (node_url, forge) = (node_url.clone(), forge.clone());
+                async move {
+                    let path = request.uri().path().to_string();
+                    let path_and_query = request.uri().path_and_query().unwrap().to_string();
+                    let method = request.method().clone();
+                    let mut headers = request.headers().clone();
+                    headers.remove(HOST);
+                    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                    let response = reqwest::Client::new()
+                        .request(method, format!("{node_url}{path_and_query}"))
+                        .headers(headers)
+                        .body(body)
+                        .send()
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let mut body = response.bytes().await.unwrap().to_vec();
+                    if let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&body) {
+                        forge(&path, &mut value);
+                        body = serde_json::to_vec(&value).unwrap();
+                    }
+
+                    Ok::<_, Infallible>(Response::builder().status(status).body(Body::from(body)).unwrap())
+                }
+            }))
+        }
+    });
+    let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+    let url = format!("http://{}", server.local_addr());
+    tokio::spawn(server);
+
+    url
+}
+
+#[cfg(feature = "mock_node")]
+#[tokio::test]
+async fn client_forged_confirmation() -> iota_sdk::client::Result<()> {
+    use iota_sdk::{
+        client::{mock_node::MockNode, Client},
+        types::block::address::Ed25519Address,
+    };
+
+    let node = MockNode::builder().with_auto_confirm(false).finish().await?;
+    let output_id = node.fund(Ed25519Address::new([1; 32]), 1_000_000).await?;
+    node.issue_milestone().await;
+    let client = Client::builder()
+        .with_node(node.url())?
+        .with_local_pow(false)
+        .finish()
+        .await?;
+    let block_id = client.build_block().with_tag(vec![1]).finish().await?.id();
+    let referenced = node.issue_milestone().await;
+    node.issue_milestone().await;
+    let config = CoordinatorConfig {
+        milestone_public_key_count: 1,
+        public_key_ranges: vec![MilestonePublicKeyRange {
+            key: node.milestone_public_key().await,
+            start: 0,
+            end: 0,
+        }],
+    };
+
+    let verifying_client = Client::builder()
+        .with_node(node.url())?
+        .with_milestone_verification(config.clone())
+        .finish()
+        .await?;
+    assert_eq!(
+        verifying_client
+            .get_block_metadata(&block_id)
+            .await?
+            .referenced_by_milestone_index,
+        Some(referenced)
+    );
+    verifying_client.get_output_metadata(&output_id).await?;
+
+    // The node claims the block and the output were confirmed by the next milestone, which is genuine.
+    let proxy_url = forging_proxy(node.url(), move |path, value| {
+        if path.ends_with("/metadata") {
+            if let Some(index) = value.get_mut("referencedByMilestoneIndex") {
+                *index = serde_json::json!(referenced + 1);
+            }
+            if let Some(index) = value.get_mut("milestoneIndexBooked") {
+                *index = serde_json::json!(referenced + 1);
+            }
+        }
+    })
+    .await;
+    let forged_client = Client::builder()
+        .with_node(&proxy_url)?
+        .with_milestone_verification(config)
+        .finish()
+        .await?;
+    assert!(matches!(
+        forged_client.get_block_metadata(&block_id).await,
+        Err(Error::MilestoneVerification(MilestoneVerificationError::UnconfirmedBlock { index, .. }))
+            if index == referenced + 1
+    ));
+    assert!(matches!(
+        forged_client.get_output_metadata(&output_id).await,
+        Err(Error::MilestoneVerification(MilestoneVerificationError::UnconfirmedOutput { index, .. }))
+            if index == referenced + 1
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::TryStreamExt;
+use iota_sdk::{
+    client::{
+        api::GetAddressesOptions,
+        mock_node::{MockNode, DEFAULT_FAUCET_AMOUNT},
+        node_api::indexer::query_parameters::QueryParameter,
+        request_funds_from_faucet,
+        secret::SecretManager,
+        Client, Result,
+    },
+    types::{
+        api::core::response::LedgerInclusionState,
+        block::{
+            address::Bech32Address,
+            payload::{
+                milestone::{dto::InclusionProofDto, InclusionProof},
+                Payload,
+            },
+        },
+        TryFromDto,
+    },
+};
+
+// THIS SEED SERVES FOR TESTING PURPOSES! DON'T USE THIS SEED IN PRODUCTION!
+const DEFAULT_DEVELOPMENT_SEED: &str = "0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2";
+
+async fn setup() -> Result<(MockNode, Client, SecretManager, Vec<Bech32Address>)> {
+    let node = MockNode::builder().finish().await?;
+    let client = Client::builder()
+        .with_node(node.url())?
+        .with_local_pow(false)
+        .finish()
+        .await?;
+    let secret_manager = SecretManager::try_from_hex_seed(DEFAULT_DEVELOPMENT_SEED.to_owned())?;
+    let addresses = secret_manager
+        .generate_ed25519_addresses(GetAddressesOptions::from_client(&client).await?.with_range(0..2))
+        .await?;
+
+    Ok((node, client, secret_manager, addresses))
+}
+
+#[tokio::test]
+async fn mock_node_info() -> Result<()> {
+    let (node, client, _, _) = setup().await?;
+
+    assert!(client.get_health(node.url()).await?);
+
+    let info = client.get_info().await?.node_info;
+    assert_eq!(info.protocol, node.protocol_parameters().await);
+    assert_eq!(info.status.latest_milestone.index, 1);
+
+    let milestone = client.get_milestone_by_index(1).await?;
+    milestone.validate(&[node.milestone_public_key().await], 1).unwrap();
+
+    assert_eq!(node.issue_milestone().await, 2);
+    assert_eq!(client.get_info().await?.node_info.status.latest_milestone.index, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_faucet_and_indexer() -> Result<()> {
+    let (node, client, _, addresses) = setup().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), &addresses[0]).await?;
+    let funded_output_id = node.fund(addresses[0], 1_000_000).await?;
+
+    let output_ids = client
+        .basic_output_ids([
+            QueryParameter::Address(addresses[0]),
+            QueryParameter::HasExpiration(false),
+            QueryParameter::HasTimelock(false),
+            QueryParameter::HasStorageDepositReturn(false),
+        ])
+        .await?;
+    assert_eq!(output_ids.items.len(), 2);
+    assert!(output_ids.items.contains(&funded_output_id));
+
+    // Follows the cursor over pages of a single output.
+    let paginated = client
+        .basic_output_ids([QueryParameter::Address(addresses[0]), QueryParameter::PageSize(1)])
+        .await?;
+    assert_eq!(paginated.items, output_ids.items);
+
+    let outputs = client.get_outputs(&output_ids.items).await?;
+    let amounts = outputs.iter().map(|o| o.output().amount()).sum::<u64>();
+    assert_eq!(amounts, DEFAULT_FAUCET_AMOUNT + 1_000_000);
+
+    assert!(client.alias_output_ids([]).await?.items.is_empty());
+    assert!(client
+        .basic_output_ids([QueryParameter::Address(addresses[1])])
+        .await?
+        .items
+        .is_empty());
+    assert!(client
+        .nft_output_ids([QueryParameter::Governor(addresses[0])])
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_output_ids_stream() -> Result<()> {
+    let (node, client, _, addresses) = setup().await?;
+
+    let mut funded_output_ids = Vec::new();
+    for amount in 1..=5 {
+        funded_output_ids.push(node.fund(addresses[0], amount * 1_000_000).await?);
+    }
+    let query_parameters = [QueryParameter::Address(addresses[0]), QueryParameter::PageSize(2)];
+
+    let pages = client
+        .basic_output_ids_stream(query_parameters.clone())?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(pages.iter().map(|page| page.items.len()).collect::<Vec<_>>(), [2, 2, 1]);
+    let mut output_ids = pages.into_iter().flat_map(|page| page.items).collect::<Vec<_>>();
+    output_ids.sort();
+    funded_output_ids.sort();
+    assert_eq!(output_ids, funded_output_ids);
+
+    // Pages can be resolved to their outputs and the stream stopped early.
+    let first_page = Box::pin(client.get_outputs_stream(client.basic_output_ids_stream(query_parameters)?, 2))
+        .try_next()
+        .await?
+        .unwrap();
+    assert_eq!(first_page.len(), 2);
+    assert!(first_page
+        .iter()
+        .all(|output| output_ids.contains(output.metadata().output_id())));
+
+    assert!(client
+        .nft_output_ids_stream([QueryParameter::Governor(addresses[0])])
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_transaction() -> Result<()> {
+    let (node, client, secret_manager, addresses) = setup().await?;
+
+    node.fund(addresses[0], 10_000_000).await?;
+
+    let block = client
+        .build_block()
+        .with_secret_manager(&secret_manager)
+        .with_output(addresses[1], 1_000_000)
+        .await?
+        .finish()
+        .await?;
+
+    let metadata = client.get_block_metadata(&block.id()).await?;
+    assert_eq!(metadata.ledger_inclusion_state, Some(LedgerInclusionState::Included));
+    assert_eq!(client.get_block(&block.id()).await?, block);
+
+    let Some(Payload::Transaction(transaction)) = block.payload() else {
+        panic!("expected a transaction payload");
+    };
+    assert_eq!(client.get_included_block(&transaction.id()).await?, block);
+
+    let output_ids = client.basic_output_ids([QueryParameter::Address(addresses[1])]).await?;
+    assert_eq!(output_ids.items.len(), 1);
+    assert_eq!(
+        client.get_output(&output_ids.items[0]).await?.output().amount(),
+        1_000_000
+    );
+
+    // Replaying the same transaction in another block conflicts, as its inputs are spent.
+    let conflicting = client
+        .finish_block_builder(None, Some(Payload::Transaction(transaction.clone())))
+        .await?;
+    let conflicting_id = client.post_block(&conflicting).await?;
+    let metadata = client.get_block_metadata(&conflicting_id).await?;
+    assert_eq!(metadata.ledger_inclusion_state, Some(LedgerInclusionState::Conflicting));
+    assert_eq!(metadata.conflict_reason, Some(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_inclusion_proof() -> Result<()> {
+    let node = MockNode::builder().with_auto_confirm(false).finish().await?;
+    let client = Client::builder()
+        .with_node(node.url())?
+        .with_local_pow(false)
+        .finish()
+        .await?;
+
+    let mut block_ids = Vec::new();
+    for tag in 0..3u8 {
+        let block = client.build_block().with_tag(vec![tag]).finish().await?;
+        block_ids.push(block.id());
+    }
+    node.issue_milestone().await;
+
+    let proof = client.get_inclusion_proof(&block_ids[1]).await?;
+    assert_eq!(proof.block().id(), block_ids[1]);
+    assert_eq!(*proof.milestone().essence().index(), 2);
+    assert!(proof.verify());
+
+    // The proof outlives the node, as a receipt that can be verified offline.
+    let receipt = serde_json::to_string(&InclusionProofDto::from(&proof))?;
+    drop(node);
+    let proof = InclusionProof::try_from_dto_with_params(
+        serde_json::from_str::<InclusionProofDto>(&receipt)?,
+        client.get_protocol_parameters().await?,
+    )?;
+    client.verify_inclusion_proof(&proof).await?;
+
+    Ok(())
+}
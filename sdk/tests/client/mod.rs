@@ -9,13 +9,25 @@ mod error;
 mod high_level;
 mod input_selection;
 mod input_signing_data;
+mod light_client;
 mod mnemonic;
+#[cfg(feature = "mock_node")]
+mod mock_node;
 #[cfg(feature = "mqtt")]
 mod mqtt;
 mod node_api;
+#[cfg(feature = "mock_node")]
+mod node_statistics;
+#[cfg(feature = "mock_node")]
+mod response_cache;
+mod retry;
 mod secret_manager;
 mod signing;
+mod slip39;
+mod transaction_summary;
 mod transactions;
+#[cfg(feature = "mock_node")]
+mod transport;
 
 use std::{
     collections::{BTreeSet, HashMap},
@@ -0,0 +1,64 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::mqtt::{Error, FromTopicEvent, MqttPayload, TopicEvent},
+    types::{
+        api::core::response::{BlockMetadataResponse, OutputWithMetadataResponse},
+        block::{
+            output::{OutputMetadata, OutputWithMetadata},
+            protocol::ProtocolParameters,
+            rand::{
+                block::{rand_block, rand_block_id},
+                output::{rand_basic_output, rand_output_id},
+            },
+            Block,
+        },
+    },
+};
+
+fn event(topic: &str, payload: MqttPayload) -> TopicEvent {
+    TopicEvent {
+        topic: topic.to_owned(),
+        payload,
+    }
+}
+
+#[test]
+fn decode_topic_events() {
+    let protocol_parameters = ProtocolParameters::default();
+
+    let block = rand_block();
+    let decoded = Block::from_topic_event(
+        event("blocks", MqttPayload::Block((&block).into())),
+        &protocol_parameters,
+    );
+    assert_eq!(decoded.unwrap(), block);
+
+    let output_id = rand_output_id();
+    let output = OutputWithMetadata::new(
+        rand_basic_output(protocol_parameters.token_supply()).into(),
+        OutputMetadata::new(rand_block_id(), output_id, false, None, None, None, 1, 1, 1),
+    );
+    let json = serde_json::to_value(OutputWithMetadataResponse::from(&output)).unwrap();
+    let decoded = OutputWithMetadata::from_topic_event(
+        event(&format!("outputs/{output_id}"), MqttPayload::Json(json)),
+        &protocol_parameters,
+    )
+    .unwrap();
+    assert_eq!(decoded.output(), output.output());
+    assert_eq!(decoded.metadata(), output.metadata());
+
+    // A block isn't block metadata.
+    assert!(matches!(
+        BlockMetadataResponse::from_topic_event(event("blocks", MqttPayload::Block((&block).into())), &protocol_parameters),
+        Err(Error::UnexpectedPayload(topic)) if topic == "blocks"
+    ));
+    assert!(matches!(
+        BlockMetadataResponse::from_topic_event(
+            event("block-metadata/referenced", MqttPayload::Json(serde_json::json!({}))),
+            &protocol_parameters
+        ),
+        Err(Error::Json(_))
+    ));
+}
@@ -57,3 +57,24 @@ async fn test_mqtt() {
     }
     client.subscriber().disconnect().await.unwrap();
 }
+
+#[ignore]
+#[tokio::test]
+async fn test_mqtt_stream() {
+    use futures::{StreamExt, TryStreamExt};
+    use iota_sdk::{client::mqtt::Topic, types::block::Block};
+
+    let client = setup_client_with_node_health_ignored().await;
+
+    let blocks = client
+        .subscribe_stream::<Block>(Topic::new("blocks").unwrap())
+        .await
+        .unwrap()
+        .take(3)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(blocks.len(), 3);
+
+    client.subscriber().disconnect().await.unwrap();
+}
@@ -0,0 +1,73 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_sdk::client::{mock_node::MockNode, node_manager::statistics::CircuitBreakerOptions, Client, Result};
+
+#[tokio::test]
+async fn node_statistics_circuit_breaker() -> Result<()> {
+    let node = MockNode::builder().finish().await?;
+    // The discard port, which nothing listens on.
+    let unreachable_url = "http://127.0.0.1:9";
+
+    let client = Client::builder()
+        .with_nodes(&[node.url(), unreachable_url])?
+        .with_ignore_node_health()
+        .with_circuit_breaker(CircuitBreakerOptions {
+            failure_threshold: 2,
+            cool_down: Duration::from_secs(60),
+        })
+        .finish()
+        .await?;
+
+    // The first node sync already failed once on the unreachable node, so the reachable one is preferred.
+    for _ in 0..3 {
+        client.get_tips().await?;
+    }
+
+    let statistics = client.node_statistics().await;
+    let reachable = statistics.iter().find(|s| s.url == node.url()).unwrap();
+    let unreachable = statistics.iter().find(|s| s.url == unreachable_url).unwrap();
+    assert_eq!(reachable.failures, 0);
+    assert!(reachable.requests >= 3);
+    assert!(reachable.average_latency.is_some());
+    assert_eq!(unreachable.requests, 1);
+    assert_eq!(unreachable.consecutive_failures, 1);
+    assert!(!unreachable.tripped);
+
+    assert!(client.get_health(unreachable_url).await.is_err());
+
+    let statistics = client.node_statistics().await;
+    let unreachable = statistics.iter().find(|s| s.url == unreachable_url).unwrap();
+    assert_eq!(unreachable.consecutive_failures, 2);
+    assert!(unreachable.tripped);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn node_statistics_by_path() -> Result<()> {
+    let node = MockNode::builder().finish().await?;
+    // Two nodes behind the same host, e.g. a proxy.
+    let node_urls = [format!("{}/node1", node.url()), format!("{}/node2", node.url())];
+
+    let client = Client::builder()
+        .with_nodes(&[node_urls[0].as_str(), node_urls[1].as_str()])?
+        .with_ignore_node_health()
+        .finish()
+        .await?;
+
+    for _ in 0..4 {
+        client.get_tips().await?;
+    }
+
+    let statistics = client.node_statistics().await;
+    assert_eq!(statistics.len(), 2);
+    assert_eq!(
+        statistics.iter().map(|s| s.url.as_str()).collect::<Vec<_>>(),
+        node_urls.iter().map(String::as_str).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        api::GetAddressesOptions, cache::ResponseCacheStatistics, mock_node::MockNode, secret::SecretManager, Client,
+        ClientBuilder, Result,
+    },
+    types::block::{output::OutputId, payload::Payload},
+};
+
+// THIS SEED SERVES FOR TESTING PURPOSES! DON'T USE THIS SEED IN PRODUCTION!
+const DEFAULT_DEVELOPMENT_SEED: &str = "0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2";
+
+async fn client(node: &MockNode, builder: ClientBuilder) -> Result<Client> {
+    builder.with_node(node.url())?.with_local_pow(false).finish().await
+}
+
+#[tokio::test]
+async fn response_cache_eviction() -> Result<()> {
+    let node = MockNode::builder().finish().await?;
+
+    let uncached = client(&node, Client::builder()).await?;
+    uncached.get_milestone_by_index(1).await?;
+    assert_eq!(uncached.response_cache_statistics().await, None);
+
+    let client = client(&node, Client::builder().with_response_cache(1)).await?;
+    let milestone = client.get_milestone_by_index(1).await?;
+    assert_eq!(client.get_milestone_by_index(1).await?, milestone);
+
+    // Only the most recently used milestone is kept.
+    node.issue_milestone().await;
+    client.get_milestone_by_index(2).await?;
+    client.get_milestone_by_index(1).await?;
+
+    assert_eq!(
+        client.response_cache_statistics().await,
+        Some(ResponseCacheStatistics {
+            hits: 1,
+            misses: 3,
+            entries: 1,
+        })
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn response_cache_spent_outputs() -> Result<()> {
+    let node = MockNode::builder().finish().await?;
+    let client = client(&node, Client::builder().with_response_cache(100)).await?;
+    let secret_manager = SecretManager::try_from_hex_seed(DEFAULT_DEVELOPMENT_SEED.to_owned())?;
+    let addresses = secret_manager
+        .generate_ed25519_addresses(GetAddressesOptions::from_client(&client).await?.with_range(0..2))
+        .await?;
+
+    node.fund(addresses[0], 10_000_000).await?;
+    let block = client
+        .build_block()
+        .with_secret_manager(&secret_manager)
+        .with_output(addresses[1], 1_000_000)
+        .await?
+        .finish()
+        .await?;
+    let Some(Payload::Transaction(transaction)) = block.payload() else {
+        panic!("expected a transaction payload");
+    };
+
+    let inputs = client.inputs_from_transaction_id(&transaction.id()).await?;
+    assert!(inputs.iter().all(|input| input.metadata().is_spent()));
+    let before = client.response_cache_statistics().await.unwrap();
+
+    // The included block and the spent inputs are answered from the cache.
+    let cached_inputs = client.inputs_from_transaction_id(&transaction.id()).await?;
+    assert!(cached_inputs
+        .iter()
+        .map(|i| i.output())
+        .eq(inputs.iter().map(|i| i.output())));
+    let after = client.response_cache_statistics().await.unwrap();
+    assert_eq!(after.misses, before.misses);
+    assert_eq!(after.hits, before.hits + 1 + inputs.len() as u64);
+
+    // Cached outputs report the latest ledger index instead of the one they were cached at.
+    node.issue_milestone().await;
+    let ledger_index = client.get_info().await?.node_info.status.confirmed_milestone.index;
+    assert!(ledger_index > inputs[0].metadata().ledger_index());
+    let cached_input = client.get_output(inputs[0].metadata().output_id()).await?;
+    assert_eq!(cached_input.metadata().ledger_index(), ledger_index);
+
+    // Unspent outputs can still change, so they are never cached.
+    let unspent_output_id = OutputId::new(transaction.id(), 0)?;
+    client.get_output(&unspent_output_id).await?;
+    client.get_output(&unspent_output_id).await?;
+    let unspent = client.response_cache_statistics().await.unwrap();
+    assert_eq!(unspent.hits, after.hits + 1);
+    assert_eq!(unspent.misses, after.misses + 2);
+    assert_eq!(unspent.entries, after.entries);
+
+    Ok(())
+}
+
+#[cfg(all(feature = "wallet", feature = "storage"))]
+#[tokio::test]
+async fn response_cache_storage() -> Result<()> {
+    use iota_sdk::wallet::storage::adapter::memory::Memory;
+
+    let node = MockNode::builder().finish().await?;
+    let client = client(
+        &node,
+        Client::builder()
+            .with_response_cache(10)
+            .with_response_cache_storage(Memory::default()),
+    )
+    .await?;
+    let milestone = client.get_milestone_by_index(1).await?;
+
+    // A new client with the same storage starts with an empty cache in memory, but doesn't need to ask the node.
+    let restarted = ClientBuilder::from_client(&client).await.finish().await?;
+    assert_eq!(restarted.response_cache_statistics().await.unwrap().entries, 0);
+    assert_eq!(restarted.get_milestone_by_index(1).await?, milestone);
+    assert_eq!(
+        restarted.response_cache_statistics().await,
+        Some(ResponseCacheStatistics {
+            hits: 1,
+            misses: 0,
+            entries: 1,
+        })
+    );
+
+    Ok(())
+}
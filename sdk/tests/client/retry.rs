@@ -0,0 +1,100 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_sdk::client::{
+    node_api::error::Error as NodeApiError,
+    node_manager::{
+        retry::RetryPolicy,
+        transport::{HttpMethod, RecordedExchange, ReplayTransport},
+    },
+    Client, Error, Result,
+};
+
+const NODE_URL: &str = "http://localhost:14265";
+const TIPS: &str = r#"{"tips":["0xc5f2bc4ff1e8e0c0db2b0e4c8b9e0e8b1a2d1bd8ef59a2a0ee2a9e30ac5f5e7b"]}"#;
+
+fn exchange(method: HttpMethod, path: &str, status: u16, body: &str) -> RecordedExchange {
+    RecordedExchange {
+        method,
+        url: format!("{NODE_URL}/{path}").parse().unwrap(),
+        request_body: (method == HttpMethod::Post).then(|| b"null".to_vec()),
+        status,
+        response_body: body.as_bytes().to_vec(),
+    }
+}
+
+/// A node that is busy on the first request of each route.
+async fn client(retry_policy: RetryPolicy) -> Result<Client> {
+    Client::builder()
+        .with_node(NODE_URL)?
+        .with_ignore_node_health()
+        .with_transport(ReplayTransport::new([
+            exchange(HttpMethod::Get, "api/core/v2/tips", 503, "busy"),
+            exchange(HttpMethod::Get, "api/core/v2/tips", 200, TIPS),
+            exchange(HttpMethod::Post, "api/plugin/route", 503, "busy"),
+            exchange(HttpMethod::Post, "api/plugin/route", 200, "{}"),
+        ]))
+        .with_retry_policy(retry_policy.with_backoff(Duration::from_millis(1), Duration::from_millis(10)))
+        .finish()
+        .await
+}
+
+#[tokio::test]
+async fn retry_transient_errors() -> Result<()> {
+    let client = client(RetryPolicy::default().with_max_attempts(3)).await?;
+
+    assert_eq!(client.get_tips().await?.len(), 1);
+    // Plugin routes may not be idempotent, so they aren't retried by default.
+    assert!(matches!(
+        client
+            .call_plugin_route::<serde_json::Value>("api/plugin/", "POST", "route", Vec::new(), None)
+            .await,
+        Err(Error::Node(NodeApiError::ResponseError { code: 503, .. }))
+    ));
+    client
+        .call_plugin_route::<serde_json::Value>("api/plugin/", "POST", "route", Vec::new(), None)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_non_idempotent() -> Result<()> {
+    let client = client(RetryPolicy::default().with_max_attempts(3).with_idempotent_only(false)).await?;
+
+    client
+        .call_plugin_route::<serde_json::Value>("api/plugin/", "POST", "route", Vec::new(), None)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_disabled() -> Result<()> {
+    let client = client(RetryPolicy::none()).await?;
+
+    assert!(matches!(
+        client.get_tips().await,
+        Err(Error::Node(NodeApiError::ResponseError { code: 503, .. }))
+    ));
+    assert!(matches!(
+        client.get_tips().await,
+        Ok(tips) if tips.len() == 1
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_disabled_by_default() -> Result<()> {
+    let client = client(RetryPolicy::default()).await?;
+
+    assert!(matches!(
+        client.get_tips().await,
+        Err(Error::Node(NodeApiError::ResponseError { code: 503, .. }))
+    ));
+
+    Ok(())
+}
@@ -27,3 +27,30 @@ async fn mnemonic_secret_manager() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn mnemonic_with_passphrase_secret_manager() -> Result<()> {
+    let mnemonic = "acoustic trophy damage hint search taste love bicycle foster cradle brown govern endless depend situate athlete pudding blame question genius transfer van random vast";
+    let dto = format!(r#"{{"mnemonicWithPassphrase": {{"mnemonic": "{mnemonic}", "passphrase": "passphrase"}}}}"#);
+    let secret_manager: SecretManager = dto.parse()?;
+    let options = GetAddressesOptions::default()
+        .with_bech32_hrp(SHIMMER_TESTNET_BECH32_HRP)
+        .with_account_index(0)
+        .with_range(0..1);
+
+    let addresses = secret_manager.generate_ed25519_addresses(options.clone()).await?;
+
+    assert_eq!(
+        addresses,
+        SecretManager::try_from_mnemonic_with_passphrase(mnemonic.to_owned(), "passphrase")?
+            .generate_ed25519_addresses(options)
+            .await?
+    );
+    // The passphrase changes the seed.
+    assert_ne!(
+        addresses[0],
+        "rms1qzev36lk0gzld0k28fd2fauz26qqzh4hd4cwymlqlv96x7phjxcw6v3ea5a"
+    );
+
+    Ok(())
+}
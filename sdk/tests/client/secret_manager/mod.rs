@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod mnemonic;
+mod policy;
 #[cfg(feature = "private_key_secret_manager")]
 mod private_key;
 #[cfg(feature = "stronghold")]
 mod stronghold;
+mod watch_only;
@@ -0,0 +1,399 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use crypto::keys::bip44::Bip44;
+use iota_sdk::{
+    client::{
+        api::{GetAddressesOptions, PartiallySignedTransaction, PreparedTransactionData},
+        constants::{SHIMMER_COIN_TYPE, SHIMMER_TESTNET_BECH32_HRP},
+        secret::{
+            policy::{PolicySecretManager, PolicyToken, PolicyViolation, SigningPolicy, SpendingLimit},
+            SecretManage, SecretManager,
+        },
+        Error, Result,
+    },
+    types::block::{
+        address::Address,
+        input::{Input, UtxoInput},
+        output::{AliasId, InputsCommitment, NftId, Output},
+        payload::{
+            transaction::{RegularTransactionEssence, TransactionEssence},
+            TaggedDataPayload,
+        },
+        protocol::protocol_parameters,
+    },
+};
+
+use crate::client::{
+    build_inputs, build_outputs,
+    Build::{Alias, Basic, Nft},
+    ALIAS_ID_1, BECH32_ADDRESS_ALIAS_1, BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1, NFT_ID_1,
+};
+
+const MNEMONIC: &str = "acoustic trophy damage hint search taste love bicycle foster cradle brown govern endless depend situate athlete pudding blame question genius transfer van random vast";
+
+async fn own_address(secret_manager: &SecretManager) -> Result<String> {
+    Ok(secret_manager
+        .generate_ed25519_addresses(
+            GetAddressesOptions::default()
+                .with_bech32_hrp(SHIMMER_TESTNET_BECH32_HRP)
+                .with_coin_type(SHIMMER_COIN_TYPE)
+                .with_range(0..1),
+        )
+        .await?[0]
+        .to_string())
+}
+
+// Sends `amount` to `destination` out of 10 Mi of the first address, with the remainder going back to it.
+fn send(
+    own_address: &str,
+    destination: &str,
+    amount: u64,
+    tagged_data: Option<TaggedDataPayload>,
+) -> Result<PreparedTransactionData> {
+    let protocol_parameters = protocol_parameters();
+    let inputs = build_inputs([Basic(
+        10_000_000,
+        own_address,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Bip44::new(SHIMMER_COIN_TYPE)),
+    )]);
+    let outputs = build_outputs([
+        Basic(amount, destination, None, None, None, None, None, None),
+        Basic(10_000_000 - amount, own_address, None, None, None, None, None, None),
+    ]);
+
+    let essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(
+            protocol_parameters.network_id(),
+            InputsCommitment::new(inputs.iter().map(|i| &i.output)),
+        )
+        .with_inputs(
+            inputs
+                .iter()
+                .map(|i| Input::Utxo(UtxoInput::from(*i.output_metadata.output_id())))
+                .collect::<Vec<_>>(),
+        )
+        .with_outputs(outputs)
+        .with_payload(tagged_data)
+        .finish_with_params(protocol_parameters)?,
+    );
+
+    Ok(PreparedTransactionData {
+        essence,
+        inputs_data: inputs,
+        remainder: None,
+    })
+}
+
+#[tokio::test]
+async fn policy_secret_manager_spending_limits() -> Result<()> {
+    let storage_path = "test-storage/policy_secret_manager_spending_limits";
+    std::fs::remove_dir_all(storage_path).ok();
+    std::fs::create_dir_all(storage_path).ok();
+    let audit_file = format!("{storage_path}/audit.jsonl");
+
+    let policy = SigningPolicy::new().with_spending_limit(
+        PolicyToken::BaseCoin,
+        SpendingLimit {
+            per_transaction: Some(1_500_000.into()),
+            daily: Some(2_500_000.into()),
+        },
+    );
+    let secret_manager = PolicySecretManager::new(SecretManager::try_from_mnemonic(MNEMONIC)?, policy.clone())
+        .with_audit_file(&audit_file)?;
+    let own_address = own_address(secret_manager.secret_manager()).await?;
+
+    // The remainder going back to the input address isn't sent.
+    let prepared_transaction_data = send(&own_address, BECH32_ADDRESS_ED25519_0, 1_000_000, None)?;
+    secret_manager
+        .sign_transaction_essence(&prepared_transaction_data, None)
+        .await?;
+
+    let records = secret_manager.recent_audit_records().await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].essence_hash, prepared_transaction_data.essence.hash());
+    assert_eq!(records[0].sent[&PolicyToken::BaseCoin], 1_000_000.into());
+    assert_eq!(
+        records[0].destinations,
+        [Address::try_from_bech32(BECH32_ADDRESS_ED25519_0)?]
+    );
+
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&send(&own_address, BECH32_ADDRESS_ED25519_0, 2_000_000, None)?, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::TransactionLimitExceeded { .. }))
+    ));
+
+    secret_manager
+        .sign_transaction_essence(&send(&own_address, BECH32_ADDRESS_ED25519_1, 1_000_000, None)?, None)
+        .await?;
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&send(&own_address, BECH32_ADDRESS_ED25519_0, 1_000_000, None)?, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::DailyLimitExceeded { amount, .. })) if amount == 3_000_000.into()
+    ));
+
+    // The daily limit holds across restarts, with the records of the audit file.
+    let secret_manager =
+        PolicySecretManager::new(SecretManager::try_from_mnemonic(MNEMONIC)?, policy).with_audit_file(&audit_file)?;
+    assert_eq!(secret_manager.recent_audit_records().await.len(), 2);
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&send(&own_address, BECH32_ADDRESS_ED25519_0, 1_000_000, None)?, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::DailyLimitExceeded { .. }))
+    ));
+
+    std::fs::remove_dir_all(storage_path).ok();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn policy_secret_manager_rules() -> Result<()> {
+    let mnemonic_secret_manager = SecretManager::try_from_mnemonic(MNEMONIC)?;
+    let own_address = own_address(&mnemonic_secret_manager).await?;
+    let policy = SigningPolicy::new()
+        .with_allowed_destinations([Address::try_from_bech32(BECH32_ADDRESS_ED25519_1)?])
+        .with_forbid_burns(true)
+        .with_max_outputs(2)
+        .with_forbid_tagged_data(true);
+    let secret_manager = PolicySecretManager::new(mnemonic_secret_manager, policy);
+
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&send(&own_address, BECH32_ADDRESS_ED25519_0, 1_000_000, None)?, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::DestinationNotAllowed(address)))
+            if address == Address::try_from_bech32(BECH32_ADDRESS_ED25519_0)?
+    ));
+
+    let tagged_data = TaggedDataPayload::new(b"tag".to_vec(), b"data".to_vec())?;
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(
+                &send(&own_address, BECH32_ADDRESS_ED25519_1, 1_000_000, Some(tagged_data))?,
+                None
+            )
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::TaggedData))
+    ));
+
+    // An NFT consumed without being sent again is burned.
+    let mut prepared_transaction_data = send(&own_address, BECH32_ADDRESS_ED25519_1, 1_000_000, None)?;
+    prepared_transaction_data.inputs_data.extend(build_inputs([Nft(
+        1_000_000,
+        NftId::from_str(NFT_ID_1)?,
+        &own_address,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Bip44::new(SHIMMER_COIN_TYPE)),
+    )]));
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&prepared_transaction_data, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::Burn))
+    ));
+
+    let mut prepared_transaction_data = send(&own_address, BECH32_ADDRESS_ED25519_1, 1_000_000, None)?;
+    let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+    let mut outputs = essence.outputs().to_vec();
+    outputs.extend(build_outputs([Basic(
+        1_000_000,
+        &own_address,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]));
+    prepared_transaction_data.essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(essence.network_id(), *essence.inputs_commitment())
+            .with_inputs(essence.inputs().to_vec())
+            .with_outputs(outputs)
+            .finish_with_params(protocol_parameters())?,
+    );
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&prepared_transaction_data, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::TooManyOutputs {
+            count: 3,
+            max: 2
+        }))
+    ));
+
+    secret_manager
+        .sign_transaction_essence(&send(&own_address, BECH32_ADDRESS_ED25519_1, 1_000_000, None)?, None)
+        .await?;
+    assert!(matches!(
+        secret_manager
+            .sign_ed25519(b"message", Bip44::new(SHIMMER_COIN_TYPE))
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::RawSigning))
+    ));
+
+    Ok(())
+}
+
+// Replaces the outputs of a transaction spending 10 Mi of the first address.
+fn send_outputs(own_address: &str, outputs: Vec<Output>) -> Result<PreparedTransactionData> {
+    let mut prepared_transaction_data = send(own_address, own_address, 1_000_000, None)?;
+    let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+    prepared_transaction_data.essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(essence.network_id(), *essence.inputs_commitment())
+            .with_inputs(essence.inputs().to_vec())
+            .with_outputs(outputs)
+            .finish_with_params(protocol_parameters())?,
+    );
+
+    Ok(prepared_transaction_data)
+}
+
+#[tokio::test]
+async fn policy_secret_manager_unlock_addresses() -> Result<()> {
+    let mnemonic_secret_manager = SecretManager::try_from_mnemonic(MNEMONIC)?;
+    let own_address = own_address(&mnemonic_secret_manager).await?;
+    let policy = SigningPolicy::new().with_allowed_destinations([Address::try_from_bech32(BECH32_ADDRESS_ED25519_1)?]);
+    let secret_manager = PolicySecretManager::new(mnemonic_secret_manager, policy);
+    let forbidden_address = Address::try_from_bech32(BECH32_ADDRESS_ED25519_0)?;
+
+    // Sending everything back to the own address is allowed.
+    secret_manager
+        .sign_transaction_essence(&send(&own_address, &own_address, 1_000_000, None)?, None)
+        .await?;
+
+    // The return address of an expiration can unlock the output once it expired.
+    let outputs = build_outputs([Basic(
+        10_000_000,
+        &own_address,
+        None,
+        None,
+        None,
+        None,
+        Some((BECH32_ADDRESS_ED25519_0, 1)),
+        None,
+    )]);
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&send_outputs(&own_address, outputs)?, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::DestinationNotAllowed(address))) if address == forbidden_address
+    ));
+
+    // The state controller of an alias can transition it.
+    let outputs = build_outputs([Alias(
+        10_000_000,
+        AliasId::null(),
+        0,
+        BECH32_ADDRESS_ED25519_0,
+        &own_address,
+        None,
+        None,
+        None,
+        None,
+    )]);
+    assert!(matches!(
+        secret_manager
+            .sign_transaction_essence(&send_outputs(&own_address, outputs)?, None)
+            .await,
+        Err(Error::PolicyViolation(PolicyViolation::DestinationNotAllowed(address))) if address == forbidden_address
+    ));
+
+    // Outputs owned by an alias of the transaction can be unlocked by its state controller.
+    for (state_controller, allowed) in [(own_address.as_str(), true), (BECH32_ADDRESS_ED25519_0, false)] {
+        let mut prepared_transaction_data = send(&own_address, &own_address, 9_000_000, None)?;
+        prepared_transaction_data.inputs_data.extend(build_inputs([Alias(
+            1_000_000,
+            AliasId::from_str(ALIAS_ID_1)?,
+            0,
+            &own_address,
+            &own_address,
+            None,
+            None,
+            None,
+            Some(Bip44::new(SHIMMER_COIN_TYPE)),
+        )]));
+        let outputs = build_outputs([
+            Alias(
+                1_000_000,
+                AliasId::from_str(ALIAS_ID_1)?,
+                1,
+                state_controller,
+                &own_address,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Basic(10_000_000, BECH32_ADDRESS_ALIAS_1, None, None, None, None, None, None),
+        ]);
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        prepared_transaction_data.essence = TransactionEssence::Regular(
+            RegularTransactionEssence::builder(
+                essence.network_id(),
+                InputsCommitment::new(prepared_transaction_data.inputs_data.iter().map(|i| &i.output)),
+            )
+            .with_inputs(
+                prepared_transaction_data
+                    .inputs_data
+                    .iter()
+                    .map(|i| Input::Utxo(UtxoInput::from(*i.output_metadata.output_id())))
+                    .collect::<Vec<_>>(),
+            )
+            .with_outputs(outputs)
+            .finish_with_params(protocol_parameters())?,
+        );
+        let result = secret_manager
+            .sign_transaction_essence(&prepared_transaction_data, None)
+            .await;
+
+        if allowed {
+            result?;
+        } else {
+            assert!(matches!(
+                result,
+                Err(Error::PolicyViolation(PolicyViolation::DestinationNotAllowed(address))) if address == forbidden_address
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn policy_secret_manager_partially_signed_transaction() -> Result<()> {
+    let mnemonic_secret_manager = SecretManager::try_from_mnemonic(MNEMONIC)?;
+    let own_address = own_address(&mnemonic_secret_manager).await?;
+    let policy = SigningPolicy::new().with_allowed_destinations([Address::try_from_bech32(BECH32_ADDRESS_ED25519_1)?]);
+    let secret_manager = PolicySecretManager::new(mnemonic_secret_manager, policy);
+
+    let mut partially_signed_transaction =
+        PartiallySignedTransaction::new(send(&own_address, BECH32_ADDRESS_ED25519_0, 1_000_000, None)?, None);
+    assert!(matches!(
+        partially_signed_transaction.sign(&secret_manager).await,
+        Err(Error::PolicyViolation(PolicyViolation::DestinationNotAllowed(_)))
+    ));
+    assert!(partially_signed_transaction.signature_unlocks().is_empty());
+
+    let mut partially_signed_transaction =
+        PartiallySignedTransaction::new(send(&own_address, BECH32_ADDRESS_ED25519_1, 1_000_000, None)?, None);
+    assert_eq!(partially_signed_transaction.sign(&secret_manager).await?, 1);
+    assert!(partially_signed_transaction.is_complete()?);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::bip44::Bip44;
+use iota_sdk::client::{
+    api::GetAddressesOptions,
+    constants::{SHIMMER_COIN_TYPE, SHIMMER_TESTNET_BECH32_HRP},
+    secret::{
+        watch_only::WatchOnlySecretManager, GenerateAddressOptions, SecretManage, SecretManager, SecretManagerDto,
+    },
+    Error, Result,
+};
+
+#[tokio::test]
+async fn watch_only_secret_manager() -> Result<()> {
+    let dto = r#"{"mnemonic": "acoustic trophy damage hint search taste love bicycle foster cradle brown govern endless depend situate athlete pudding blame question genius transfer van random vast"}"#;
+    let mnemonic_secret_manager: SecretManager = dto.parse()?;
+
+    let account = WatchOnlySecretManager::export_account(&mnemonic_secret_manager, SHIMMER_COIN_TYPE, 0, 2).await?;
+    let secret_manager = SecretManager::from(WatchOnlySecretManager::new([account]));
+
+    for internal in [false, true] {
+        let options = GetAddressesOptions::default()
+            .with_bech32_hrp(SHIMMER_TESTNET_BECH32_HRP)
+            .with_coin_type(SHIMMER_COIN_TYPE)
+            .with_range(0..2)
+            .with_options(GenerateAddressOptions {
+                internal,
+                ledger_nano_prompt: false,
+            });
+
+        assert_eq!(
+            secret_manager.generate_ed25519_addresses(options.clone()).await?,
+            mnemonic_secret_manager.generate_ed25519_addresses(options).await?
+        );
+    }
+
+    assert!(matches!(
+        secret_manager
+            .generate_ed25519_addresses(
+                GetAddressesOptions::default()
+                    .with_coin_type(SHIMMER_COIN_TYPE)
+                    .with_range(2..3),
+            )
+            .await,
+        Err(Error::MissingPublicKey { address_index: 2, .. })
+    ));
+    assert!(matches!(
+        secret_manager
+            .generate_ed25519_addresses(
+                GetAddressesOptions::default()
+                    .with_coin_type(SHIMMER_COIN_TYPE)
+                    .with_account_index(1)
+                    .with_range(0..1),
+            )
+            .await,
+        Err(Error::MissingPublicKey { account_index: 1, .. })
+    ));
+    assert!(matches!(
+        secret_manager
+            .sign_ed25519(&[0; 32], Bip44::new(SHIMMER_COIN_TYPE))
+            .await,
+        Err(Error::WatchOnlySecretManager)
+    ));
+
+    // The public keys can be stored and restored.
+    let dto = serde_json::to_string(&SecretManagerDto::from(&secret_manager))?;
+    let restored: SecretManager = dto.parse()?;
+    let options = GetAddressesOptions::default()
+        .with_bech32_hrp(SHIMMER_TESTNET_BECH32_HRP)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_range(0..2);
+
+    assert_eq!(
+        restored.generate_ed25519_addresses(options.clone()).await?,
+        mnemonic_secret_manager.generate_ed25519_addresses(options).await?
+    );
+
+    Ok(())
+}
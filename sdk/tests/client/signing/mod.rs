@@ -4,6 +4,7 @@
 mod alias;
 mod basic;
 mod nft;
+mod partially_signed;
 
 use std::str::FromStr;
 
@@ -0,0 +1,197 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use crypto::keys::bip44::Bip44;
+use iota_sdk::{
+    client::{
+        api::{
+            GetAddressesOptions, PartiallySignedTransaction, PartiallySignedTransactionDto, PreparedTransactionData,
+        },
+        constants::{SHIMMER_COIN_TYPE, SHIMMER_TESTNET_BECH32_HRP},
+        secret::{SecretManage, SecretManager},
+        Client, Error, Result,
+    },
+    types::{
+        block::{
+            address::{Bech32Address, ToBech32Ext},
+            input::{Input, UtxoInput},
+            output::{AliasId, InputsCommitment},
+            payload::transaction::{RegularTransactionEssence, TransactionEssence},
+            protocol::protocol_parameters,
+            unlock::{SignatureUnlock, Unlock},
+        },
+        TryFromDto,
+    },
+};
+
+use crate::client::{
+    build_inputs, build_outputs,
+    Build::{Alias, Basic},
+    ALIAS_ID_1, BECH32_ADDRESS_ALIAS_1,
+};
+
+async fn first_address(secret_manager: &SecretManager) -> Result<Bech32Address> {
+    Ok(secret_manager
+        .generate_ed25519_addresses(
+            GetAddressesOptions::default()
+                .with_coin_type(SHIMMER_COIN_TYPE)
+                .with_range(0..1),
+        )
+        .await?[0]
+        .to_bech32(SHIMMER_TESTNET_BECH32_HRP))
+}
+
+// An alias state controlled by one party, a basic output owned by the alias and one owned by another party.
+async fn multi_party_transaction(
+    state_controller: &SecretManager,
+    owner: &SecretManager,
+) -> Result<PreparedTransactionData> {
+    let state_controller_address = first_address(state_controller).await?.to_string();
+    let owner_address = first_address(owner).await?.to_string();
+    let protocol_parameters = protocol_parameters();
+    let alias_id = AliasId::from_str(ALIAS_ID_1)?;
+
+    let inputs = build_inputs([
+        Alias(
+            1_000_000,
+            alias_id,
+            0,
+            &state_controller_address,
+            &state_controller_address,
+            None,
+            None,
+            None,
+            Some(Bip44::new(SHIMMER_COIN_TYPE)),
+        ),
+        Basic(1_000_000, BECH32_ADDRESS_ALIAS_1, None, None, None, None, None, None),
+        Basic(
+            1_000_000,
+            &owner_address,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Bip44::new(SHIMMER_COIN_TYPE)),
+        ),
+    ]);
+
+    let outputs = build_outputs([
+        Alias(
+            1_000_000,
+            alias_id,
+            1,
+            &state_controller_address,
+            &state_controller_address,
+            None,
+            None,
+            None,
+            None,
+        ),
+        Basic(2_000_000, &owner_address, None, None, None, None, None, None),
+    ]);
+
+    let essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(
+            protocol_parameters.network_id(),
+            InputsCommitment::new(inputs.iter().map(|i| &i.output)),
+        )
+        .with_inputs(
+            inputs
+                .iter()
+                .map(|i| Input::Utxo(UtxoInput::from(*i.output_metadata.output_id())))
+                .collect::<Vec<_>>(),
+        )
+        .with_outputs(outputs)
+        .finish_with_params(protocol_parameters)?,
+    );
+
+    Ok(PreparedTransactionData {
+        essence,
+        inputs_data: inputs,
+        remainder: None,
+    })
+}
+
+#[tokio::test]
+async fn partially_signed_transaction_multi_party() -> Result<()> {
+    let state_controller = SecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+    let owner = SecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+    let prepared_transaction_data = multi_party_transaction(&state_controller, &owner).await?;
+
+    let mut state_controller_transaction = PartiallySignedTransaction::new(prepared_transaction_data.clone(), 100);
+    assert_eq!(state_controller_transaction.required_addresses()?.len(), 2);
+    assert!(!state_controller_transaction.is_complete()?);
+
+    // Each party only adds the signature of its own address.
+    assert_eq!(state_controller_transaction.sign(&state_controller).await?, 1);
+    assert_eq!(state_controller_transaction.sign(&state_controller).await?, 0);
+    assert_eq!(
+        state_controller_transaction.missing_addresses()?,
+        [*first_address(&owner).await?.inner().as_ed25519()]
+    );
+    assert!(matches!(
+        state_controller_transaction.clone().finalize(),
+        Err(Error::MissingSignature(_))
+    ));
+
+    let mut owner_transaction = PartiallySignedTransaction::new(prepared_transaction_data, 100);
+    assert_eq!(owner_transaction.sign(&owner).await?, 1);
+
+    state_controller_transaction.merge(owner_transaction)?;
+    assert!(state_controller_transaction.is_complete()?);
+
+    let tx_payload = state_controller_transaction.finalize()?;
+    let unlocks = tx_payload.unlocks();
+
+    assert_eq!(unlocks.len(), 3);
+    assert_eq!((*unlocks).get(0).unwrap().kind(), SignatureUnlock::KIND);
+    match (*unlocks).get(1).unwrap() {
+        Unlock::Alias(unlock) => assert_eq!(unlock.index(), 0),
+        _ => panic!("invalid unlock type"),
+    }
+    assert_eq!((*unlocks).get(2).unwrap().kind(), SignatureUnlock::KIND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn partially_signed_transaction_dto_and_mismatch() -> Result<()> {
+    let state_controller = SecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+    let owner = SecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+    let prepared_transaction_data = multi_party_transaction(&state_controller, &owner).await?;
+
+    let mut partially_signed_transaction = PartiallySignedTransaction::new(prepared_transaction_data.clone(), 100);
+    partially_signed_transaction.sign(&owner).await?;
+
+    let dto = PartiallySignedTransactionDto::from(&partially_signed_transaction);
+    let json = serde_json::to_string(&dto)?;
+    let from_dto = PartiallySignedTransaction::try_from_dto(serde_json::from_str(&json)?)?;
+    assert_eq!(from_dto, partially_signed_transaction);
+
+    // A signature of another transaction isn't accepted.
+    let other_transaction = multi_party_transaction(&state_controller, &owner).await?;
+    let Unlock::Signature(signature_unlock) = owner
+        .signature_unlock(&other_transaction.essence.hash(), Bip44::new(SHIMMER_COIN_TYPE))
+        .await?
+    else {
+        panic!("invalid unlock type");
+    };
+    assert!(matches!(
+        partially_signed_transaction.add_signature_unlock(signature_unlock),
+        Err(Error::UnexpectedSignature)
+    ));
+
+    assert!(matches!(
+        partially_signed_transaction.merge(PartiallySignedTransaction::new(other_transaction, 100)),
+        Err(Error::PartiallySignedTransactionMismatch)
+    ));
+    assert!(matches!(
+        partially_signed_transaction.merge(PartiallySignedTransaction::new(prepared_transaction_data, 0)),
+        Err(Error::PartiallySignedTransactionMismatch)
+    ));
+
+    Ok(())
+}
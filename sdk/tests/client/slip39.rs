@@ -0,0 +1,95 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::bip39::Mnemonic;
+use iota_sdk::client::{
+    slip39::{self, ShareGroup},
+    Client, Error, Result,
+};
+
+// Test vectors of <https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json>.
+#[test]
+fn slip39_vectors() -> Result<()> {
+    let master_secret = slip39::recover_master_secret(
+        &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+        b"TREZOR",
+    )?;
+    assert_eq!(
+        prefix_hex::encode(master_secret.as_slice()),
+        "0xbb54aac4b89dc868ba37d9cc21b2cece"
+    );
+
+    let master_secret = slip39::recover_master_secret(
+        &[
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ],
+        b"TREZOR",
+    )?;
+    assert_eq!(
+        prefix_hex::encode(master_secret.as_slice()),
+        "0xb43ceb7e57a0ea8766221624d01b0864"
+    );
+
+    let master_secret = slip39::recover_master_secret(
+        &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+        b"TREZOR",
+    )?;
+    assert_eq!(
+        prefix_hex::encode(master_secret.as_slice()),
+        "0x989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92"
+    );
+
+    // Invalid checksum.
+    assert!(matches!(
+        slip39::recover_master_secret(
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"],
+            b"TREZOR",
+        ),
+        Err(slip39::Error::InvalidChecksum)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn split_and_combine_mnemonic() -> Result<()> {
+    let mnemonic = Client::generate_mnemonic()?;
+    let shares = Client::split_mnemonic(
+        &*mnemonic,
+        2,
+        &[ShareGroup::new(1, 1), ShareGroup::new(2, 3), ShareGroup::new(3, 5)],
+    )?;
+    assert_eq!(shares.iter().map(Vec::len).collect::<Vec<_>>(), [1, 3, 5]);
+
+    let combined =
+        Client::combine_mnemonic_shares(&[shares[0][0].clone(), shares[1][2].clone(), shares[1][0].clone()])?;
+    assert_eq!(combined.as_ref(), mnemonic.as_ref());
+    let combined = Client::combine_mnemonic_shares(&[
+        shares[2][4].clone(),
+        shares[1][1].clone(),
+        shares[2][0].clone(),
+        shares[1][2].clone(),
+        shares[2][2].clone(),
+    ])?;
+    assert_eq!(combined.as_ref(), mnemonic.as_ref());
+
+    // A single complete group isn't enough.
+    assert!(matches!(
+        Client::combine_mnemonic_shares(&[shares[0][0].clone(), shares[1][0].clone()]),
+        Err(Error::Slip39(slip39::Error::InsufficientShares(_)))
+    ));
+    // Shares of another mnemonic.
+    let other_shares = Client::split_mnemonic(&*mnemonic, 1, &[ShareGroup::new(2, 2)])?;
+    assert!(matches!(
+        Client::combine_mnemonic_shares(&[shares[0][0].clone(), other_shares[0][0].clone()]),
+        Err(Error::Slip39(slip39::Error::SharesMismatch(_)))
+    ));
+    assert!(matches!(
+        Client::split_mnemonic(&*mnemonic, 1, &[ShareGroup::new(1, 2)]),
+        Err(Error::Slip39(slip39::Error::InvalidParameters(_)))
+    ));
+    assert!(Client::split_mnemonic(Mnemonic::from("invalid mnemonic"), 1, &[ShareGroup::new(1, 1)]).is_err());
+
+    Ok(())
+}
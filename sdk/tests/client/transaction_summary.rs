@@ -0,0 +1,201 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use iota_sdk::{
+    client::{
+        api::{PreparedTransactionData, StorageDepositSummary, TransactionSummaryDto},
+        secret::types::InputSigningData,
+    },
+    types::block::{
+        input::{Input, UtxoInput},
+        output::{InputsCommitment, NativeToken, NftId, Output, TokenId},
+        payload::{
+            transaction::{RegularTransactionEssence, TransactionEssence},
+            Payload, TaggedDataPayload,
+        },
+        protocol::protocol_parameters,
+    },
+};
+
+use crate::client::{
+    addresses, build_inputs, build_outputs,
+    Build::{Basic, Nft},
+    BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1, NFT_ID_1, TOKEN_ID_1,
+};
+
+fn prepared_transaction(
+    inputs: Vec<InputSigningData>,
+    outputs: Vec<Output>,
+    tagged_data: Option<TaggedDataPayload>,
+) -> PreparedTransactionData {
+    let protocol_parameters = protocol_parameters();
+    let essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(
+            protocol_parameters.network_id(),
+            InputsCommitment::new(inputs.iter().map(|i| &i.output)),
+        )
+        .with_inputs(
+            inputs
+                .iter()
+                .map(|i| Input::Utxo(UtxoInput::from(*i.output_metadata.output_id())))
+                .collect::<Vec<_>>(),
+        )
+        .with_outputs(outputs)
+        .with_payload(tagged_data.map(Payload::from))
+        .finish_with_params(protocol_parameters)
+        .unwrap(),
+    );
+
+    PreparedTransactionData {
+        essence,
+        inputs_data: inputs,
+        remainder: None,
+    }
+}
+
+fn native_token(token_id: &str, amount: u64) -> NativeToken {
+    NativeToken::new(TokenId::from_str(token_id).unwrap(), amount).unwrap()
+}
+
+#[test]
+fn transaction_summary_transfer() {
+    let nft_id_1 = NftId::from_str(NFT_ID_1).unwrap();
+    let inputs = build_inputs([
+        Basic(
+            1_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            Some(vec![(TOKEN_ID_1, 100)]),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        Nft(
+            500_000,
+            nft_id_1,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+    ]);
+    let outputs = build_outputs([
+        Basic(
+            700_000,
+            BECH32_ADDRESS_ED25519_0,
+            Some(vec![(TOKEN_ID_1, 60)]),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        Basic(
+            300_000,
+            BECH32_ADDRESS_ED25519_1,
+            Some(vec![(TOKEN_ID_1, 30)]),
+            None,
+            Some((BECH32_ADDRESS_ED25519_0, 250_000)),
+            None,
+            None,
+            None,
+        ),
+        Nft(
+            500_000,
+            nft_id_1,
+            BECH32_ADDRESS_ED25519_1,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+    ]);
+    let tagged_data = TaggedDataPayload::new(b"tag".to_vec(), b"data".to_vec()).unwrap();
+    let summary = prepared_transaction(inputs, outputs, Some(tagged_data.clone())).summary();
+    let addresses = addresses([BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1]);
+
+    assert_eq!(summary.addresses.len(), 2);
+    let sender = summary.address(&addresses[0]).unwrap();
+    assert_eq!(sender.base_coin_sent, 800_000);
+    assert_eq!(sender.base_coin_received, 0);
+    assert_eq!(sender.native_tokens_sent, [native_token(TOKEN_ID_1, 40)]);
+    assert_eq!(sender.nfts_sent, [nft_id_1]);
+    assert!(sender.nfts_received.is_empty());
+
+    let receiver = summary.address(&addresses[1]).unwrap();
+    assert_eq!(receiver.base_coin_sent, 0);
+    assert_eq!(receiver.base_coin_received, 800_000);
+    assert_eq!(receiver.native_tokens_received, [native_token(TOKEN_ID_1, 30)]);
+    assert_eq!(receiver.nfts_received, [nft_id_1]);
+
+    assert_eq!(
+        summary.storage_deposits_locked,
+        [StorageDepositSummary {
+            return_address: addresses[0],
+            amount: 250_000
+        }]
+    );
+    assert!(summary.storage_deposits_returned.is_empty());
+    assert_eq!(summary.burned_native_tokens, [native_token(TOKEN_ID_1, 10)]);
+    assert_eq!(summary.tagged_data, Some(tagged_data));
+
+    let json = serde_json::to_value(TransactionSummaryDto::from(&summary)).unwrap();
+    assert_eq!(json["addresses"].as_array().unwrap().len(), 2);
+    assert_eq!(json["storageDepositsLocked"][0]["amount"], "250000");
+}
+
+#[test]
+fn transaction_summary_mint_nft() {
+    let inputs = build_inputs([Basic(
+        1_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+    let outputs = build_outputs([
+        Nft(
+            500_000,
+            NftId::null(),
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        Nft(
+            500_000,
+            NftId::null(),
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+    ]);
+    let summary = prepared_transaction(inputs, outputs, None).summary();
+
+    // The base coins stay with the address, only the minted NFTs are a change.
+    assert_eq!(summary.addresses.len(), 1);
+    let minter = &summary.addresses[0];
+    assert_eq!(minter.base_coin_sent, 0);
+    assert_eq!(minter.base_coin_received, 0);
+    assert_eq!(minter.nfts_received, [NftId::null(), NftId::null()]);
+    assert!(summary.burned_native_tokens.is_empty());
+    assert_eq!(summary.tagged_data, None);
+}
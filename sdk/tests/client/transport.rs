@@ -0,0 +1,64 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        mock_node::MockNode,
+        node_api::{error::Error as NodeApiError, indexer::query_parameters::QueryParameter},
+        node_manager::transport::{RecordingTransport, ReplayTransport},
+        Client, Error, Result,
+    },
+    types::block::address::{Ed25519Address, ToBech32Ext},
+};
+
+#[tokio::test]
+async fn transport_record_replay() -> Result<()> {
+    let storage_path = "test-storage/transport_record_replay";
+    std::fs::create_dir_all(storage_path).ok();
+    let session_path = format!("{storage_path}/session.json");
+
+    let node = MockNode::builder().finish().await?;
+    let node_url = node.url().to_string();
+    let address = Ed25519Address::new([1; Ed25519Address::LENGTH]).to_bech32_unchecked("rms");
+    node.fund(address, 1_000_000).await?;
+
+    let recorder = RecordingTransport::default();
+    let client = Client::builder()
+        .with_node(&node_url)?
+        .with_transport(recorder.clone())
+        .finish()
+        .await?;
+    let info = client.get_info().await?;
+    let milestone = client.get_milestone_by_index(1).await?;
+    let output_ids = client.basic_output_ids([QueryParameter::Address(address)]).await?;
+    let outputs = client.get_outputs(&output_ids.items).await?;
+    recorder.save(&session_path)?;
+
+    drop(client);
+    drop(node);
+
+    let client = Client::builder()
+        .with_node(&node_url)?
+        .with_transport(ReplayTransport::from_file(&session_path)?)
+        .finish()
+        .await?;
+    assert_eq!(client.get_info().await?, info);
+    assert_eq!(client.get_milestone_by_index(1).await?, milestone);
+    let replayed_output_ids = client.basic_output_ids([QueryParameter::Address(address)]).await?;
+    assert_eq!(replayed_output_ids.items, output_ids.items);
+    let replayed_outputs = client.get_outputs(&output_ids.items).await?;
+    assert!(replayed_outputs
+        .iter()
+        .map(|o| o.output())
+        .eq(outputs.iter().map(|o| o.output())));
+
+    // Requests that weren't recorded can't be answered.
+    assert!(matches!(
+        client.get_milestone_by_index(2).await,
+        Err(Error::Node(NodeApiError::Transport(_)))
+    ));
+
+    std::fs::remove_dir_all(storage_path).ok();
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::types::{
+    block::{
+        payload::milestone::{
+            dto::InclusionProofDto, InclusionProof, MerkleHasher, MerkleProof, MilestoneEssence, MilestoneIndex,
+            MilestoneOptions, MilestonePayload,
+        },
+        protocol::protocol_parameters,
+        rand::{
+            block::{rand_block, rand_block_ids},
+            milestone::{rand_merkle_root, rand_milestone_id},
+            parents::rand_parents,
+            signature::rand_signature,
+        },
+        Block, BlockId, Error,
+    },
+    TryFromDto,
+};
+
+fn milestone(included_block_ids: &[BlockId]) -> MilestonePayload {
+    MilestonePayload::new(
+        MilestoneEssence::new(
+            MilestoneIndex(1),
+            0,
+            protocol_parameters().protocol_version(),
+            rand_milestone_id(),
+            rand_parents(),
+            MerkleHasher::digest(included_block_ids),
+            rand_merkle_root(),
+            [],
+            MilestoneOptions::from_vec(vec![]).unwrap(),
+        )
+        .unwrap(),
+        [rand_signature()],
+    )
+    .unwrap()
+}
+
+fn included_blocks(block: &Block, position: usize, len: usize) -> Vec<BlockId> {
+    let mut block_ids = rand_block_ids(len - 1);
+    block_ids.insert(position, block.id());
+    block_ids
+}
+
+#[test]
+fn merkle_proof_root() {
+    for len in 1..=9 {
+        let block_ids = rand_block_ids(len);
+        let root = MerkleHasher::digest(&block_ids);
+
+        for (index, block_id) in block_ids.iter().enumerate() {
+            let proof = MerkleProof::new(&block_ids, index).unwrap();
+            assert_eq!(proof.root(), root);
+            assert!(proof.contains(block_id));
+        }
+        assert_eq!(
+            MerkleProof::new(&block_ids, len),
+            Err(Error::InvalidMerkleProofIndex(len))
+        );
+    }
+}
+
+#[test]
+fn merkle_hasher_empty() {
+    assert_eq!(
+        MerkleHasher::digest(&[]).to_string(),
+        "0x0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+    );
+}
+
+#[test]
+fn inclusion_proof_verify() {
+    let block = rand_block();
+    let block_ids = included_blocks(&block, 2, 5);
+    let proof = InclusionProof::new(milestone(&block_ids), block.clone(), &block_ids).unwrap();
+
+    assert!(proof.verify());
+    assert_eq!(proof.block(), &block);
+
+    // Another block isn't proven by the same audit path.
+    let mut tampered = InclusionProofDto::from(&proof);
+    tampered.block = (&rand_block()).into();
+    assert!(
+        !InclusionProof::try_from_dto_with_params(tampered, protocol_parameters())
+            .unwrap()
+            .verify()
+    );
+
+    // Nor is the block proven against another milestone.
+    let mut tampered = InclusionProofDto::from(&proof);
+    tampered.milestone = (&milestone(&rand_block_ids(5))).into();
+    assert!(
+        !InclusionProof::try_from_dto_with_params(tampered, protocol_parameters())
+            .unwrap()
+            .verify()
+    );
+}
+
+#[test]
+fn inclusion_proof_new_invalid() {
+    let block = rand_block();
+    let block_ids = included_blocks(&block, 0, 3);
+
+    assert!(matches!(
+        InclusionProof::new(milestone(&block_ids), block.clone(), &block_ids[1..]),
+        Err(Error::MilestoneInclusionMerkleRootMismatch { .. })
+    ));
+
+    let other_block_ids = rand_block_ids(3);
+    assert_eq!(
+        InclusionProof::new(milestone(&other_block_ids), block.clone(), &other_block_ids),
+        Err(Error::MissingIncludedBlock(block.id()))
+    );
+}
+
+#[test]
+fn inclusion_proof_json_roundtrip() {
+    let block = rand_block();
+    let block_ids = included_blocks(&block, 6, 7);
+    let proof = InclusionProof::new(milestone(&block_ids), block, &block_ids).unwrap();
+
+    let json = serde_json::to_value(InclusionProofDto::from(&proof)).unwrap();
+    assert!(json["proof"]["r"]["l"]["h"].is_string());
+    let dto = serde_json::from_value::<InclusionProofDto>(json).unwrap();
+    let roundtrip = InclusionProof::try_from_dto_with_params(dto, protocol_parameters()).unwrap();
+
+    assert_eq!(roundtrip, proof);
+    assert!(roundtrip.verify());
+}
@@ -0,0 +1,174 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::{
+    hashes::{blake2b::Blake2b256, Digest},
+    signatures::ed25519,
+};
+use iota_sdk::types::block::{
+    address::{Address, Ed25519Address},
+    input::{Input, UtxoInput},
+    output::{
+        unlock_condition::{AddressUnlockCondition, TimelockUnlockCondition},
+        BasicOutput, InputsCommitment, Output, OutputId,
+    },
+    payload::transaction::{RegularTransactionEssence, TransactionEssence, TransactionId, TransactionPayload},
+    protocol::protocol_parameters,
+    rand::transaction::rand_transaction_id,
+    semantic::{ConflictReason, LedgerState},
+    signature::{Ed25519Signature, Signature},
+    unlock::{ReferenceUnlock, SignatureUnlock, Unlock, Unlocks},
+};
+
+fn address(key: &ed25519::SecretKey) -> Address {
+    Address::from(Ed25519Address::new(
+        Blake2b256::digest(key.public_key().to_bytes()).into(),
+    ))
+}
+
+fn basic_output(address: Address, amount: u64, timelock: Option<u32>) -> Output {
+    let mut builder = BasicOutput::build_with_amount(amount).add_unlock_condition(AddressUnlockCondition::new(address));
+
+    if let Some(timelock) = timelock {
+        builder = builder.add_unlock_condition(TimelockUnlockCondition::new(timelock).unwrap());
+    }
+
+    Output::Basic(
+        builder
+            .finish_with_params(protocol_parameters().token_supply())
+            .unwrap(),
+    )
+}
+
+fn transaction(
+    key: &ed25519::SecretKey,
+    inputs: &[(OutputId, &Output)],
+    outputs: impl Into<Vec<Output>>,
+) -> TransactionPayload {
+    let protocol_parameters = protocol_parameters();
+    let essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(
+            protocol_parameters.network_id(),
+            InputsCommitment::new(inputs.iter().map(|(_, output)| *output)),
+        )
+        .with_inputs(
+            inputs
+                .iter()
+                .map(|(output_id, _)| Input::Utxo(UtxoInput::from(*output_id)))
+                .collect::<Vec<_>>(),
+        )
+        .with_outputs(outputs)
+        .finish_with_params(&protocol_parameters)
+        .unwrap(),
+    );
+    let signature = Ed25519Signature::new(key.public_key(), key.sign(&essence.hash()));
+    let unlocks = core::iter::once(Unlock::Signature(SignatureUnlock::from(Signature::from(signature))))
+        .chain((1..inputs.len()).map(|_| Unlock::Reference(ReferenceUnlock::new(0).unwrap())))
+        .collect::<Vec<_>>();
+
+    TransactionPayload::new(essence, Unlocks::new(unlocks).unwrap()).unwrap()
+}
+
+fn output_id(transaction_id: TransactionId, index: u16) -> OutputId {
+    OutputId::new(transaction_id, index).unwrap()
+}
+
+#[test]
+fn ledger_state_dependent_transactions() {
+    let key = ed25519::SecretKey::generate().unwrap();
+    let genesis_id = output_id(rand_transaction_id(), 0);
+    let genesis = basic_output(address(&key), 1_000_000, None);
+    let mut ledger_state = LedgerState::new(1, 0);
+    ledger_state.add_output(genesis_id, genesis.clone());
+
+    let split = [
+        basic_output(address(&key), 600_000, None),
+        basic_output(address(&key), 400_000, None),
+    ];
+    let tx_1 = transaction(&key, &[(genesis_id, &genesis)], split.clone());
+    let tx_2 = transaction(
+        &key,
+        &[
+            (output_id(tx_1.id(), 0), &split[0]),
+            (output_id(tx_1.id(), 1), &split[1]),
+        ],
+        [basic_output(address(&key), 1_000_000, None)],
+    );
+
+    // The outputs of the first transaction don't exist yet.
+    assert_eq!(
+        ledger_state.validate_transaction(&tx_2),
+        Ok(ConflictReason::InputUtxoNotFound)
+    );
+    assert_eq!(
+        ledger_state.apply_transactions([&tx_1, &tx_2, &tx_1]),
+        [
+            Ok(ConflictReason::None),
+            Ok(ConflictReason::None),
+            Ok(ConflictReason::InputUtxoAlreadySpentInThisMilestone)
+        ]
+    );
+    assert_eq!(ledger_state.spending_transaction(&genesis_id), Some(tx_1.id()));
+    assert_eq!(
+        ledger_state.unspent_outputs().map(|(id, _)| *id).collect::<Vec<_>>(),
+        [output_id(tx_2.id(), 0)]
+    );
+
+    ledger_state.apply_milestone(2, 10);
+    assert_eq!(
+        ledger_state.apply_transaction(&tx_2),
+        Ok(ConflictReason::InputUtxoAlreadySpent)
+    );
+}
+
+#[test]
+fn ledger_state_conflicts() {
+    let key = ed25519::SecretKey::generate().unwrap();
+    let other_key = ed25519::SecretKey::generate().unwrap();
+    let genesis_id = output_id(rand_transaction_id(), 0);
+    let genesis = basic_output(address(&key), 1_000_000, None);
+    let mut ledger_state = LedgerState::new(1, 0);
+    ledger_state.add_output(genesis_id, genesis.clone());
+
+    assert_eq!(
+        ledger_state.apply_transaction(&transaction(
+            &key,
+            &[(genesis_id, &genesis)],
+            [basic_output(address(&key), 999_999, None)]
+        )),
+        Ok(ConflictReason::CreatedConsumedAmountMismatch)
+    );
+    assert_eq!(
+        ledger_state.apply_transaction(&transaction(
+            &other_key,
+            &[(genesis_id, &genesis)],
+            [basic_output(address(&other_key), 1_000_000, None)]
+        )),
+        Ok(ConflictReason::InvalidSignature)
+    );
+    // Conflicting transactions leave the ledger state untouched.
+    assert_eq!(ledger_state.unspent_output(&genesis_id), Some(&genesis));
+}
+
+#[test]
+fn ledger_state_milestone_timestamp() {
+    let key = ed25519::SecretKey::generate().unwrap();
+    let locked_id = output_id(rand_transaction_id(), 0);
+    let locked = basic_output(address(&key), 1_000_000, Some(100));
+    let mut ledger_state = LedgerState::new(1, 50);
+    ledger_state.add_output(locked_id, locked.clone());
+
+    let tx = transaction(
+        &key,
+        &[(locked_id, &locked)],
+        [basic_output(address(&key), 1_000_000, None)],
+    );
+
+    assert_eq!(
+        ledger_state.apply_transaction(&tx),
+        Ok(ConflictReason::TimelockNotExpired)
+    );
+    ledger_state.apply_milestone(2, 100);
+    assert_eq!(ledger_state.apply_transaction(&tx), Ok(ConflictReason::None));
+    assert_eq!(ledger_state.unspent_output(&locked_id), None);
+}
@@ -8,7 +8,9 @@ mod block;
 mod block_id;
 mod ed25519_signature;
 mod foundry_id;
+mod inclusion_proof;
 mod input;
+mod ledger_state;
 mod migrated_funds_entry;
 mod milestone_id;
 mod milestone_index;
@@ -3,14 +3,17 @@
 
 use std::path::PathBuf;
 
-use crypto::keys::bip39::Mnemonic;
+use crypto::keys::bip39::{Mnemonic, Passphrase};
 use iota_sdk::{
     client::{
         constants::{IOTA_COIN_TYPE, SHIMMER_COIN_TYPE},
         node_manager::node::{Node, NodeDto},
         secret::{mnemonic::MnemonicSecretManager, stronghold::StrongholdSecretManager, SecretManager},
+        slip39::ShareGroup,
+        utils::Password,
+        Client,
     },
-    wallet::{ClientOptions, Result, Wallet},
+    wallet::{ClientOptions, Error, Result, Wallet},
     Url,
 };
 
@@ -568,3 +571,117 @@ async fn restore_no_secret_manager_data() -> Result<()> {
     );
     tear_down(storage_path)
 }
+
+// Backup the mnemonic in SLIP-39 shares and recover it from the required ones
+#[tokio::test]
+async fn backup_and_combine_mnemonic_shares() -> Result<()> {
+    iota_stronghold::engine::snapshot::try_set_encrypt_work_factor(0).unwrap();
+
+    let storage_path = "test-storage/backup_and_combine_mnemonic_shares";
+    setup(storage_path)?;
+
+    let mnemonic = Mnemonic::from("inhale gorilla deny three celery song category owner lottery rent author wealth penalty crawl hobby obtain glad warm early rain clutch slab august bleak".to_string());
+    let passphrase = Passphrase::from("passphrase".to_owned());
+    let secret_manager =
+        MnemonicSecretManager::try_from_mnemonic_with_passphrase(mnemonic.clone(), passphrase.clone())?;
+
+    let wallet = Wallet::builder()
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .with_client_options(ClientOptions::new().with_node(NODE_LOCAL)?)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+
+    let shares_path = format!("{storage_path}/shares");
+    let groups = [ShareGroup::new(1, 1), ShareGroup::new(2, 3), ShareGroup::new(2, 2)];
+    let share_passwords = groups
+        .iter()
+        .enumerate()
+        .map(|(group_index, group)| {
+            (0..group.member_count)
+                .map(|member_index| Password::from(format!("share password {group_index} {member_index}")))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // The mnemonic and passphrase must be the ones of the wallet
+    for (mnemonic, passphrase) in [
+        (Client::generate_mnemonic()?, Some(passphrase.clone())),
+        (mnemonic.clone(), None),
+    ] {
+        assert!(matches!(
+            wallet
+                .backup_mnemonic_shares(mnemonic, passphrase, 2, &groups, &shares_path, share_passwords.clone())
+                .await,
+            Err(Error::Backup(_))
+        ));
+    }
+
+    // Every share needs a password
+    assert!(matches!(
+        wallet
+            .backup_mnemonic_shares(
+                mnemonic.clone(),
+                passphrase.clone(),
+                2,
+                &groups,
+                &shares_path,
+                share_passwords[..2].to_vec(),
+            )
+            .await,
+        Err(Error::Backup(_))
+    ));
+
+    let paths = wallet
+        .backup_mnemonic_shares(
+            mnemonic.clone(),
+            passphrase.clone(),
+            2,
+            &groups,
+            &shares_path,
+            share_passwords.clone(),
+        )
+        .await?;
+    assert_eq!(
+        paths[1][2],
+        PathBuf::from(format!("{shares_path}/share_2_3.stronghold"))
+    );
+
+    // Shares aren't written next to the ones of an earlier split
+    assert!(matches!(
+        wallet
+            .backup_mnemonic_shares(
+                mnemonic.clone(),
+                passphrase,
+                2,
+                &groups,
+                &shares_path,
+                share_passwords.clone(),
+            )
+            .await,
+        Err(Error::Backup(_))
+    ));
+
+    // The password of another share fails
+    Wallet::read_mnemonic_share(&paths[1][0], share_passwords[1][1].clone())
+        .await
+        .unwrap_err();
+
+    let mut shares = Vec::new();
+    for (group_index, member_index) in [(1, 0), (2, 1), (1, 2), (2, 0)] {
+        shares.push(
+            Wallet::read_mnemonic_share(
+                &paths[group_index][member_index],
+                share_passwords[group_index][member_index].clone(),
+            )
+            .await?,
+        );
+    }
+    assert_eq!(Client::combine_mnemonic_shares(&shares)?.as_ref(), mnemonic.as_ref());
+
+    // A single group isn't enough
+    assert!(Client::combine_mnemonic_shares(&shares[..1]).is_err());
+
+    tear_down(storage_path)
+}
@@ -4,6 +4,8 @@
 mod constants;
 
 use crypto::keys::bip39::Mnemonic;
+#[cfg(feature = "mock_node")]
+use iota_sdk::client::mock_node::MockNode;
 use iota_sdk::{
     client::{
         constants::SHIMMER_COIN_TYPE,
@@ -47,6 +49,26 @@ pub(crate) async fn make_wallet(storage_path: &str, mnemonic: Option<Mnemonic>,
     wallet_builder.finish().await
 }
 
+/// Creates a new wallet with a random mnemonic, SHIMMER_COIN_TYPE and a storage path, connected to a mock node.
+#[allow(dead_code, unused_variables)]
+#[cfg(feature = "mock_node")]
+pub(crate) async fn make_mock_node_wallet(storage_path: &str, node: &MockNode) -> Result<Wallet> {
+    let client_options = ClientOptions::new().with_node(node.url())?.with_local_pow(false);
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+
+    #[allow(unused_mut)]
+    let mut wallet_builder = Wallet::builder()
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .with_client_options(client_options)
+        .with_coin_type(SHIMMER_COIN_TYPE);
+    #[cfg(feature = "storage")]
+    {
+        wallet_builder = wallet_builder.with_storage_path(storage_path);
+    }
+
+    wallet_builder.finish().await
+}
+
 #[allow(dead_code, unused_variables)]
 #[cfg(feature = "ledger_nano")]
 pub(crate) async fn make_ledger_nano_wallet(storage_path: &str, node: Option<&str>) -> Result<Wallet> {
@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_sdk::{
-    client::api::PreparedTransactionDataDto,
+    client::api::{
+        input_selection::{
+            RejectedInput, RejectionReason, Requirement, RequirementTrace, SelectionTrace, SelectionTraceDto,
+        },
+        PreparedTransactionDataDto,
+    },
     types::block::{
         address::{Address, Bech32Address, Ed25519Address},
         input::{Input, UtxoInput},
@@ -14,8 +19,8 @@ use iota_sdk::{
     wallet::{
         account::types::{InclusionState, OutputData, OutputDataDto},
         events::types::{
-            AddressData, NewOutputEvent, SpentOutputEvent, TransactionInclusionEvent, TransactionProgressEvent,
-            WalletEvent,
+            AddressData, NewOutputEvent, PaymentStatus, PaymentStatusEvent, ScheduledPaymentExecutedEvent,
+            SpentOutputEvent, TransactionInclusionEvent, TransactionProgressEvent, WalletEvent,
         },
     },
 };
@@ -121,4 +126,54 @@ fn wallet_events_serde() {
     ));
 
     assert_serde_eq(WalletEvent::TransactionProgress(TransactionProgressEvent::Broadcasting));
+
+    let output_id = OutputId::new(TransactionId::new(prefix_hex::decode(TRANSACTION_ID).unwrap()), 0).unwrap();
+    let trace = SelectionTrace {
+        requirements: vec![RequirementTrace {
+            requirement: Requirement::Amount,
+            selected_inputs: vec![output_id],
+            fulfilled: false,
+        }],
+        rejected_inputs: vec![
+            RejectedInput {
+                output_id,
+                reason: RejectionReason::Expiring { expires_at: 100 },
+            },
+            RejectedInput {
+                output_id,
+                reason: RejectionReason::StorageDepositReturn {
+                    return_address: Address::Ed25519(Ed25519Address::new(prefix_hex::decode(ED25519_ADDRESS).unwrap())),
+                    amount: 42600,
+                },
+            },
+        ],
+    };
+
+    assert_serde_eq(WalletEvent::TransactionProgress(
+        TransactionProgressEvent::SelectingInputsFailed(Box::new(SelectionTraceDto::from(&trace))),
+    ));
+    assert_serde_eq(WalletEvent::PaymentStatus(PaymentStatusEvent {
+        payment_id: 0,
+        status: PaymentStatus::Queued,
+    }));
+
+    assert_serde_eq(WalletEvent::PaymentStatus(PaymentStatusEvent {
+        payment_id: 1,
+        status: PaymentStatus::Sent {
+            transaction_id: TransactionId::new(prefix_hex::decode(TRANSACTION_ID).unwrap()),
+        },
+    }));
+
+    assert_serde_eq(WalletEvent::PaymentStatus(PaymentStatusEvent {
+        payment_id: 2,
+        status: PaymentStatus::Failed {
+            error: "insufficient funds 0/1000000 available".to_string(),
+        },
+    }));
+
+    assert_serde_eq(WalletEvent::ScheduledPaymentExecuted(ScheduledPaymentExecutedEvent {
+        payment_id: 0,
+        instalment: 3,
+        transaction_id: TransactionId::new(prefix_hex::decode(TRANSACTION_ID).unwrap()),
+    }));
 }
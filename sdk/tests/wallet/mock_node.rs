@@ -0,0 +1,685 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        api::input_selection::RejectionReason,
+        mock_node::{MockNode, DEFAULT_FAUCET_AMOUNT},
+        request_funds_from_faucet,
+    },
+    types::block::{
+        address::{Bech32Address, Hrp},
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, ExpirationUnlockCondition, GovernorAddressUnlockCondition,
+                StateControllerAddressUnlockCondition, StorageDepositReturnUnlockCondition, UnlockCondition,
+            },
+            AliasId, AliasOutputBuilder, BasicOutputBuilder, NftId, NftOutputBuilder, OutputId,
+        },
+    },
+    wallet::{
+        account::{
+            types::InclusionState, Features, MissedInstalments, OutputParams, PaymentSchedule, SweepOptions,
+            SyncOptions, TransactionOptions,
+        },
+        Error, MintNftParams, Result, SendParams,
+    },
+};
+
+use crate::wallet::common::{make_mock_node_wallet, setup, tear_down};
+
+#[tokio::test]
+async fn mock_node_send_amount() -> Result<()> {
+    let storage_path = "test-storage/mock_node_send_amount";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT);
+
+    let amount = 1_000_000;
+    let tx = account_0
+        .send(amount, *account_1.addresses().await?[0].address(), None)
+        .await?;
+
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT - amount);
+    assert_eq!(
+        account_0
+            .get_transaction(&tx.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Confirmed
+    );
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), amount);
+
+    // Input selection errors keep their variant.
+    assert!(matches!(
+        account_1
+            .send(2 * amount, *account_0.addresses().await?[0].address(), None)
+            .await,
+        Err(Error::InsufficientFunds { available, required }) if available == amount && required == 2 * amount
+    ));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_payment_queue() -> Result<()> {
+    let storage_path = "test-storage/mock_node_payment_queue";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    account_0.sync(None).await?;
+
+    assert!(account_0.flush_payment_queue(None).await?.is_none());
+
+    let amount = 1_000_000;
+    let address = *account_1.addresses().await?[0].address();
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        ids.push(account_0.queue_payment(SendParams::new(amount, address)?).await?);
+    }
+    assert_eq!(ids, [0, 1, 2]);
+
+    let removed = account_0.remove_queued_payment(ids[1]).await?.unwrap();
+    assert_eq!(removed.id, ids[1]);
+    assert!(account_0.remove_queued_payment(ids[1]).await?.is_none());
+    assert_eq!(account_0.queued_payments().await.len(), 2);
+
+    // Payments to another network are refused, micro amounts are only sent if allowed.
+    let foreign_address = Bech32Address::new(Hrp::from_str_unchecked("foreign"), *address.inner());
+    assert!(account_0
+        .queue_payment(SendParams::new(amount, foreign_address)?)
+        .await
+        .is_err());
+    account_0.queue_payment(SendParams::new(1, address)?).await?;
+
+    let tx = account_0.flush_payment_queue(None).await?.unwrap();
+    assert!(account_0.queued_payments().await.is_empty());
+    // Both payments and a remainder.
+    assert_eq!(tx.payload.essence().as_regular().outputs().len(), 3);
+
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT - 2 * amount);
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 2 * amount);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_sweep() -> Result<()> {
+    let storage_path = "test-storage/mock_node_sweep";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    // Nothing to sweep.
+    assert!(account_0
+        .sweep(*account_1.addresses().await?[0].address(), None)
+        .await?
+        .transactions
+        .is_empty());
+
+    request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    account_0.sync(None).await?;
+
+    account_0.mint_nfts([MintNftParams::new()], None).await?;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.nfts().len(), 1);
+
+    let sweep = account_0
+        .sweep(*account_1.addresses().await?[0].address(), None)
+        .await?;
+    assert_eq!(sweep.transactions.len(), 1);
+    assert!(sweep.error.is_none());
+
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), 0);
+    assert!(balance.nfts().is_empty());
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), DEFAULT_FAUCET_AMOUNT);
+    assert_eq!(balance.nfts().len(), 1);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_sweep_aliases() -> Result<()> {
+    let storage_path = "test-storage/mock_node_sweep_aliases";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let account_0_address = *account_0.addresses().await?[0].address();
+    let account_1_address = *account_1.addresses().await?[0].address();
+    let token_supply = account_1.client().get_token_supply().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), &account_1_address).await?;
+    account_1.sync(None).await?;
+
+    // An alias with the highest amount, so it comes first, and a micro amount that leaves a remainder below the
+    // storage deposit of an output of its own.
+    let outputs = [
+        AliasOutputBuilder::new_with_amount(2_000_000, AliasId::null())
+            .with_unlock_conditions([
+                UnlockCondition::from(StateControllerAddressUnlockCondition::new(account_0_address)),
+                UnlockCondition::from(GovernorAddressUnlockCondition::new(account_0_address)),
+            ])
+            .finish_output(token_supply)?,
+        BasicOutputBuilder::new_with_amount(1_000_001)
+            .with_unlock_conditions([
+                UnlockCondition::from(AddressUnlockCondition::new(account_0_address)),
+                UnlockCondition::from(StorageDepositReturnUnlockCondition::new(
+                    account_1_address,
+                    1_000_000,
+                    token_supply,
+                )?),
+                UnlockCondition::from(ExpirationUnlockCondition::new(
+                    account_1_address,
+                    account_1.client().get_time_checked().await? + 3600,
+                )?),
+            ])
+            .finish_output(token_supply)?,
+    ];
+    account_1.send_outputs(outputs, None).await?;
+    account_1.sync(None).await?;
+    account_0.sync(None).await?;
+
+    // The alias can't take the remainder, its amount can't change in a governance transition.
+    assert!(matches!(
+        account_0
+            .sweep(account_1_address, SweepOptions::new().with_include_aliases(true))
+            .await,
+        Err(Error::InsufficientFunds { .. })
+    ));
+
+    let outputs = [NftOutputBuilder::new_with_amount(1_000_000, NftId::null())
+        .with_unlock_conditions([AddressUnlockCondition::new(account_0_address)])
+        .finish_output(token_supply)?];
+    account_1.send_outputs(outputs, None).await?;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.aliases().len(), 1);
+    assert_eq!(balance.nfts().len(), 1);
+
+    // The NFT takes the remainder instead.
+    let sweep = account_0
+        .sweep(account_1_address, SweepOptions::new().with_include_aliases(true))
+        .await?;
+    assert_eq!(sweep.transactions.len(), 1);
+    assert!(sweep.error.is_none());
+
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), 0);
+    assert!(balance.aliases().is_empty());
+    assert!(balance.nfts().is_empty());
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), DEFAULT_FAUCET_AMOUNT);
+    assert_eq!(balance.aliases().len(), 1);
+    assert_eq!(balance.nfts().len(), 1);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_last_selection_trace() -> Result<()> {
+    let storage_path = "test-storage/mock_node_last_selection_trace";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let account_0_address = *account_0.addresses().await?[0].address();
+    let account_1_address = *account_1.addresses().await?[0].address();
+
+    request_funds_from_faucet(&node.faucet_url(), &account_1_address).await?;
+    account_1.sync(None).await?;
+
+    let expires_at = account_1.client().get_time_checked().await? + 3600;
+    let outputs = [BasicOutputBuilder::new_with_amount(2_000_000)
+        .with_unlock_conditions([
+            UnlockCondition::from(AddressUnlockCondition::new(account_0_address)),
+            UnlockCondition::from(ExpirationUnlockCondition::new(account_1_address, expires_at)?),
+        ])
+        .finish_output(account_1.client().get_token_supply().await?)?];
+    let tx = account_1.send_outputs(outputs, None).await?;
+    let expiring_output_id = OutputId::new(tx.transaction_id, 0)?;
+    account_0.sync(None).await?;
+
+    assert!(account_0.last_selection_trace().await.is_none());
+    // The only output could still be returned to its sender.
+    assert!(account_0.send(1_000_000, account_1_address, None).await.is_err());
+    assert_eq!(
+        account_0
+            .last_selection_trace()
+            .await
+            .unwrap()
+            .rejection(&expiring_output_id),
+        Some(&RejectionReason::Expiring { expires_at })
+    );
+
+    // A successful selection clears the trace.
+    request_funds_from_faucet(&node.faucet_url(), &account_0_address).await?;
+    account_0
+        .sync(Some(SyncOptions {
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+    account_0.send(1_000_000, account_1_address, None).await?;
+    assert!(account_0.last_selection_trace().await.is_none());
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_replace_transaction() -> Result<()> {
+    let storage_path = "test-storage/mock_node_replace_transaction";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().with_auto_confirm(false).finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    for _ in 0..2 {
+        request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    }
+    node.issue_milestone().await;
+    account_0.sync(None).await?;
+
+    let amount = 1_000_000;
+    let address = *account_1.addresses().await?[0].address();
+    let tx = account_0.send(amount, address, None).await?;
+    assert_eq!(tx.payload.essence().as_regular().inputs().len(), 1);
+
+    // The corrected amount requires the second output as additional input.
+    let outputs = [BasicOutputBuilder::new_with_amount(DEFAULT_FAUCET_AMOUNT + amount)
+        .add_unlock_condition(AddressUnlockCondition::new(address))
+        .finish_output(account_0.client().get_token_supply().await?)?];
+    let replacement = account_0
+        .replace_transaction(
+            &tx.transaction_id,
+            outputs,
+            TransactionOptions {
+                note: Some("corrected".to_owned()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(replacement.payload.essence().as_regular().inputs().len(), 2);
+    assert_eq!(account_0.pending_transactions().await.len(), 2);
+
+    // The replaced transaction was submitted first, so it's the one getting confirmed.
+    node.issue_milestone().await;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 2 * DEFAULT_FAUCET_AMOUNT - amount);
+    assert!(account_0.pending_transactions().await.is_empty());
+
+    let tx = account_0.get_transaction(&tx.transaction_id).await.unwrap();
+    assert_eq!(tx.inclusion_state, InclusionState::Confirmed);
+    assert_eq!(tx.note.as_deref(), Some("corrected"));
+    assert_eq!(
+        account_0
+            .get_transaction(&replacement.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Conflicting
+    );
+
+    assert!(matches!(
+        account_0.replace_transaction(&tx.transaction_id, [], None).await,
+        Err(Error::TransactionNotPending(_))
+    ));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_cancel_transaction() -> Result<()> {
+    let storage_path = "test-storage/mock_node_cancel_transaction";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().with_auto_confirm(false).finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    node.issue_milestone().await;
+    account_0.sync(None).await?;
+
+    let amount = 1_000_000;
+    let tx = account_0
+        .send(amount, *account_1.addresses().await?[0].address(), None)
+        .await?;
+
+    let cancellation = account_0.cancel_transaction(&tx.transaction_id, None).await?;
+    let essence = cancellation.payload.essence().as_regular();
+    assert_eq!(essence.inputs(), tx.payload.essence().as_regular().inputs());
+    assert_eq!(essence.outputs().len(), 1);
+    assert_eq!(essence.outputs()[0].amount(), DEFAULT_FAUCET_AMOUNT);
+    assert_eq!(
+        essence.outputs()[0]
+            .unlock_conditions()
+            .unwrap()
+            .address()
+            .unwrap()
+            .address(),
+        account_0.addresses().await?[0].address().inner()
+    );
+
+    node.issue_milestone().await;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT - amount);
+    assert_eq!(
+        account_0
+            .get_transaction(&cancellation.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Conflicting
+    );
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_replacement_confirmed() -> Result<()> {
+    let storage_path = "test-storage/mock_node_replacement_confirmed";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().with_auto_confirm(false).finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    for _ in 0..2 {
+        request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    }
+    node.issue_milestone().await;
+    account_0.sync(None).await?;
+
+    let amount = 1_000_000;
+    let address = *account_1.addresses().await?[0].address();
+    let tx = account_0
+        .send(
+            amount,
+            address,
+            TransactionOptions {
+                note: Some("original".to_owned()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let outputs = [BasicOutputBuilder::new_with_amount(DEFAULT_FAUCET_AMOUNT + amount)
+        .add_unlock_condition(AddressUnlockCondition::new(address))
+        .finish_output(account_0.client().get_token_supply().await?)?];
+    let replacement = account_0.replace_transaction(&tx.transaction_id, outputs, None).await?;
+
+    // The replaced transaction never gets confirmed, so the replacement is.
+    assert!(node.orphan_block(&tx.block_id.unwrap()).await);
+    node.issue_milestone().await;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT - amount);
+    assert!(account_0.details().await.locked_outputs().is_empty());
+    assert!(account_0.pending_transactions().await.is_empty());
+
+    let replacement = account_0.get_transaction(&replacement.transaction_id).await.unwrap();
+    assert_eq!(replacement.inclusion_state, InclusionState::Confirmed);
+    assert_eq!(replacement.note.as_deref(), Some("original"));
+    assert_eq!(
+        account_0
+            .get_transaction(&tx.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Conflicting
+    );
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT + amount);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_cancellation_confirmed() -> Result<()> {
+    let storage_path = "test-storage/mock_node_cancellation_confirmed";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().with_auto_confirm(false).finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    node.issue_milestone().await;
+    account_0.sync(None).await?;
+
+    let tx = account_0
+        .send(1_000_000, *account_1.addresses().await?[0].address(), None)
+        .await?;
+    let cancellation = account_0.cancel_transaction(&tx.transaction_id, None).await?;
+
+    // The cancelled transaction never gets confirmed, so the cancellation is.
+    assert!(node.orphan_block(&tx.block_id.unwrap()).await);
+    node.issue_milestone().await;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), DEFAULT_FAUCET_AMOUNT);
+    assert!(account_0.details().await.locked_outputs().is_empty());
+    assert!(account_0.pending_transactions().await.is_empty());
+    assert_eq!(
+        account_0
+            .get_transaction(&cancellation.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Confirmed
+    );
+    assert_eq!(
+        account_0
+            .get_transaction(&tx.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Conflicting
+    );
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 0);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_scheduled_payments() -> Result<()> {
+    let storage_path = "test-storage/mock_node_scheduled_payments";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), account_0.addresses().await?[0].address()).await?;
+    account_0.sync(None).await?;
+
+    let amount = 1_000_000;
+    let params = OutputParams {
+        recipient_address: *account_1.addresses().await?[0].address(),
+        amount,
+        assets: None,
+        features: None,
+        unlocks: None,
+        storage_deposit: None,
+    };
+    let now = account_0.client().get_time_checked().await?;
+
+    assert!(matches!(
+        account_0
+            .schedule_payment(params.clone(), PaymentSchedule::at(now).with_instalments(2))
+            .await,
+        Err(Error::InvalidPaymentSchedule(_))
+    ));
+    assert!(account_0.execute_scheduled_payments(None).await?.is_none());
+
+    // Three of the five instalments are due, they are all sent.
+    let recurring = account_0
+        .schedule_payment(
+            params.clone(),
+            PaymentSchedule::recurring(now - 25, 10)
+                .with_instalments(5)
+                .with_missed_instalments(MissedInstalments::PayAll),
+        )
+        .await?;
+    // Three instalments are due, a single one is sent by default.
+    let catching_up = account_0
+        .schedule_payment(params.clone(), PaymentSchedule::recurring(now - 25, 10))
+        .await?;
+    let single = account_0
+        .schedule_payment(params.clone(), PaymentSchedule::at(now + 3600))
+        .await?;
+    // Its output can't be built, which doesn't hold back the others.
+    let failing = account_0
+        .schedule_payment(
+            OutputParams {
+                features: Some(Features {
+                    tag: Some("not hex".to_string()),
+                    ..Default::default()
+                }),
+                ..params.clone()
+            },
+            PaymentSchedule::at(now - 25),
+        )
+        .await?;
+    assert_eq!(account_0.scheduled_payments().await.len(), 4);
+
+    let tx = account_0.execute_scheduled_payments(None).await?.unwrap();
+    // The instalments and a remainder.
+    assert_eq!(tx.payload.essence().as_regular().outputs().len(), 5);
+    assert!(account_0.execute_scheduled_payments(None).await?.is_none());
+
+    let scheduled_payments = account_0.scheduled_payments().await;
+    for scheduled_payment in &scheduled_payments[..2] {
+        assert_eq!(scheduled_payment.executed_instalments, 3);
+        assert_eq!(scheduled_payment.last_transaction_id, Some(tx.transaction_id));
+        assert_eq!(scheduled_payment.next_due_time(), Some(now + 5));
+    }
+
+    assert_eq!(scheduled_payments[3].executed_instalments, 0);
+    assert!(scheduled_payments[3].next_due_time().is_some());
+
+    assert_eq!(
+        account_0.cancel_scheduled_payment(single.id).await?.unwrap().id,
+        single.id
+    );
+    assert_eq!(
+        account_0.cancel_scheduled_payment(failing.id).await?.unwrap().id,
+        failing.id
+    );
+    assert!(account_0.cancel_scheduled_payment(single.id).await?.is_none());
+    assert_eq!(
+        account_0.cancel_scheduled_payment(catching_up.id).await?.unwrap().id,
+        catching_up.id
+    );
+    assert_eq!(account_0.scheduled_payments().await[0].id, recurring.id);
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 4 * amount);
+
+    account_0.sync(None).await?;
+    // Timelocked instalments are sent right away.
+    let tx = account_0
+        .send_timelocked_payment(
+            params,
+            PaymentSchedule::recurring(now + 3600, 3600).with_instalments(2),
+            None,
+        )
+        .await?;
+    let timelocks = tx
+        .payload
+        .essence()
+        .as_regular()
+        .outputs()
+        .iter()
+        .filter_map(|output| output.unlock_conditions()?.timelock())
+        .map(|timelock| timelock.timestamp())
+        .collect::<Vec<_>>();
+    assert_eq!(timelocks, [now + 3600, now + 7200]);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn mock_node_transaction_batch_resubmit() -> Result<()> {
+    let storage_path = "test-storage/mock_node_transaction_batch_resubmit";
+    setup(storage_path)?;
+
+    let node = MockNode::builder().finish().await.unwrap();
+    let wallet = make_mock_node_wallet(storage_path, &node).await?;
+
+    let account = wallet.create_account().finish().await?;
+
+    request_funds_from_faucet(&node.faucet_url(), account.addresses().await?[0].address()).await?;
+    account.sync(None).await?;
+
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(*account.addresses().await?[0].address()))
+        .finish_output(account.client().get_token_supply().await?)?;
+    let mut batch = account.plan_transaction_batch(vec![output; 2]).await?;
+
+    // The transaction was signed, but not submitted before a crash.
+    let prepared_transaction_data = account
+        .prepare_transaction(batch.transactions[0].outputs.clone(), None)
+        .await?;
+    let signed_transaction_data = account.sign_transaction_essence(&prepared_transaction_data).await?;
+    let transaction_id = signed_transaction_data.transaction_payload.id();
+    batch.transactions[0].signed_transaction_data = Some(signed_transaction_data);
+
+    // It's submitted as it is instead of being prepared again.
+    let batch = account.process_transaction_batch(batch, None).await?;
+    assert!(batch.is_finished());
+    assert_eq!(batch.transactions[0].transaction_id(), Some(transaction_id));
+    assert_eq!(
+        account.get_transaction(&transaction_id).await.unwrap().inclusion_state,
+        InclusionState::Confirmed
+    );
+
+    tear_down(storage_path)
+}
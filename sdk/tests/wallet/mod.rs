@@ -21,9 +21,12 @@ mod error;
 mod events;
 #[cfg(feature = "stronghold")]
 mod migrate_stronghold_snapshot_v2_to_v3;
+#[cfg(feature = "mock_node")]
+mod mock_node;
 mod native_tokens;
 mod output_preparation;
 mod syncing;
+mod transaction_batch;
 mod transactions;
 #[cfg(not(target_os = "windows"))]
 #[cfg(feature = "rocksdb")]
@@ -0,0 +1,91 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::{
+        block::{
+            address::Address,
+            output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, Output},
+            protocol::protocol_parameters,
+        },
+        TryFromDto,
+    },
+    wallet::account::{TransactionBatch, TransactionBatchDto},
+};
+
+fn outputs(count: usize) -> Vec<Output> {
+    let address = Address::try_from_bech32("rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy").unwrap();
+
+    (0..count)
+        .map(|index| {
+            BasicOutputBuilder::new_with_amount(1_000_000 + index as u64)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .finish_output(protocol_parameters().token_supply())
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn batch_single_transaction() {
+    let batch = TransactionBatch::new(outputs(127)).unwrap();
+
+    assert_eq!(batch.transactions.len(), 1);
+    assert_eq!(batch.transactions[0].outputs.len(), 127);
+    assert!(!batch.is_finished());
+    assert_eq!(batch.confirmed_count(), 0);
+}
+
+#[test]
+fn batch_multiple_transactions() {
+    let outputs = outputs(300);
+    let mut batch = TransactionBatch::new(outputs.clone()).unwrap();
+
+    // One output per transaction is left for the remainder.
+    assert_eq!(
+        batch
+            .transactions
+            .iter()
+            .map(|transaction| transaction.outputs.len())
+            .collect::<Vec<_>>(),
+        [127, 127, 46]
+    );
+    // The outputs are sent in the order they were provided.
+    assert_eq!(
+        batch
+            .transactions
+            .iter()
+            .flat_map(|transaction| transaction.outputs.clone())
+            .collect::<Vec<_>>(),
+        outputs
+    );
+
+    for transaction in &mut batch.transactions {
+        transaction.confirmed = true;
+    }
+
+    assert!(batch.is_finished());
+    assert_eq!(batch.confirmed_count(), 3);
+}
+
+#[test]
+fn batch_unique_id() {
+    assert_ne!(
+        TransactionBatch::new(outputs(1)).unwrap().id,
+        TransactionBatch::new(outputs(1)).unwrap().id
+    );
+}
+
+#[test]
+fn batch_dto_roundtrip() {
+    let mut batch = TransactionBatch::new(outputs(200)).unwrap();
+    batch.transactions[0].confirmed = true;
+
+    let dto = TransactionBatchDto::from(&batch);
+    let json = serde_json::to_string(&dto).unwrap();
+
+    assert_eq!(
+        TransactionBatch::try_from_dto(serde_json::from_str::<TransactionBatchDto>(&json).unwrap()).unwrap(),
+        batch
+    );
+}
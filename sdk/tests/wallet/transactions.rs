@@ -70,6 +70,36 @@ async fn send_amount_127_outputs() -> Result<()> {
     tear_down(storage_path)
 }
 
+#[ignore]
+#[tokio::test]
+async fn send_amount_300_outputs_batched() -> Result<()> {
+    let storage_path = "test-storage/send_amount_300_outputs_batched";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+
+    let amount = 1_000_000;
+    let batch = account_0
+        .send_with_params_batched(
+            vec![SendParams::new(amount, *account_1.addresses().await?[0].address())?; 300],
+            None,
+        )
+        .await?;
+
+    assert_eq!(batch.transactions.len(), 3);
+    assert!(batch.is_finished());
+    #[cfg(feature = "storage")]
+    assert!(account_0.transaction_batches().await?.is_empty());
+
+    let balance = account_1.sync(None).await.unwrap();
+    assert_eq!(balance.base_coin().available(), 300 * amount);
+
+    tear_down(storage_path)
+}
+
 #[ignore]
 #[tokio::test]
 async fn send_amount_custom_input() -> Result<()> {